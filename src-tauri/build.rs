@@ -12,5 +12,20 @@ fn main() {
   println!("cargo:rerun-if-env-changed=NEXT_PUBLIC_POSTHOG_KEY");
   println!("cargo:rerun-if-env-changed=NEXT_PUBLIC_POSTHOG_HOST");
 
-  tauri_build::build()
+  // The in-app `posthog` plugin (src/analytics/mod.rs) needs its commands
+  // declared so capabilities can grant them. Without the `analytics`
+  // feature there's no plugin, and its capability is left out too
+  // (`context()` in src/lib.rs).
+  let mut attributes = tauri_build::Attributes::new();
+  if std::env::var_os("CARGO_FEATURE_ANALYTICS").is_some() {
+    attributes = attributes.plugin(
+      "posthog",
+      tauri_build::InlinedPlugin::new()
+        .commands(&["capture", "reset", "get_distinct_id"])
+        .default_permission(tauri_build::DefaultPermissionRule::AllowAllCommands),
+    );
+  }
+  println!("cargo:rerun-if-changed=optional-capabilities");
+
+  tauri_build::try_build(attributes).expect("failed to run tauri-build")
 }
@@ -0,0 +1,372 @@
+//! Anonymous usage analytics, sent to PostHog only if the user agreed
+//! (see `docs/00-analytics-north-star-metric.md`).
+//!
+//! This is the `posthog` plugin the webview captures through, in place of
+//! `tauri-plugin-posthog`: every event is dropped unless sharing is on,
+//! and events go out under a random id that is thrown away whenever
+//! sharing is turned off, rather than the machine id.
+//!
+//! Captured events are scrubbed of anything the user typed and put in a
+//! [`queue`] on disk, which is sent in batches every so often and retried
+//! with backoff while PostHog can't be reached.
+
+mod posthog;
+mod queue;
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use tauri::plugin::{Builder, TauriPlugin};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::{config, protected, util};
+
+use posthog::{Client, Failure};
+use queue::{Queue, QueuedEvent};
+
+/// Emitted with the new consent when it changes.
+pub const CONSENT_EVENT: &str = "analytics-consent-changed";
+/// The user's answer, missing until they've given one. A [`protected`]
+/// file, so page scripts can't agree on their behalf.
+const CONSENT_FILE: &str = "analytics-consent.json";
+const SETTINGS_STORE_FILE: &str = "analytics-settings.json";
+/// Where earlier builds kept the answer; see `take_legacy_refusal`.
+const LEGACY_CONSENT_STORE_KEY: &str = "consent";
+const DISTINCT_ID_STORE_KEY: &str = "distinctId";
+const QUEUE_FILE: &str = "analytics-queue.json";
+/// How often queued events are sent, unless a full batch is waiting.
+const SEND_INTERVAL: Duration = Duration::from_secs(60);
+/// Properties that can hold what the user typed, such as the task a
+/// timer was started for. Compared without case, `_` or `-`.
+const SCRUBBED_PROPERTIES: &[&str] = &[
+  "task",
+  "taskdescription",
+  "description",
+  "title",
+  "note",
+  "notes",
+  "text",
+  "label",
+  "message",
+];
+
+struct AnalyticsState {
+  client: Client,
+  queue: Mutex<Queue>,
+  /// Wakes the sender early once a full batch is waiting.
+  batch_ready: tokio::sync::Notify,
+}
+
+/// Baked in by `build.rs`; without one nothing is sent.
+fn api_key() -> &'static str {
+  option_env!("NEXT_PUBLIC_POSTHOG_KEY").unwrap_or("")
+}
+
+fn host<R: Runtime>(app: &AppHandle<R>) -> String {
+  // Debug builds can be pointed at a stand-in server.
+  #[cfg(debug_assertions)]
+  if let Ok(host) = std::env::var("ZEROHZ_ANALYTICS_HOST") {
+    return host;
+  }
+  if let Some(host) = config::get(app).analytics_host() {
+    return host.to_string();
+  }
+  option_env!("NEXT_PUBLIC_POSTHOG_HOST")
+    .unwrap_or(posthog::DEFAULT_HOST)
+    .to_string()
+}
+
+/// The answer earlier builds kept in the store, where the webview could
+/// write it. Only a no is carried over; anyone who said yes is asked again.
+fn take_legacy_refusal<R: Runtime>(app: &AppHandle<R>, path: &std::path::Path) -> Option<bool> {
+  let store = app.store(SETTINGS_STORE_FILE).ok()?;
+  let answer = store.get(LEGACY_CONSENT_STORE_KEY)?.as_bool();
+  store.delete(LEGACY_CONSENT_STORE_KEY);
+  if let Err(e) = store.save() {
+    log::warn!("Failed to save {}: {}", SETTINGS_STORE_FILE, e);
+  }
+  if answer != Some(false) {
+    return None;
+  }
+  if let Err(e) = util::write_switch(path, false) {
+    log::warn!("Failed to save {}: {}", path.display(), e);
+  }
+  Some(false)
+}
+
+/// The user's answer, or `None` if they haven't been asked yet.
+pub fn consent<R: Runtime>(app: &AppHandle<R>) -> Option<bool> {
+  let path = protected::path(app, CONSENT_FILE).ok()?;
+  util::read_switch(&path).or_else(|| take_legacy_refusal(app, &path))
+}
+
+/// Also off, whatever the user said, if the configuration turns it off.
+pub fn is_enabled<R: Runtime>(app: &AppHandle<R>) -> bool {
+  config::get(app).analytics_enabled() && consent(app) == Some(true) && !api_key().is_empty()
+}
+
+/// Turning sharing off also forgets the id and anything not sent yet, so
+/// turning it back on starts over as someone new.
+pub fn set_consent<R: Runtime>(app: &AppHandle<R>, enabled: bool) -> Result<(), String> {
+  let path = protected::path(app, CONSENT_FILE)?;
+  util::write_switch(&path, enabled).map_err(|e| format!("Failed to save {}: {}", path.display(), e))?;
+  if !enabled {
+    if let Ok(mut queue) = app.state::<AnalyticsState>().queue.lock() {
+      queue.clear();
+    }
+    reset_distinct_id(app)?;
+  }
+  if let Err(e) = app.emit(CONSENT_EVENT, enabled) {
+    log::warn!("Failed to emit {}: {}", CONSENT_EVENT, e);
+  }
+  Ok(())
+}
+
+fn reset_distinct_id<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+  let store = app.store(SETTINGS_STORE_FILE).map_err(|e| e.to_string())?;
+  store.delete(DISTINCT_ID_STORE_KEY);
+  store.save().map_err(|e| e.to_string())
+}
+
+/// Random, created on first use.
+fn distinct_id<R: Runtime>(app: &AppHandle<R>) -> Result<String, String> {
+  let store = app.store(SETTINGS_STORE_FILE).map_err(|e| e.to_string())?;
+  if let Some(id) = store.get(DISTINCT_ID_STORE_KEY).and_then(|v| v.as_str().map(str::to_string)) {
+    return Ok(id);
+  }
+  let id = util::random_hex(16)?;
+  store.set(DISTINCT_ID_STORE_KEY, id.clone());
+  store.save().map_err(|e| e.to_string())?;
+  Ok(id)
+}
+
+/// A version 4 UUID.
+fn event_uuid() -> Result<String, String> {
+  let mut bytes = [0u8; 16];
+  getrandom::fill(&mut bytes).map_err(|e| e.to_string())?;
+  bytes[6] = (bytes[6] & 0x0f) | 0x40;
+  bytes[8] = (bytes[8] & 0x3f) | 0x80;
+  let hex = util::hex(&bytes);
+  Ok(format!(
+    "{}-{}-{}-{}-{}",
+    &hex[..8],
+    &hex[8..12],
+    &hex[12..16],
+    &hex[16..20],
+    &hex[20..]
+  ))
+}
+
+/// Drop what the user may have typed, and PostHog's own `$` properties,
+/// which only this module sets.
+fn scrub(properties: &mut Map<String, Value>) {
+  properties.retain(|key, _| {
+    let normalized: String = key
+      .chars()
+      .filter(|c| *c != '_' && *c != '-')
+      .flat_map(char::to_lowercase)
+      .collect();
+    !key.starts_with('$') && !SCRUBBED_PROPERTIES.contains(&normalized.as_str())
+  });
+  for value in properties.values_mut() {
+    if let Value::Object(nested) = value {
+      scrub(nested);
+    }
+  }
+}
+
+/// Queue `event` if sharing is on; otherwise drop it.
+pub fn capture<R: Runtime>(app: &AppHandle<R>, event: &str, mut properties: Map<String, Value>) {
+  if !is_enabled(app) {
+    return;
+  }
+  let (distinct_id, uuid) = match distinct_id(app).and_then(|id| Ok((id, event_uuid()?))) {
+    Ok(ids) => ids,
+    Err(e) => {
+      log::warn!("No analytics id: {}", e);
+      return;
+    }
+  };
+  scrub(&mut properties);
+  properties.insert("$lib".to_string(), json!("zerohz-desktop"));
+  properties.insert("$lib_version".to_string(), json!(app.package_info().version.to_string()));
+  // Anonymous events: no person profile behind the id.
+  properties.insert("$process_person_profile".to_string(), json!(false));
+
+  let state = app.state::<AnalyticsState>();
+  let Ok(mut queue) = state.queue.lock() else {
+    return;
+  };
+  let evicted = queue.push(QueuedEvent {
+    uuid,
+    event: event.to_string(),
+    distinct_id,
+    properties,
+    timestamp: chrono::Utc::now().to_rfc3339(),
+  });
+  if evicted > 0 {
+    log::warn!("Analytics queue is full; dropped {} old events", evicted);
+  }
+  if queue.len() >= queue::BATCH_SIZE {
+    state.batch_ready.notify_one();
+  }
+}
+
+/// Send whatever is queued, a batch at a time, until it's empty or PostHog
+/// can't be reached.
+async fn send_queued<R: Runtime>(app: &AppHandle<R>) -> Result<(), Failure> {
+  let state = app.state::<AnalyticsState>();
+  loop {
+    let batch = match state.queue.lock() {
+      Ok(queue) => queue.next_batch(),
+      Err(_) => return Ok(()),
+    };
+    if batch.is_empty() {
+      return Ok(());
+    }
+    match state.client.send(api_key(), &batch).await {
+      Ok(()) => {}
+      // Trying again wouldn't help, and would hold up everything after it.
+      Err(Failure::Rejected(status)) => {
+        log::warn!("PostHog rejected {} events ({}); dropping them", batch.len(), status);
+      }
+      Err(e) => return Err(e),
+    }
+    if let Ok(mut queue) = state.queue.lock() {
+      queue.remove(&batch);
+    }
+  }
+}
+
+async fn keep_sending<R: Runtime>(app: AppHandle<R>) {
+  let state = app.state::<AnalyticsState>();
+  let mut failures = 0;
+  loop {
+    let wait = if failures == 0 {
+      SEND_INTERVAL
+    } else {
+      queue::backoff(failures)
+    };
+    // A full batch doesn't cut a backoff short.
+    tokio::select! {
+      _ = tokio::time::sleep(wait) => {}
+      _ = state.batch_ready.notified(), if failures == 0 => {}
+    }
+    if !is_enabled(&app) {
+      continue;
+    }
+    match send_queued(&app).await {
+      Ok(()) => failures = 0,
+      Err(e) => {
+        failures += 1;
+        log::warn!("{}; retrying in {:?}", e, queue::backoff(failures));
+      }
+    }
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CaptureRequest {
+  event: String,
+  #[serde(default)]
+  properties: Option<Map<String, Value>>,
+}
+
+mod commands {
+  use tauri::{AppHandle, Runtime};
+
+  #[tauri::command]
+  pub(super) fn capture<R: Runtime>(app: AppHandle<R>, request: super::CaptureRequest) {
+    super::capture(&app, &request.event, request.properties.unwrap_or_default());
+  }
+
+  #[tauri::command]
+  pub(super) fn reset<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    super::reset_distinct_id(&app)
+  }
+
+  /// `None` while sharing is off.
+  #[tauri::command]
+  pub(super) fn get_distinct_id<R: Runtime>(app: AppHandle<R>) -> Result<Option<String>, String> {
+    if !super::is_enabled(&app) {
+      return Ok(None);
+    }
+    super::distinct_id(&app).map(Some)
+  }
+}
+
+/// The `posthog` plugin. Its commands are declared for the capability
+/// files in `build.rs`.
+pub fn plugin<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("posthog")
+    .invoke_handler(tauri::generate_handler![
+      commands::capture,
+      commands::reset,
+      commands::get_distinct_id,
+    ])
+    .setup(|app, _api| {
+      let path = match app.path().app_config_dir() {
+        Ok(dir) => Some(dir.join(QUEUE_FILE)),
+        Err(e) => {
+          log::warn!("No place for the analytics queue: {}", e);
+          None
+        }
+      };
+      let mut queue = Queue::load(path);
+      // Turning sharing off clears the queue; don't send any left behind
+      // anyway, e.g. by a crash in between, or once the configuration
+      // turns analytics off.
+      if consent(app) != Some(true) || !config::get(app).analytics_enabled() {
+        queue.clear();
+      }
+      app.manage(AnalyticsState {
+        client: Client::new(&host(app)),
+        queue: Mutex::new(queue),
+        batch_ready: tokio::sync::Notify::new(),
+      });
+      tauri::async_runtime::spawn(keep_sending(app.clone()));
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn scrub_drops_typed_text_and_dollar_properties() {
+    let mut properties = json!({
+      "minutes": 25,
+      "task_description": "Write the report",
+      "Task-Description": "Write the report",
+      "TITLE": "Focus",
+      "$lib": "spoofed",
+      "$set": { "email": "someone@example.com" },
+      "timer": { "preset": "pomodoro", "note": "call mum", "Label": "work" },
+      "sounds": ["rain", "cafe"],
+    });
+    scrub(properties.as_object_mut().unwrap());
+    assert_eq!(
+      properties,
+      json!({
+        "minutes": 25,
+        "timer": { "preset": "pomodoro" },
+        "sounds": ["rain", "cafe"],
+      })
+    );
+  }
+
+  #[test]
+  fn event_uuid_is_version_4() {
+    let uuid = event_uuid().unwrap();
+    let groups: Vec<&str> = uuid.split('-').collect();
+    assert_eq!(groups.iter().map(|g| g.len()).collect::<Vec<_>>(), [8, 4, 4, 4, 12]);
+    assert!(groups[2].starts_with('4'));
+    assert!(matches!(&groups[3][..1], "8" | "9" | "a" | "b"));
+  }
+}
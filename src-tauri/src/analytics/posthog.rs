@@ -0,0 +1,131 @@
+//! Client for PostHog's batch endpoint
+//! (<https://posthog.com/docs/api/capture#batch-events>).
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde_json::json;
+
+use super::queue::QueuedEvent;
+
+pub const DEFAULT_HOST: &str = "https://app.posthog.com";
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Why a batch wasn't taken.
+#[derive(Debug)]
+pub enum Failure {
+  /// Offline, timed out, rate limited or down: worth trying again.
+  Unreachable(String),
+  /// PostHog won't ever take it, e.g. a bad key or a malformed event.
+  Rejected(StatusCode),
+}
+
+impl std::fmt::Display for Failure {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Failure::Unreachable(e) => write!(f, "Couldn't reach PostHog: {}", e),
+      Failure::Rejected(status) => write!(f, "PostHog rejected the events: {}", status),
+    }
+  }
+}
+
+pub struct Client {
+  http: reqwest::Client,
+  batch_url: String,
+}
+
+impl Client {
+  /// `host` is [`DEFAULT_HOST`] unless built with another, or against a
+  /// stand-in server.
+  pub fn new(host: &str) -> Self {
+    let http = reqwest::Client::builder()
+      .timeout(TIMEOUT)
+      .build()
+      .unwrap_or_default();
+    Self {
+      http,
+      batch_url: format!("{}/batch/", host.trim_end_matches('/')),
+    }
+  }
+
+  pub async fn send(&self, api_key: &str, events: &[QueuedEvent]) -> Result<(), Failure> {
+    let body = json!({ "api_key": api_key, "batch": events }).to_string();
+    let response = self
+      .http
+      .post(&self.batch_url)
+      .header(reqwest::header::CONTENT_TYPE, "application/json")
+      .body(body)
+      .send()
+      .await
+      .map_err(|e| Failure::Unreachable(e.to_string()))?;
+    let status = response.status();
+    if status.is_success() {
+      Ok(())
+    } else if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+      Err(Failure::Unreachable(status.to_string()))
+    } else {
+      Err(Failure::Rejected(status))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::{Map, Value};
+
+  use super::*;
+  use crate::test_support::mock_http::{self, Mock};
+
+  /// A stand-in for PostHog. The host has a trailing slash, which is
+  /// trimmed.
+  async fn server() -> (Client, Mock) {
+    let mock = Mock::start().await;
+    (Client::new(&mock.url("/")), mock)
+  }
+
+  fn events() -> Vec<QueuedEvent> {
+    vec![QueuedEvent {
+      uuid: "0190c5d2-0000-4000-8000-000000000000".to_string(),
+      event: "session_completed".to_string(),
+      distinct_id: "someone".to_string(),
+      properties: Map::from_iter([("minutes".to_string(), json!(25))]),
+      timestamp: "2026-03-01T00:00:00+00:00".to_string(),
+    }]
+  }
+
+  #[tokio::test]
+  async fn accepted_batch_carries_the_key_and_events() {
+    let (client, mock) = server().await;
+    client.send("phc_test", &events()).await.unwrap();
+    let request = mock.last_request();
+    assert_eq!(request.path, "/batch/");
+    assert_eq!(request.headers["content-type"], "application/json");
+    let body: Value = serde_json::from_str(&request.body).unwrap();
+    assert_eq!(body["api_key"], "phc_test");
+    assert_eq!(body["batch"], serde_json::to_value(events()).unwrap());
+  }
+
+  #[tokio::test]
+  async fn rejected_batch_is_not_worth_retrying() {
+    let (client, mock) = server().await;
+    for status in [StatusCode::BAD_REQUEST, StatusCode::UNAUTHORIZED] {
+      mock.reply(status.as_u16(), "{}");
+      let result = client.send("phc_test", &events()).await;
+      assert!(matches!(result, Err(Failure::Rejected(s)) if s == status), "{:?}", result);
+    }
+  }
+
+  #[tokio::test]
+  async fn outages_and_rate_limits_are_unreachable() {
+    let (client, mock) = server().await;
+    for status in [500, 503, 429] {
+      mock.reply(status, "{}");
+      let result = client.send("phc_test", &events()).await;
+      assert!(matches!(result, Err(Failure::Unreachable(_))), "{}: {:?}", status, result);
+    }
+
+    let closed = Client::new(&format!("http://{}", mock_http::closed_address()));
+    let result = closed.send("phc_test", &events()).await;
+    assert!(matches!(result, Err(Failure::Unreachable(_))), "{:?}", result);
+  }
+}
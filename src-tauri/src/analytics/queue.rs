@@ -0,0 +1,201 @@
+//! Events waiting to be sent, kept on disk so those captured offline or
+//! just before quitting go out later.
+
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::util;
+
+/// Oldest events are dropped beyond this, so a machine that stays offline
+/// doesn't grow the file forever.
+pub const MAX_EVENTS: usize = 1000;
+/// Most events sent in one request.
+pub const BATCH_SIZE: usize = 50;
+const FIRST_RETRY: Duration = Duration::from_secs(30);
+const LAST_RETRY: Duration = Duration::from_secs(60 * 60);
+
+/// One event, as PostHog's batch endpoint takes it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueuedEvent {
+  /// Lets PostHog drop a batch sent twice, when the answer to the first
+  /// try got lost.
+  pub uuid: String,
+  pub event: String,
+  pub distinct_id: String,
+  pub properties: Map<String, Value>,
+  /// RFC 3339, when it was captured rather than sent.
+  pub timestamp: String,
+}
+
+pub struct Queue {
+  events: VecDeque<QueuedEvent>,
+  path: Option<PathBuf>,
+}
+
+impl Queue {
+  /// What was left in `path` last time; nothing if it's missing or
+  /// unreadable.
+  pub fn load(path: Option<PathBuf>) -> Self {
+    let events = path.as_deref().map(read).unwrap_or_default();
+    Self { events, path }
+  }
+
+  pub fn len(&self) -> usize {
+    self.events.len()
+  }
+
+  /// Returns how many old events were dropped to make room.
+  pub fn push(&mut self, event: QueuedEvent) -> usize {
+    self.events.push_back(event);
+    let evicted = self.events.len().saturating_sub(MAX_EVENTS);
+    self.events.drain(..evicted);
+    self.save();
+    evicted
+  }
+
+  /// The oldest events, up to [`BATCH_SIZE`].
+  pub fn next_batch(&self) -> Vec<QueuedEvent> {
+    self.events.iter().take(BATCH_SIZE).cloned().collect()
+  }
+
+  /// Forget `sent`. Looked up by id, since some may have been evicted
+  /// while they were on their way.
+  pub fn remove(&mut self, sent: &[QueuedEvent]) {
+    let ids: HashSet<&str> = sent.iter().map(|e| e.uuid.as_str()).collect();
+    self.events.retain(|e| !ids.contains(e.uuid.as_str()));
+    self.save();
+  }
+
+  pub fn clear(&mut self) {
+    self.events.clear();
+    if let Some(path) = &self.path {
+      if let Err(e) = std::fs::remove_file(path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+          log::warn!("Failed to remove {}: {}", path.display(), e);
+        }
+      }
+    }
+  }
+
+  fn save(&self) {
+    let Some(path) = &self.path else {
+      return;
+    };
+    if let Err(e) = write(path, &self.events) {
+      log::warn!("Failed to save the analytics queue: {}", e);
+    }
+  }
+}
+
+/// How long to wait after `failures` sends in a row didn't get through:
+/// doubling from 30 seconds up to an hour.
+pub fn backoff(failures: u32) -> Duration {
+  let doublings = failures.saturating_sub(1).min(16);
+  (FIRST_RETRY * 2u32.pow(doublings)).min(LAST_RETRY)
+}
+
+fn read(path: &Path) -> VecDeque<QueuedEvent> {
+  let bytes = match std::fs::read(path) {
+    Ok(bytes) => bytes,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return VecDeque::new(),
+    Err(e) => {
+      log::warn!("Failed to read {}: {}", path.display(), e);
+      return VecDeque::new();
+    }
+  };
+  match serde_json::from_slice::<VecDeque<QueuedEvent>>(&bytes) {
+    Ok(mut events) => {
+      let evicted = events.len().saturating_sub(MAX_EVENTS);
+      events.drain(..evicted);
+      events
+    }
+    Err(e) => {
+      log::warn!("Dropping unreadable {}: {}", path.display(), e);
+      VecDeque::new()
+    }
+  }
+}
+
+fn write(path: &Path, events: &VecDeque<QueuedEvent>) -> Result<(), String> {
+  let json = serde_json::to_vec(events).map_err(|e| e.to_string())?;
+  util::write_atomic(path, &json).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::TempDir;
+
+  fn event(n: usize) -> QueuedEvent {
+    QueuedEvent {
+      uuid: format!("uuid-{}", n),
+      event: "session_completed".to_string(),
+      distinct_id: "someone".to_string(),
+      properties: Map::new(),
+      timestamp: "2026-03-01T00:00:00+00:00".to_string(),
+    }
+  }
+
+  #[test]
+  fn backoff_doubles_up_to_an_hour() {
+    assert_eq!(backoff(0), FIRST_RETRY);
+    assert_eq!(backoff(1), Duration::from_secs(30));
+    assert_eq!(backoff(2), Duration::from_secs(60));
+    assert_eq!(backoff(3), Duration::from_secs(120));
+    assert_eq!(backoff(8), LAST_RETRY);
+    assert_eq!(backoff(u32::MAX), LAST_RETRY);
+  }
+
+  #[test]
+  fn oldest_events_are_evicted_beyond_the_limit() {
+    let mut queue = Queue::load(None);
+    for n in 0..MAX_EVENTS {
+      assert_eq!(queue.push(event(n)), 0);
+    }
+    assert_eq!(queue.push(event(MAX_EVENTS)), 1);
+    assert_eq!(queue.len(), MAX_EVENTS);
+    assert_eq!(queue.next_batch()[0], event(1));
+    assert_eq!(queue.next_batch().len(), BATCH_SIZE);
+  }
+
+  #[test]
+  fn a_file_over_the_limit_keeps_the_newest() {
+    let dir = TempDir::new();
+    let path = dir.join("queue.json");
+    write(&path, &(0..MAX_EVENTS + 3).map(event).collect()).unwrap();
+    let queue = Queue::load(Some(path.clone()));
+    assert_eq!(queue.len(), MAX_EVENTS);
+    assert_eq!(queue.next_batch()[0], event(3));
+  }
+
+  #[test]
+  fn sent_events_are_removed_by_id_and_the_rest_survive_a_reload() {
+    let dir = TempDir::new();
+    let path = dir.join("queue.json");
+    let mut queue = Queue::load(Some(path.clone()));
+    for n in 0..3 {
+      queue.push(event(n));
+    }
+    let batch = queue.next_batch();
+    queue.push(event(3));
+    // Evicted while the batch was on its way.
+    queue.events.pop_front();
+    queue.remove(&batch);
+    assert_eq!(Queue::load(Some(path.clone())).next_batch(), vec![event(3)]);
+
+    queue.clear();
+    assert!(!path.exists());
+  }
+
+  #[test]
+  fn unreadable_file_starts_empty() {
+    let dir = TempDir::new();
+    let path = dir.join("queue.json");
+    util::write_atomic(&path, b"not json").unwrap();
+    assert_eq!(Queue::load(Some(path.clone())).len(), 0);
+  }
+}
@@ -0,0 +1,183 @@
+//! Opt-in HTTP/WebSocket API on 127.0.0.1 for automation tools (Stream Deck,
+//! scripts, home automation). It's off until enabled from the tray, and
+//! every request needs the token kept in `<app config>/api-token`.
+//!
+//! Whether it's on is kept in `<app config>/protected/api-settings.json`, a
+//! [`protected`] file page scripts can't write, so they can't turn it on.
+
+mod server;
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::watch;
+
+use crate::audio::playback::PlaybackState;
+use crate::audio::AudioState;
+use crate::catalog::{self, CatalogSound};
+use crate::remote::{self, Action, Response, Status};
+use crate::timer::{TimerState, TimerStatus};
+use crate::{protected, util};
+
+const API_STORE_FILE: &str = "api.json";
+const PORT_STORE_KEY: &str = "port";
+const DEFAULT_PORT: u16 = 27183;
+const TOKEN_FILE: &str = "api-token";
+const SETTINGS_FILE: &str = "api-settings.json";
+
+#[derive(Default)]
+pub struct ApiState {
+  running: Mutex<Option<Running>>,
+}
+
+struct Running {
+  address: SocketAddr,
+  /// Dropping this stops the server.
+  _stop: watch::Sender<()>,
+}
+
+/// Where to reach the API, shown when it's enabled.
+pub struct Endpoint {
+  pub url: String,
+  pub token_path: PathBuf,
+}
+
+struct AppControl<R: Runtime>(AppHandle<R>);
+
+// Derived `Clone` would require `R: Clone`.
+impl<R: Runtime> Clone for AppControl<R> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+impl<R: Runtime> server::Control for AppControl<R> {
+  fn status(&self) -> Status {
+    remote::status(&self.0)
+  }
+
+  fn handle(&self, actions: Vec<Action>) -> Response {
+    remote::handle(&self.0, &actions)
+  }
+
+  fn sounds(&self) -> Vec<CatalogSound> {
+    catalog::sounds(&self.0)
+  }
+
+  fn timer_updates(&self) -> watch::Receiver<TimerStatus> {
+    self.0.state::<TimerState>().subscribe()
+  }
+
+  fn playback_updates(&self) -> watch::Receiver<PlaybackState> {
+    self.0.state::<AudioState>().subscribe_playback()
+  }
+}
+
+/// Off until turned on from the tray. Earlier builds kept the switch where
+/// the webview could write it, so it isn't carried over from there.
+pub fn is_enabled<R: Runtime>(app: &AppHandle<R>) -> bool {
+  let Ok(path) = protected::path(app, SETTINGS_FILE) else {
+    return false;
+  };
+  util::read_switch(&path).unwrap_or(false)
+}
+
+fn port<R: Runtime>(app: &AppHandle<R>) -> u16 {
+  app
+    .store(API_STORE_FILE)
+    .ok()
+    .and_then(|s| s.get(PORT_STORE_KEY))
+    .and_then(|v| v.as_u64())
+    .and_then(|p| u16::try_from(p).ok())
+    .unwrap_or(DEFAULT_PORT)
+}
+
+/// Takes effect at the next start; see `enable` to start it now.
+pub fn set_enabled<R: Runtime>(app: &AppHandle<R>, enabled: bool) -> Result<(), String> {
+  let path = protected::path(app, SETTINGS_FILE)?;
+  util::write_switch(&path, enabled).map_err(|e| format!("Failed to save {}: {}", path.display(), e))
+}
+
+fn token_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+  app
+    .path()
+    .app_config_dir()
+    .map(|dir| dir.join(TOKEN_FILE))
+    .map_err(|e| e.to_string())
+}
+
+/// The saved token, or a new random one. Only the user can read the file.
+fn load_or_create_token(path: &Path) -> Result<String, String> {
+  if let Ok(token) = std::fs::read_to_string(path) {
+    let token = token.trim();
+    if !token.is_empty() {
+      return Ok(token.to_string());
+    }
+  }
+
+  let token = util::random_hex(32).map_err(|e| format!("Failed to generate an API token: {}", e))?;
+  util::write_private(path, token.as_bytes()).map_err(|e| format!("Failed to save the API token: {}", e))?;
+  Ok(token)
+}
+
+fn start<R: Runtime>(app: &AppHandle<R>) -> Result<Endpoint, String> {
+  let state = app.state::<ApiState>();
+  let mut running = state.running.lock().map_err(|e| e.to_string())?;
+  let token_path = token_path(app)?;
+  if let Some(running) = running.as_ref() {
+    return Ok(Endpoint {
+      url: format!("http://{}", running.address),
+      token_path,
+    });
+  }
+
+  let token = load_or_create_token(&token_path)?;
+  // Bind here rather than in the task so a taken port is reported.
+  let port = port(app);
+  let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+    .and_then(|l| l.set_nonblocking(true).map(|_| l))
+    .map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+  let address = listener.local_addr().map_err(|e| e.to_string())?;
+  let (stop, stopped) = watch::channel(());
+  let control = AppControl(app.clone());
+  tauri::async_runtime::spawn(async move {
+    let result = match tokio::net::TcpListener::from_std(listener) {
+      Ok(listener) => server::serve(listener, control, token, stopped).await,
+      Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+      log::warn!("Local API stopped: {}", e);
+    }
+  });
+
+  *running = Some(Running { address, _stop: stop });
+  Ok(Endpoint {
+    url: format!("http://{}", address),
+    token_path,
+  })
+}
+
+/// Start the API at launch if the user turned it on.
+pub fn start_if_enabled<R: Runtime>(app: &AppHandle<R>) {
+  if !is_enabled(app) {
+    return;
+  }
+  if let Err(e) = start(app) {
+    log::warn!("Failed to start the local API: {}", e);
+  }
+}
+
+pub fn enable<R: Runtime>(app: &AppHandle<R>) -> Result<Endpoint, String> {
+  let endpoint = start(app)?;
+  set_enabled(app, true)?;
+  Ok(endpoint)
+}
+
+pub fn disable<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+  let state = app.state::<ApiState>();
+  state.running.lock().map_err(|e| e.to_string())?.take();
+  set_enabled(app, false)
+}
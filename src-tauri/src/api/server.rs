@@ -0,0 +1,522 @@
+//! Routes of the local API. The app is reached only through [`Control`], so
+//! the server can run against a stand-in as well as a live [`AppHandle`].
+//!
+//! [`AppHandle`]: tauri::AppHandle
+
+use std::future::Future;
+use std::sync::Arc;
+
+use axum::extract::rejection::JsonRejection;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response as HttpResponse};
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+
+use crate::audio::playback::PlaybackState;
+use crate::catalog::CatalogSound;
+use crate::remote::{Action, Response, Status};
+use crate::scenes::SCENES;
+use crate::timer::TimerStatus;
+
+/// What the API can see and do.
+pub trait Control: Clone + Send + Sync + 'static {
+  fn status(&self) -> Status;
+  /// Apply `actions` and report the resulting status. Blocks while the
+  /// frontend syncs the change back.
+  fn handle(&self, actions: Vec<Action>) -> Response;
+  fn sounds(&self) -> Vec<CatalogSound>;
+  fn timer_updates(&self) -> watch::Receiver<TimerStatus>;
+  fn playback_updates(&self) -> watch::Receiver<PlaybackState>;
+}
+
+#[derive(Clone)]
+struct Server<C> {
+  control: C,
+  token: Arc<str>,
+  /// Closed when the server stops, ending open event streams.
+  stopped: watch::Receiver<()>,
+}
+
+/// Serve the API on `listener` until `stopped`'s sender is dropped.
+pub fn serve<C: Control>(
+  listener: TcpListener,
+  control: C,
+  token: String,
+  stopped: watch::Receiver<()>,
+) -> impl Future<Output = std::io::Result<()>> {
+  let mut shutdown = stopped.clone();
+  let router = router(Server {
+    control,
+    token: token.into(),
+    stopped,
+  });
+  async move {
+    axum::serve(listener, router)
+      .with_graceful_shutdown(async move {
+        let _ = shutdown.changed().await;
+      })
+      .await
+  }
+}
+
+fn router<C: Control>(server: Server<C>) -> Router {
+  Router::new()
+    .route("/v1/status", get(get_status::<C>))
+    .route("/v1/sounds", get(list_sounds::<C>))
+    .route("/v1/sounds/{id}", put(set_sound::<C>))
+    .route("/v1/scenes", get(list_scenes))
+    .route("/v1/timer/start", post(start_timer::<C>))
+    .route("/v1/timer/pause", post(pause_timer::<C>))
+    .route("/v1/timer/resume", post(resume_timer::<C>))
+    .route("/v1/timer/stop", post(stop_timer::<C>))
+    .route("/v1/playback", put(set_playback::<C>))
+    .route("/v1/playback/scene", post(play_scene::<C>))
+    .route("/v1/playback/stop", post(stop_playback::<C>))
+    .route("/v1/events", get(events::<C>))
+    .route_layer(middleware::from_fn_with_state(server.clone(), authorize::<C>))
+    .fallback(|| async { reply(StatusCode::NOT_FOUND, Response::failed("Not found")) })
+    .with_state(server)
+}
+
+fn reply(code: StatusCode, response: Response) -> HttpResponse {
+  (code, Json(response)).into_response()
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+  token: Option<String>,
+}
+
+/// Compare without returning early, so response times don't reveal how much
+/// of a guess was right.
+fn same_token(given: &str, token: &str) -> bool {
+  given.len() == token.len() && given.bytes().zip(token.bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Require the token, as `Authorization: Bearer <token>` or, for the event
+/// stream (browsers can't set headers on a WebSocket), `?token=<token>`.
+/// Requests must also be addressed to a loopback name, so a web page can't
+/// reach the API through a DNS name it rebinds to 127.0.0.1.
+async fn authorize<C: Control>(State(server): State<Server<C>>, request: Request, next: Next) -> HttpResponse {
+  let host = request
+    .headers()
+    .get(header::HOST)
+    .and_then(|h| h.to_str().ok())
+    .map(|h| h.rsplit_once(':').map_or(h, |(name, _)| name))
+    .unwrap_or_default();
+  if !matches!(host, "127.0.0.1" | "localhost") {
+    return reply(StatusCode::FORBIDDEN, Response::failed("Requests must be made to 127.0.0.1"));
+  }
+
+  let header_token = request
+    .headers()
+    .get(header::AUTHORIZATION)
+    .and_then(|h| h.to_str().ok())
+    .and_then(|h| h.strip_prefix("Bearer "))
+    .map(str::to_string);
+  let query_token = match request.uri().path() {
+    "/v1/events" => Query::<TokenQuery>::try_from_uri(request.uri())
+      .ok()
+      .and_then(|q| q.0.token),
+    _ => None,
+  };
+  match header_token.or(query_token) {
+    Some(given) if same_token(&given, &server.token) => next.run(request).await,
+    _ => reply(StatusCode::UNAUTHORIZED, Response::failed("Missing or invalid API token")),
+  }
+}
+
+/// Apply `actions` off the async runtime, since handling waits for the
+/// frontend.
+async fn run<C: Control>(server: Server<C>, actions: Vec<Action>) -> HttpResponse {
+  let control = server.control.clone();
+  match tokio::task::spawn_blocking(move || control.handle(actions)).await {
+    Ok(response) if response.ok => reply(StatusCode::OK, response),
+    Ok(response) => reply(StatusCode::BAD_REQUEST, response),
+    Err(e) => reply(StatusCode::INTERNAL_SERVER_ERROR, Response::failed(e.to_string())),
+  }
+}
+
+fn bad_body(rejection: JsonRejection) -> HttpResponse {
+  reply(StatusCode::BAD_REQUEST, Response::failed(rejection.body_text()))
+}
+
+async fn get_status<C: Control>(State(server): State<Server<C>>) -> HttpResponse {
+  let response = Response {
+    ok: true,
+    error: None,
+    status: Some(server.control.status()),
+  };
+  reply(StatusCode::OK, response)
+}
+
+async fn list_sounds<C: Control>(State(server): State<Server<C>>) -> Json<Vec<CatalogSound>> {
+  Json(server.control.sounds())
+}
+
+#[derive(Serialize)]
+struct SceneInfo {
+  id: &'static str,
+  sounds: &'static [&'static str],
+}
+
+async fn list_scenes() -> Json<Vec<SceneInfo>> {
+  Json(
+    SCENES
+      .iter()
+      .map(|s| SceneInfo {
+        id: s.id,
+        sounds: s.sounds,
+      })
+      .collect(),
+  )
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StartTimer {
+  preset: String,
+  task: Option<String>,
+}
+
+async fn start_timer<C: Control>(
+  State(server): State<Server<C>>,
+  body: Result<Json<StartTimer>, JsonRejection>,
+) -> HttpResponse {
+  match body {
+    Ok(Json(StartTimer { preset, task })) => run(server, vec![Action::Start { preset, task }]).await,
+    Err(rejection) => bad_body(rejection),
+  }
+}
+
+async fn pause_timer<C: Control>(State(server): State<Server<C>>) -> HttpResponse {
+  run(server, vec![Action::PauseTimer]).await
+}
+
+async fn resume_timer<C: Control>(State(server): State<Server<C>>) -> HttpResponse {
+  run(server, vec![Action::ResumeTimer]).await
+}
+
+async fn stop_timer<C: Control>(State(server): State<Server<C>>) -> HttpResponse {
+  run(server, vec![Action::StopTimer]).await
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SetSound {
+  on: Option<bool>,
+  volume: Option<f32>,
+}
+
+async fn set_sound<C: Control>(
+  State(server): State<Server<C>>,
+  Path(sound): Path<String>,
+  body: Result<Json<SetSound>, JsonRejection>,
+) -> HttpResponse {
+  match body {
+    Ok(Json(SetSound { on, volume })) => run(server, vec![Action::Sound { sound, on, volume }]).await,
+    Err(rejection) => bad_body(rejection),
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SetPlayback {
+  muted: Option<bool>,
+  volume: Option<f32>,
+}
+
+async fn set_playback<C: Control>(
+  State(server): State<Server<C>>,
+  body: Result<Json<SetPlayback>, JsonRejection>,
+) -> HttpResponse {
+  let Json(SetPlayback { muted, volume }) = match body {
+    Ok(body) => body,
+    Err(rejection) => return bad_body(rejection),
+  };
+  let mut actions = Vec::new();
+  if let Some(muted) = muted {
+    actions.push(Action::Mute { muted });
+  }
+  if let Some(volume) = volume {
+    actions.push(Action::Volume { volume });
+  }
+  run(server, actions).await
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlayScene {
+  scene: String,
+}
+
+async fn play_scene<C: Control>(
+  State(server): State<Server<C>>,
+  body: Result<Json<PlayScene>, JsonRejection>,
+) -> HttpResponse {
+  match body {
+    Ok(Json(PlayScene { scene })) => run(server, vec![Action::Scene { scene }]).await,
+    Err(rejection) => bad_body(rejection),
+  }
+}
+
+async fn stop_playback<C: Control>(State(server): State<Server<C>>) -> HttpResponse {
+  run(server, vec![Action::StopSounds]).await
+}
+
+/// A message on the event stream.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum Event {
+  /// Sent on connect and whenever anything but the timer's clock changes.
+  Status { status: Status },
+  /// The running timer advanced by a second.
+  Tick { timer: TimerStatus },
+}
+
+async fn events<C: Control>(State(server): State<Server<C>>, upgrade: WebSocketUpgrade) -> HttpResponse {
+  upgrade.on_upgrade(move |socket| stream_events(socket, server))
+}
+
+/// Whether `next` differs from `previous` only by the clock.
+fn is_tick(previous: &TimerStatus, next: &TimerStatus) -> bool {
+  previous.current_seconds != next.current_seconds
+    && *previous
+      == TimerStatus {
+        current_seconds: previous.current_seconds,
+        ..next.clone()
+      }
+}
+
+async fn send(socket: &mut WebSocket, event: &Event) -> bool {
+  match serde_json::to_string(event) {
+    Ok(text) => socket.send(Message::Text(text.into())).await.is_ok(),
+    Err(_) => false,
+  }
+}
+
+async fn stream_events<C: Control>(mut socket: WebSocket, server: Server<C>) {
+  let control = server.control;
+  let mut stopped = server.stopped;
+  let mut timer = control.timer_updates();
+  let mut playback = control.playback_updates();
+  let mut last_timer = timer.borrow_and_update().clone();
+  playback.mark_unchanged();
+
+  let status = Event::Status {
+    status: control.status(),
+  };
+  if !send(&mut socket, &status).await {
+    return;
+  }
+  loop {
+    let event = tokio::select! {
+      changed = timer.changed() => {
+        if changed.is_err() {
+          return;
+        }
+        let next = timer.borrow_and_update().clone();
+        let tick = is_tick(&last_timer, &next);
+        last_timer = next.clone();
+        if tick {
+          Event::Tick { timer: next }
+        } else {
+          Event::Status { status: control.status() }
+        }
+      }
+      changed = playback.changed() => {
+        if changed.is_err() {
+          return;
+        }
+        playback.mark_unchanged();
+        Event::Status { status: control.status() }
+      }
+      message = socket.recv() => match message {
+        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+        // Pings are answered by the socket itself; anything else is ignored.
+        Some(Ok(_)) => continue,
+      },
+      _ = stopped.changed() => {
+        let _ = socket.send(Message::Close(None)).await;
+        return;
+      }
+    };
+    if !send(&mut socket, &event).await {
+      return;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::net::SocketAddr;
+  use std::sync::Mutex;
+
+  use futures_util::StreamExt;
+  use tokio_tungstenite::{tungstenite, MaybeTlsStream, WebSocketStream};
+
+  use super::*;
+  use crate::audio::playback::PlaybackStatus;
+
+  const TOKEN: &str = "secret";
+
+  /// Records the timer the way the frontend would sync it back.
+  #[derive(Clone)]
+  struct FakeControl {
+    timer: Arc<watch::Sender<TimerStatus>>,
+    playback: Arc<watch::Sender<PlaybackState>>,
+    actions: Arc<Mutex<Vec<Action>>>,
+  }
+
+  impl FakeControl {
+    fn new() -> Self {
+      Self {
+        timer: Arc::new(watch::channel(TimerStatus::default()).0),
+        playback: Arc::new(watch::channel(PlaybackState::default()).0),
+        actions: Arc::default(),
+      }
+    }
+  }
+
+  impl Control for FakeControl {
+    fn status(&self) -> Status {
+      let audio = self.playback.borrow().clone();
+      Status {
+        version: "0.0.0".to_string(),
+        timer: self.timer.borrow().clone(),
+        playback: audio.status(),
+        audio,
+        scene: None,
+        title: String::new(),
+      }
+    }
+
+    fn handle(&self, actions: Vec<Action>) -> Response {
+      for action in &actions {
+        if let Action::Start { preset, task } = action {
+          self.timer.send_modify(|timer| {
+            timer.is_running = true;
+            timer.preset = Some(preset.clone());
+            timer.task = task.clone();
+          });
+        }
+      }
+      self.actions.lock().unwrap().extend(actions);
+      Response {
+        ok: true,
+        error: None,
+        status: Some(self.status()),
+      }
+    }
+
+    fn sounds(&self) -> Vec<CatalogSound> {
+      Vec::new()
+    }
+
+    fn timer_updates(&self) -> watch::Receiver<TimerStatus> {
+      self.timer.subscribe()
+    }
+
+    fn playback_updates(&self) -> watch::Receiver<PlaybackState> {
+      self.playback.subscribe()
+    }
+  }
+
+  /// Serve `control` on a free port until the returned sender is dropped.
+  async fn start(control: FakeControl) -> (SocketAddr, watch::Sender<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let (stop, stopped) = watch::channel(());
+    tokio::spawn(serve(listener, control, TOKEN.to_string(), stopped));
+    (address, stop)
+  }
+
+  async fn next_event(socket: &mut WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>) -> serde_json::Value {
+    match socket.next().await.unwrap().unwrap() {
+      tungstenite::Message::Text(text) => serde_json::from_str(&text).unwrap(),
+      other => panic!("unexpected message: {:?}", other),
+    }
+  }
+
+  #[tokio::test]
+  async fn requests_need_the_token() {
+    let (address, _stop) = start(FakeControl::new()).await;
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/v1/status", address);
+
+    let missing = client.get(&url).send().await.unwrap();
+    assert_eq!(missing.status(), reqwest::StatusCode::UNAUTHORIZED);
+    let wrong = client.get(&url).bearer_auth("secreT").send().await.unwrap();
+    assert_eq!(wrong.status(), reqwest::StatusCode::UNAUTHORIZED);
+    let right = client.get(&url).bearer_auth(TOKEN).send().await.unwrap();
+    assert_eq!(right.status(), reqwest::StatusCode::OK);
+  }
+
+  #[tokio::test]
+  async fn requests_must_name_a_loopback_host() {
+    let (address, _stop) = start(FakeControl::new()).await;
+    let response = reqwest::Client::new()
+      .get(format!("http://{}/v1/status", address))
+      .header(reqwest::header::HOST, format!("rebound.example:{}", address.port()))
+      .bearer_auth(TOKEN)
+      .send()
+      .await
+      .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+  }
+
+  #[tokio::test]
+  async fn starting_the_timer_reaches_the_control() {
+    let control = FakeControl::new();
+    let (address, _stop) = start(control.clone()).await;
+    let response = reqwest::Client::new()
+      .post(format!("http://{}/v1/timer/start", address))
+      .bearer_auth(TOKEN)
+      .header(reqwest::header::CONTENT_TYPE, "application/json")
+      .body(r#"{"preset":"pomodoro","task":"Write"}"#)
+      .send()
+      .await
+      .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    assert_eq!(
+      *control.actions.lock().unwrap(),
+      vec![Action::Start {
+        preset: "pomodoro".to_string(),
+        task: Some("Write".to_string()),
+      }]
+    );
+    let timer = control.timer.borrow().clone();
+    assert!(timer.is_running);
+    assert_eq!(timer.preset.as_deref(), Some("pomodoro"));
+
+    let body: serde_json::Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(body["status"]["timer"]["isRunning"], true);
+  }
+
+  #[tokio::test]
+  async fn event_stream_sends_the_status_then_ticks() {
+    let control = FakeControl::new();
+    let (address, _stop) = start(control.clone()).await;
+    let url = format!("ws://{}/v1/events?token={}", address, TOKEN);
+    let (mut socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+    let status = next_event(&mut socket).await;
+    assert_eq!(status["type"], "status");
+    assert_eq!(
+      status["status"]["playback"],
+      serde_json::to_value(PlaybackStatus::Stopped).unwrap()
+    );
+
+    control.timer.send_modify(|timer| timer.current_seconds = 1);
+    let tick = next_event(&mut socket).await;
+    assert_eq!(tick["type"], "tick");
+    assert_eq!(tick["timer"]["currentSeconds"], 1);
+  }
+}
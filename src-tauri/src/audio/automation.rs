@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Gain floor used by exponential segments (-60 dB). Exponential curves can't
+/// start from or land on zero, so both ends are clamped to this before
+/// interpolating and the exact endpoint values are returned at the edges.
+const EXP_FLOOR: f32 = 0.001;
+
+/// Longest envelope we accept (24h of session time).
+const MAX_KEYFRAME_TIME: f64 = 24.0 * 60.0 * 60.0;
+
+/// Upper bound on keyframes per envelope so a bad payload can't balloon state.
+const MAX_KEYFRAMES: usize = 256;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interpolation {
+  #[default]
+  Linear,
+  Exponential,
+}
+
+/// A single point on an automation curve.
+///
+/// `curve` describes how the segment *arriving* at this keyframe is shaped,
+/// matching how Web Audio's `linearRampToValueAtTime` /
+/// `exponentialRampToValueAtTime` are scheduled.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Keyframe {
+  /// Session time in seconds.
+  pub time: f64,
+  /// Gain multiplier applied on top of the sound's volume slider (0~1).
+  pub value: f32,
+  #[serde(default)]
+  pub curve: Interpolation,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Envelope {
+  pub keyframes: Vec<Keyframe>,
+}
+
+impl Envelope {
+  /// Validate and normalize an envelope coming from the frontend.
+  /// Keyframes are sorted by time; values outside 0~1 are rejected rather
+  /// than clamped so a typo doesn't silently turn into full volume.
+  pub fn new(mut keyframes: Vec<Keyframe>) -> Result<Self, String> {
+    if keyframes.is_empty() {
+      return Err("Envelope must have at least one keyframe".to_string());
+    }
+    if keyframes.len() > MAX_KEYFRAMES {
+      return Err(format!("Envelope has more than {} keyframes", MAX_KEYFRAMES));
+    }
+
+    for kf in &keyframes {
+      if !kf.time.is_finite() || kf.time < 0.0 || kf.time > MAX_KEYFRAME_TIME {
+        return Err(format!("Invalid keyframe time: {}", kf.time));
+      }
+      if !kf.value.is_finite() || !(0.0..=1.0).contains(&kf.value) {
+        return Err(format!("Invalid keyframe value: {}", kf.value));
+      }
+    }
+
+    keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    Ok(Self { keyframes })
+  }
+
+  /// Evaluate the envelope at `t` seconds of session time.
+  ///
+  /// Before the first keyframe the first value is held, after the last
+  /// keyframe the last value is held.
+  pub fn value_at(&self, t: f64) -> f32 {
+    let (first, last) = match (self.keyframes.first(), self.keyframes.last()) {
+      (Some(first), Some(last)) => (first, last),
+      _ => return 1.0,
+    };
+
+    if t <= first.time {
+      return first.value;
+    }
+    if t >= last.time {
+      return last.value;
+    }
+
+    // First keyframe strictly after t; guaranteed to exist and be > 0 here.
+    let idx = self.keyframes.partition_point(|kf| kf.time <= t);
+    let from = self.keyframes[idx - 1];
+    let to = self.keyframes[idx];
+
+    let span = to.time - from.time;
+    if span <= 0.0 {
+      return to.value;
+    }
+    let x = ((t - from.time) / span) as f32;
+
+    interpolate(from.value, to.value, x, to.curve)
+  }
+}
+
+fn interpolate(a: f32, b: f32, x: f32, curve: Interpolation) -> f32 {
+  if x <= 0.0 {
+    return a;
+  }
+  if x >= 1.0 {
+    return b;
+  }
+
+  match curve {
+    Interpolation::Linear => a + (b - a) * x,
+    Interpolation::Exponential => {
+      let from = a.max(EXP_FLOOR);
+      let to = b.max(EXP_FLOOR);
+      from * (to / from).powf(x)
+    }
+  }
+}
+
+/// Per-sound automation envelopes keyed by sound id.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Automation {
+  envelopes: HashMap<String, Envelope>,
+}
+
+impl Automation {
+  pub fn set(&mut self, sound_id: String, envelope: Envelope) {
+    self.envelopes.insert(sound_id, envelope);
+  }
+
+  pub fn remove(&mut self, sound_id: &str) -> bool {
+    self.envelopes.remove(sound_id).is_some()
+  }
+
+  pub fn envelopes(&self) -> &HashMap<String, Envelope> {
+    &self.envelopes
+  }
+
+  /// Gain multiplier for `sound_id` at session time `t`; 1 when the sound
+  /// has no automation.
+  pub fn gain(&self, sound_id: &str, t: f64) -> f32 {
+    self.envelopes.get(sound_id).map(|env| env.value_at(t)).unwrap_or(1.0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn kf(time: f64, value: f32, curve: Interpolation) -> Keyframe {
+    Keyframe { time, value, curve }
+  }
+
+  fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-5
+  }
+
+  #[test]
+  fn linear_segment_interpolates_evenly() {
+    let env = Envelope::new(vec![kf(0.0, 0.0, Interpolation::Linear), kf(10.0, 1.0, Interpolation::Linear)]).unwrap();
+    assert!(close(env.value_at(2.5), 0.25));
+    assert!(close(env.value_at(5.0), 0.5));
+  }
+
+  #[test]
+  fn exponential_segment_interpolates_geometrically() {
+    let env = Envelope::new(vec![
+      kf(0.0, 0.01, Interpolation::Linear),
+      kf(10.0, 1.0, Interpolation::Exponential),
+    ])
+    .unwrap();
+    // Halfway between 0.01 and 1 on a log scale.
+    assert!(close(env.value_at(5.0), 0.1));
+  }
+
+  #[test]
+  fn holds_outside_the_keyframes() {
+    let env = Envelope::new(vec![kf(5.0, 0.2, Interpolation::Linear), kf(10.0, 0.8, Interpolation::Linear)]).unwrap();
+    assert_eq!(env.value_at(0.0), 0.2);
+    assert_eq!(env.value_at(5.0), 0.2);
+    assert_eq!(env.value_at(10.0), 0.8);
+    assert_eq!(env.value_at(100.0), 0.8);
+  }
+
+  #[test]
+  fn exponential_segment_reaches_zero_endpoints_exactly() {
+    let env = Envelope::new(vec![
+      kf(0.0, 0.0, Interpolation::Linear),
+      kf(10.0, 1.0, Interpolation::Exponential),
+      kf(20.0, 0.0, Interpolation::Exponential),
+    ])
+    .unwrap();
+    assert_eq!(env.value_at(0.0), 0.0);
+    assert_eq!(env.value_at(20.0), 0.0);
+    // Just inside the segments the curve starts from the floor, not zero.
+    assert!(close(env.value_at(1e-9), EXP_FLOOR));
+    assert!(close(interpolate(0.0, 0.0, 0.5, Interpolation::Exponential), EXP_FLOOR));
+    assert!(env.value_at(19.999) > 0.0);
+  }
+
+  #[test]
+  fn sorts_unsorted_keyframes() {
+    let env = Envelope::new(vec![kf(10.0, 1.0, Interpolation::Linear), kf(0.0, 0.0, Interpolation::Linear)]).unwrap();
+    assert_eq!(env.keyframes[0].time, 0.0);
+    assert!(close(env.value_at(5.0), 0.5));
+  }
+
+  #[test]
+  fn rejects_invalid_keyframes() {
+    assert!(Envelope::new(Vec::new()).is_err());
+    assert!(Envelope::new(vec![kf(-1.0, 0.5, Interpolation::Linear)]).is_err());
+    assert!(Envelope::new(vec![kf(f64::NAN, 0.5, Interpolation::Linear)]).is_err());
+    assert!(Envelope::new(vec![kf(MAX_KEYFRAME_TIME + 1.0, 0.5, Interpolation::Linear)]).is_err());
+    assert!(Envelope::new(vec![kf(0.0, 1.5, Interpolation::Linear)]).is_err());
+    assert!(Envelope::new(vec![kf(0.0, f32::NAN, Interpolation::Linear)]).is_err());
+    let too_many = (0..=MAX_KEYFRAMES).map(|i| kf(i as f64, 0.5, Interpolation::Linear)).collect();
+    assert!(Envelope::new(too_many).is_err());
+  }
+
+  #[test]
+  fn sounds_without_automation_play_at_full_gain() {
+    let mut automation = Automation::default();
+    automation.set("rain".to_string(), Envelope::new(vec![kf(0.0, 0.5, Interpolation::Linear)]).unwrap());
+    assert_eq!(automation.gain("rain", 3.0), 0.5);
+    assert_eq!(automation.gain("fire", 3.0), 1.0);
+  }
+}
@@ -0,0 +1,137 @@
+use std::io::Cursor;
+
+use symphonia::core::audio::SampleBuffer as SymphoniaBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// Decoded PCM, always interleaved stereo f32.
+#[derive(Debug)]
+pub struct SampleBuffer {
+  pub sample_rate: u32,
+  pub samples: Vec<f32>,
+}
+
+impl SampleBuffer {
+  pub fn frames(&self) -> usize {
+    self.samples.len() / 2
+  }
+
+  #[inline]
+  pub fn frame(&self, index: usize) -> (f32, f32) {
+    (self.samples[index * 2], self.samples[index * 2 + 1])
+  }
+
+  /// Linear-interpolation resample to `target_rate`. Ambient beds are
+  /// forgiving enough that this beats pulling in a polyphase resampler.
+  pub fn resampled(self, target_rate: u32) -> Self {
+    if self.sample_rate == target_rate || self.frames() < 2 {
+      return Self {
+        sample_rate: target_rate,
+        samples: self.samples,
+      };
+    }
+
+    let ratio = self.sample_rate as f64 / target_rate as f64;
+    let src_frames = self.frames();
+    let out_frames = ((src_frames as f64) / ratio).floor() as usize;
+    let mut samples = Vec::with_capacity(out_frames * 2);
+
+    for i in 0..out_frames {
+      let pos = i as f64 * ratio;
+      let idx = pos.floor() as usize;
+      let frac = (pos - idx as f64) as f32;
+      let (l0, r0) = self.frame(idx.min(src_frames - 1));
+      let (l1, r1) = self.frame((idx + 1).min(src_frames - 1));
+      samples.push(l0 + (l1 - l0) * frac);
+      samples.push(r0 + (r1 - r0) * frac);
+    }
+
+    Self {
+      sample_rate: target_rate,
+      samples,
+    }
+  }
+}
+
+/// Decode a whole audio file held in memory.
+///
+/// `extension` is only a probing hint (e.g. `"m4a"`); the container is still
+/// sniffed from the bytes.
+pub fn decode(bytes: Vec<u8>, extension: Option<&str>) -> Result<SampleBuffer, String> {
+  let mut hint = Hint::new();
+  if let Some(ext) = extension {
+    hint.with_extension(ext);
+  }
+
+  let mss = MediaSourceStream::new(Box::new(Cursor::new(bytes)), Default::default());
+  let probed = symphonia::default::get_probe()
+    .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+    .map_err(|e| format!("Unsupported audio format: {}", e))?;
+  let mut format = probed.format;
+
+  let track = format
+    .tracks()
+    .iter()
+    .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+    .ok_or_else(|| "No audio track found".to_string())?;
+  let track_id = track.id;
+  let sample_rate = track
+    .codec_params
+    .sample_rate
+    .ok_or_else(|| "Unknown sample rate".to_string())?;
+
+  let mut decoder = symphonia::default::get_codecs()
+    .make(&track.codec_params, &DecoderOptions::default())
+    .map_err(|e| format!("Unsupported codec: {}", e))?;
+
+  let mut samples = Vec::new();
+  let mut scratch: Option<SymphoniaBuffer<f32>> = None;
+
+  loop {
+    let packet = match format.next_packet() {
+      Ok(packet) => packet,
+      Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+      Err(SymphoniaError::ResetRequired) => break,
+      Err(e) => return Err(format!("Failed to read audio: {}", e)),
+    };
+    if packet.track_id() != track_id {
+      continue;
+    }
+
+    let decoded = match decoder.decode(&packet) {
+      Ok(decoded) => decoded,
+      // Corrupt frames are skipped, same as browsers do.
+      Err(SymphoniaError::DecodeError(_)) => continue,
+      Err(e) => return Err(format!("Failed to decode audio: {}", e)),
+    };
+
+    let spec = *decoded.spec();
+    let channels = spec.channels.count();
+    if channels == 0 {
+      continue;
+    }
+
+    let buf = match scratch.as_mut() {
+      Some(buf) if buf.capacity() >= decoded.capacity() * channels => buf,
+      _ => scratch.insert(SymphoniaBuffer::<f32>::new(decoded.capacity() as u64, spec)),
+    };
+    buf.copy_interleaved_ref(decoded);
+
+    for frame in buf.samples().chunks_exact(channels) {
+      let l = frame[0];
+      let r = if channels > 1 { frame[1] } else { l };
+      samples.push(l);
+      samples.push(r);
+    }
+  }
+
+  if samples.is_empty() {
+    return Err("Audio file contains no samples".to_string());
+  }
+
+  Ok(SampleBuffer { sample_rate, samples })
+}
@@ -0,0 +1,76 @@
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputDevice {
+  pub name: String,
+  pub is_default: bool,
+}
+
+/// What the output is doing right now. Sent back from the device commands
+/// and as the `audio-device-changed` payload.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputStatus {
+  /// Device the user picked; `None` follows the system default.
+  pub selected: Option<String>,
+  /// Device the stream is actually open on. Differs from `selected` while
+  /// the picked device is unplugged.
+  pub active: Option<String>,
+  pub default_device: Option<String>,
+}
+
+pub fn default_output_name() -> Option<String> {
+  cpal::default_host()
+    .default_output_device()
+    .and_then(|d| d.name().ok())
+}
+
+/// Output devices of the default host. Devices are identified by name only,
+/// so duplicates (which some backends report) are collapsed.
+pub fn list_output_devices() -> Result<Vec<OutputDevice>, String> {
+  let host = cpal::default_host();
+  let default_name = host.default_output_device().and_then(|d| d.name().ok());
+  let devices = host
+    .output_devices()
+    .map_err(|e| format!("Failed to enumerate output devices: {}", e))?;
+
+  let mut list: Vec<OutputDevice> = Vec::new();
+  for device in devices {
+    let Ok(name) = device.name() else {
+      continue;
+    };
+    if list.iter().any(|d| d.name == name) {
+      continue;
+    }
+    list.push(OutputDevice {
+      is_default: default_name.as_deref() == Some(name.as_str()),
+      name,
+    });
+  }
+  Ok(list)
+}
+
+pub fn is_available(name: &str) -> bool {
+  find_output_device(name).is_some()
+}
+
+pub fn find_output_device(name: &str) -> Option<cpal::Device> {
+  cpal::default_host()
+    .output_devices()
+    .ok()?
+    .find(|d| d.name().map(|n| n == name).unwrap_or(false))
+}
+
+/// The device to open: the preferred one when it's plugged in, otherwise
+/// the system default.
+pub fn resolve_output_device(preferred: Option<&str>) -> Option<cpal::Device> {
+  if let Some(name) = preferred {
+    match find_output_device(name) {
+      Some(device) => return Some(device),
+      None => log::warn!("Output device {:?} not found, using the default device", name),
+    }
+  }
+  cpal::default_host().default_output_device()
+}
@@ -0,0 +1,268 @@
+use std::f32::consts::{FRAC_PI_4, PI, SQRT_2};
+
+use serde::{Deserialize, Serialize};
+
+/// Cutoffs at or above this are treated as "filter off".
+pub const MAX_CUTOFF: f32 = 20_000.0;
+const MIN_CUTOFF: f32 = 20.0;
+/// Butterworth Q: flat passband, no resonant bump at the cutoff.
+const BUTTERWORTH_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Constant-power pan law. `pan` is -1 (hard left) ~ 1 (hard right); centre
+/// gives -3 dB per side so perceived loudness stays flat across the sweep.
+pub fn pan_gains(pan: f32) -> (f32, f32) {
+  let angle = (pan.clamp(-1.0, 1.0) + 1.0) * FRAC_PI_4;
+  (angle.cos(), angle.sin())
+}
+
+/// One-pole smoother used for click-free gain changes.
+#[derive(Clone, Copy, Debug)]
+pub struct Smoothed {
+  current: f32,
+  coeff: f32,
+}
+
+impl Smoothed {
+  /// `time_constant` in seconds (time to reach ~63% of a step).
+  pub fn new(initial: f32, time_constant: f32, sample_rate: u32) -> Self {
+    let coeff = 1.0 - (-1.0 / (time_constant * sample_rate as f32)).exp();
+    Self { current: initial, coeff }
+  }
+
+  #[inline]
+  pub fn next(&mut self, target: f32) -> f32 {
+    self.current += (target - self.current) * self.coeff;
+    self.current
+  }
+
+  pub fn value(&self) -> f32 {
+    self.current
+  }
+}
+
+/// Normalized biquad coefficients (a0 = 1).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BiquadCoeffs {
+  pub b0: f32,
+  pub b1: f32,
+  pub b2: f32,
+  pub a1: f32,
+  pub a2: f32,
+}
+
+impl BiquadCoeffs {
+  /// RBJ Audio EQ Cookbook low-pass.
+  pub fn lowpass(cutoff: f32, q: f32, sample_rate: u32) -> Self {
+    let w0 = 2.0 * PI * cutoff / sample_rate as f32;
+    let (sin_w0, cos_w0) = w0.sin_cos();
+    let alpha = sin_w0 / (2.0 * q);
+
+    let a0 = 1.0 + alpha;
+    let b1 = (1.0 - cos_w0) / a0;
+    Self {
+      b0: b1 / 2.0,
+      b1,
+      b2: b1 / 2.0,
+      a1: -2.0 * cos_w0 / a0,
+      a2: (1.0 - alpha) / a0,
+    }
+  }
+}
+
+/// Stereo biquad, transposed direct form II.
+#[derive(Clone, Copy, Debug)]
+pub struct Biquad {
+  coeffs: BiquadCoeffs,
+  z1: [f32; 2],
+  z2: [f32; 2],
+}
+
+impl Biquad {
+  pub fn new(coeffs: BiquadCoeffs) -> Self {
+    Self {
+      coeffs,
+      z1: [0.0; 2],
+      z2: [0.0; 2],
+    }
+  }
+
+  /// Swap coefficients without clearing state so sweeping the cutoff
+  /// doesn't click.
+  pub fn set_coeffs(&mut self, coeffs: BiquadCoeffs) {
+    self.coeffs = coeffs;
+  }
+
+  #[inline]
+  pub fn process(&mut self, l: f32, r: f32) -> (f32, f32) {
+    (self.tick(0, l), self.tick(1, r))
+  }
+
+  #[inline]
+  fn tick(&mut self, ch: usize, x: f32) -> f32 {
+    let c = self.coeffs;
+    let y = c.b0 * x + self.z1[ch];
+    self.z1[ch] = c.b1 * x - c.a1 * y + self.z2[ch];
+    self.z2[ch] = c.b2 * x - c.a2 * y;
+    y
+  }
+}
+
+/// Per-sound tone and placement, stored next to the volume in app settings.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoundEffect {
+  /// -1 (left) ~ 1 (right).
+  pub pan: f32,
+  /// Low-pass cutoff in Hz; [`MAX_CUTOFF`] leaves the sound full-band.
+  pub lowpass: f32,
+}
+
+impl Default for SoundEffect {
+  fn default() -> Self {
+    Self {
+      pan: 0.0,
+      lowpass: MAX_CUTOFF,
+    }
+  }
+}
+
+impl SoundEffect {
+  /// Clamp values from the frontend into range; garbage falls back to neutral.
+  pub fn sanitized(self) -> Self {
+    let neutral = Self::default();
+    Self {
+      pan: if self.pan.is_finite() { self.pan.clamp(-1.0, 1.0) } else { neutral.pan },
+      lowpass: if self.lowpass.is_finite() {
+        self.lowpass.clamp(MIN_CUTOFF, MAX_CUTOFF)
+      } else {
+        neutral.lowpass
+      },
+    }
+  }
+}
+
+/// Track insert chain: low-pass, then constant-power pan.
+#[derive(Clone, Debug)]
+pub struct EffectChain {
+  filter: Option<Biquad>,
+  pan: (f32, f32),
+}
+
+impl EffectChain {
+  pub fn new(effect: SoundEffect, sample_rate: u32) -> Self {
+    let mut chain = Self {
+      filter: None,
+      pan: (1.0, 1.0),
+    };
+    chain.configure(effect, sample_rate);
+    chain
+  }
+
+  pub fn configure(&mut self, effect: SoundEffect, sample_rate: u32) {
+    // Leave headroom below Nyquist; past that the filter is inaudible anyway.
+    let nyquist_limit = sample_rate as f32 * 0.45;
+    if effect.lowpass >= MAX_CUTOFF || effect.lowpass >= nyquist_limit {
+      self.filter = None;
+    } else {
+      let coeffs = BiquadCoeffs::lowpass(effect.lowpass, BUTTERWORTH_Q, sample_rate);
+      match self.filter.as_mut() {
+        Some(filter) => filter.set_coeffs(coeffs),
+        None => self.filter = Some(Biquad::new(coeffs)),
+      }
+    }
+
+    // Scaled so centre is unity on both channels (a stereo bed already
+    // carries its own image), hard left/right is +3 dB on that side.
+    let (l, r) = pan_gains(effect.pan);
+    self.pan = (l * SQRT_2, r * SQRT_2);
+  }
+
+  #[inline]
+  pub fn process(&mut self, l: f32, r: f32) -> (f32, f32) {
+    let (l, r) = match self.filter.as_mut() {
+      Some(filter) => filter.process(l, r),
+      None => (l, r),
+    };
+    (l * self.pan.0, r * self.pan.1)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SAMPLE_RATE: u32 = 48_000;
+
+  /// Steady-state gain of `chain` for a sine at `freq`, left channel.
+  fn measured_gain(chain: &mut EffectChain, freq: f32) -> f32 {
+    let samples = SAMPLE_RATE as usize;
+    let mut peak_in = 0.0f32;
+    let mut peak_out = 0.0f32;
+    for n in 0..samples {
+      let x = (2.0 * PI * freq * n as f32 / SAMPLE_RATE as f32).sin();
+      let (y, _) = chain.process(x, x);
+      // Skip the filter settling in.
+      if n > samples / 2 {
+        peak_in = peak_in.max(x.abs());
+        peak_out = peak_out.max(y.abs());
+      }
+    }
+    peak_out / peak_in
+  }
+
+  fn db(gain: f32) -> f32 {
+    20.0 * gain.log10()
+  }
+
+  fn lowpass_chain(cutoff: f32) -> EffectChain {
+    // Centred pan is unity, so only the filter shows.
+    EffectChain::new(SoundEffect { pan: 0.0, lowpass: cutoff }, SAMPLE_RATE)
+  }
+
+  #[test]
+  fn lowpass_is_3db_down_at_the_cutoff() {
+    let gain = db(measured_gain(&mut lowpass_chain(1_000.0), 1_000.0));
+    assert!((gain + 3.0).abs() < 0.2, "{} dB", gain);
+  }
+
+  #[test]
+  fn lowpass_passes_low_frequencies_at_unity() {
+    let gain = db(measured_gain(&mut lowpass_chain(5_000.0), 100.0));
+    assert!(gain.abs() < 0.1, "{} dB", gain);
+  }
+
+  #[test]
+  fn lowpass_attenuates_the_stopband() {
+    // Second order: about -12 dB per octave, so -24 dB two octaves up.
+    let gain = db(measured_gain(&mut lowpass_chain(1_000.0), 4_000.0));
+    assert!(gain < -22.0, "{} dB", gain);
+  }
+
+  #[test]
+  fn filter_off_is_bypassed() {
+    let (l, r) = lowpass_chain(MAX_CUTOFF).process(0.5, -0.25);
+    assert!((l - 0.5).abs() < 1e-6 && (r + 0.25).abs() < 1e-6);
+  }
+
+  #[test]
+  fn pan_law_keeps_power_constant() {
+    for i in -10..=10 {
+      let (l, r) = pan_gains(i as f32 / 10.0);
+      assert!((l * l + r * r - 1.0).abs() < 1e-5);
+    }
+    let (l, r) = pan_gains(0.0);
+    assert!((db(l) + 3.01).abs() < 0.01 && (l - r).abs() < 1e-6);
+    assert!(pan_gains(-1.0).1.abs() < 1e-6);
+    assert!(pan_gains(1.0).0.abs() < 1e-6);
+  }
+
+  #[test]
+  fn sanitized_clamps_garbage() {
+    let effect = SoundEffect {
+      pan: f32::NAN,
+      lowpass: 5.0,
+    }
+    .sanitized();
+    assert_eq!(effect, SoundEffect { pan: 0.0, lowpass: MIN_CUTOFF });
+  }
+}
@@ -0,0 +1,171 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use cpal::traits::{DeviceTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample};
+
+use super::devices;
+use super::mixer::Mixer;
+
+/// Owns the output stream. cpal streams aren't `Send` on every platform, so
+/// the stream lives on its own thread and is torn down when this is dropped.
+pub struct Engine {
+  sample_rate: u32,
+  device_name: String,
+  lost: Arc<AtomicBool>,
+  shutdown: Option<mpsc::Sender<()>>,
+  thread: Option<JoinHandle<()>>,
+}
+
+impl Engine {
+  /// Open `device` (by name), or the default output device when it's `None`
+  /// or not plugged in, and start rendering `mixer` into it.
+  pub fn start(mixer: Arc<Mutex<Mixer>>, device: Option<&str>) -> Result<Self, String> {
+    let (ready_tx, ready_rx) = mpsc::channel::<Result<(u32, String), String>>();
+    let (shutdown_tx, shutdown_rx) = mpsc::channel::<()>();
+    let device = device.map(str::to_string);
+    let lost = Arc::new(AtomicBool::new(false));
+    let stream_lost = lost.clone();
+
+    let thread = std::thread::Builder::new()
+      .name("zerohz-audio".to_string())
+      .spawn(move || {
+        let stream = match open_stream(mixer, device.as_deref(), stream_lost) {
+          Ok((stream, sample_rate, name)) => {
+            let _ = ready_tx.send(Ok((sample_rate, name)));
+            stream
+          }
+          Err(e) => {
+            let _ = ready_tx.send(Err(e));
+            return;
+          }
+        };
+        // Blocks until the engine is dropped (sender closes).
+        let _ = shutdown_rx.recv();
+        drop(stream);
+      })
+      .map_err(|e| format!("Failed to spawn audio thread: {}", e))?;
+
+    let (sample_rate, device_name) = ready_rx
+      .recv()
+      .map_err(|_| "Audio thread exited during startup".to_string())??;
+
+    Ok(Self {
+      sample_rate,
+      device_name,
+      lost,
+      shutdown: Some(shutdown_tx),
+      thread: Some(thread),
+    })
+  }
+
+  pub fn sample_rate(&self) -> u32 {
+    self.sample_rate
+  }
+
+  pub fn device_name(&self) -> &str {
+    &self.device_name
+  }
+
+  /// The stream reported an error (typically the device was unplugged) and
+  /// has to be reopened.
+  pub fn is_lost(&self) -> bool {
+    self.lost.load(Ordering::Relaxed)
+  }
+}
+
+impl Drop for Engine {
+  fn drop(&mut self) {
+    if let Some(tx) = self.shutdown.take() {
+      let _ = tx.send(());
+    }
+    // Wait for the stream to close so the device is free to reopen.
+    if let Some(thread) = self.thread.take() {
+      let _ = thread.join();
+    }
+  }
+}
+
+fn open_stream(
+  mixer: Arc<Mutex<Mixer>>,
+  preferred: Option<&str>,
+  lost: Arc<AtomicBool>,
+) -> Result<(cpal::Stream, u32, String), String> {
+  let device = devices::resolve_output_device(preferred)
+    .ok_or_else(|| "No audio output device available".to_string())?;
+  let name = device.name().unwrap_or_default();
+  let supported = device
+    .default_output_config()
+    .map_err(|e| format!("Failed to query output config: {}", e))?;
+
+  let sample_rate = supported.sample_rate().0;
+  let config: cpal::StreamConfig = supported.config();
+
+  // The mixer's buffers are resampled to the device rate on load.
+  if let Ok(mut m) = mixer.lock() {
+    m.set_sample_rate(sample_rate);
+  }
+
+  let stream = match supported.sample_format() {
+    SampleFormat::F32 => build_stream::<f32>(&device, &config, mixer, lost),
+    SampleFormat::I16 => build_stream::<i16>(&device, &config, mixer, lost),
+    SampleFormat::U16 => build_stream::<u16>(&device, &config, mixer, lost),
+    SampleFormat::I32 => build_stream::<i32>(&device, &config, mixer, lost),
+    other => Err(format!("Unsupported sample format: {:?}", other)),
+  }?;
+
+  stream
+    .play()
+    .map_err(|e| format!("Failed to start output stream: {}", e))?;
+
+  Ok((stream, sample_rate, name))
+}
+
+fn build_stream<T>(
+  device: &cpal::Device,
+  config: &cpal::StreamConfig,
+  mixer: Arc<Mutex<Mixer>>,
+  lost: Arc<AtomicBool>,
+) -> Result<cpal::Stream, String>
+where
+  T: SizedSample + FromSample<f32>,
+{
+  let channels = config.channels as usize;
+  let mut stereo: Vec<f32> = Vec::new();
+
+  device
+    .build_output_stream(
+      config,
+      move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+        let frames = data.len() / channels;
+        stereo.resize(frames * 2, 0.0);
+
+        match mixer.lock() {
+          Ok(mut m) => m.render(&mut stereo),
+          Err(_) => stereo.fill(0.0),
+        }
+
+        for (frame, lr) in data.chunks_exact_mut(channels).zip(stereo.chunks_exact(2)) {
+          if channels == 1 {
+            frame[0] = T::from_sample((lr[0] + lr[1]) * 0.5);
+            continue;
+          }
+          frame[0] = T::from_sample(lr[0]);
+          frame[1] = T::from_sample(lr[1]);
+          for s in frame.iter_mut().skip(2) {
+            *s = T::from_sample(0.0);
+          }
+        }
+      },
+      move |err| {
+        log::error!("Audio output stream error: {}", err);
+        if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+          lost.store(true, Ordering::Relaxed);
+        }
+      },
+      None,
+    )
+    .map_err(|e| format!("Failed to build output stream: {}", e))
+}
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+
+/// Shortest gap we allow between two triggers of the same layer.
+const MIN_INTERVAL: f64 = 0.5;
+
+/// A "one-shot" layer: instead of looping, a slice of the sound is played at
+/// randomized intervals with random gain and stereo position.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventLayer {
+  /// Mean seconds between triggers.
+  pub interval: f64,
+  /// How far (0~1, fraction of `interval`) each gap may deviate either way.
+  pub jitter: f64,
+  /// Seconds of the sample played per trigger. 0 plays the whole sample.
+  pub duration: f64,
+  pub min_gain: f32,
+  pub max_gain: f32,
+  /// Triggers are placed uniformly in `[-pan_spread, pan_spread]`.
+  pub pan_spread: f32,
+}
+
+impl EventLayer {
+  /// Occasional rolls of thunder instead of a constant storm bed.
+  pub fn thunder() -> Self {
+    Self {
+      interval: 45.0,
+      jitter: 0.6,
+      duration: 14.0,
+      min_gain: 0.6,
+      max_gain: 1.0,
+      pan_spread: 0.8,
+    }
+  }
+
+  pub fn validate(&self) -> Result<(), String> {
+    if !self.interval.is_finite() || !self.jitter.is_finite() || !self.duration.is_finite() {
+      return Err("Event layer timings must be finite".to_string());
+    }
+    if !(0.0..1.0).contains(&self.jitter) {
+      return Err(format!("Invalid jitter: {}", self.jitter));
+    }
+    if self.interval * (1.0 - self.jitter) < MIN_INTERVAL {
+      return Err(format!("Interval too short: {}s", self.interval));
+    }
+    if self.duration < 0.0 {
+      return Err(format!("Invalid duration: {}", self.duration));
+    }
+    if !(0.0..=1.0).contains(&self.min_gain)
+      || !(0.0..=1.0).contains(&self.max_gain)
+      || self.min_gain > self.max_gain
+    {
+      return Err(format!("Invalid gain range: {}~{}", self.min_gain, self.max_gain));
+    }
+    if !(0.0..=1.0).contains(&self.pan_spread) {
+      return Err(format!("Invalid pan spread: {}", self.pan_spread));
+    }
+    Ok(())
+  }
+}
+
+/// SplitMix64. Small, fast and - unlike `rand`'s `StdRng` - guaranteed to
+/// produce the same sequence forever, which keeps seeded runs reproducible.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+  pub fn new(seed: u64) -> Self {
+    Self(seed)
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+  }
+
+  /// Uniform in `[0, 1)`.
+  pub fn next_f64(&mut self) -> f64 {
+    (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  /// Uniform in `[lo, hi)`.
+  pub fn range(&mut self, lo: f64, hi: f64) -> f64 {
+    lo + (hi - lo) * self.next_f64()
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trigger {
+  /// Session time in seconds the trigger was scheduled for.
+  pub at: f64,
+  pub gain: f32,
+  /// -1 (left) ~ 1 (right).
+  pub pan: f32,
+  /// Where in the sample to start, as a fraction (0~1) of its length.
+  pub offset: f64,
+}
+
+/// Schedules triggers for one layer against session time.
+#[derive(Clone, Debug)]
+pub struct EventScheduler {
+  layer: EventLayer,
+  rng: Rng,
+  next_at: f64,
+}
+
+impl EventScheduler {
+  pub fn new(layer: EventLayer, seed: u64) -> Self {
+    let mut rng = Rng::new(seed);
+    // First trigger lands somewhere inside the first gap so a fresh session
+    // doesn't always open with (or wait the full interval for) an event.
+    let next_at = next_gap(&layer, &mut rng) * rng.next_f64();
+    Self { layer, rng, next_at }
+  }
+
+  pub fn layer(&self) -> &EventLayer {
+    &self.layer
+  }
+
+  /// Return the next trigger due at or before `now`, if any. Call repeatedly
+  /// until it returns `None` to drain everything due.
+  pub fn poll(&mut self, now: f64) -> Option<Trigger> {
+    if now < self.next_at {
+      return None;
+    }
+
+    let at = self.next_at;
+    let layer = self.layer;
+    let gain = self.rng.range(layer.min_gain as f64, layer.max_gain as f64) as f32;
+    let pan = self.rng.range(-layer.pan_spread as f64, layer.pan_spread as f64) as f32;
+    let offset = self.rng.next_f64();
+
+    self.next_at = at + next_gap(&layer, &mut self.rng);
+    Some(Trigger { at, gain, pan, offset })
+  }
+}
+
+fn next_gap(layer: &EventLayer, rng: &mut Rng) -> f64 {
+  let deviation = rng.range(-layer.jitter, layer.jitter);
+  (layer.interval * (1.0 + deviation)).max(MIN_INTERVAL)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn drain(scheduler: &mut EventScheduler, until: f64) -> Vec<Trigger> {
+    std::iter::from_fn(|| scheduler.poll(until)).collect()
+  }
+
+  #[test]
+  fn same_seed_gives_the_same_schedule() {
+    let a = drain(&mut EventScheduler::new(EventLayer::thunder(), 42), 3600.0);
+    let b = drain(&mut EventScheduler::new(EventLayer::thunder(), 42), 3600.0);
+    let c = drain(&mut EventScheduler::new(EventLayer::thunder(), 43), 3600.0);
+    assert!(!a.is_empty());
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+  }
+
+  #[test]
+  fn gaps_stay_within_the_jitter() {
+    let layer = EventLayer::thunder();
+    let triggers = drain(&mut EventScheduler::new(layer, 7), 24.0 * 3600.0);
+    let lo = layer.interval * (1.0 - layer.jitter);
+    let hi = layer.interval * (1.0 + layer.jitter);
+    assert!(triggers[0].at >= 0.0 && triggers[0].at < hi);
+    for pair in triggers.windows(2) {
+      let gap = pair[1].at - pair[0].at;
+      assert!((lo..hi).contains(&gap), "gap {} outside {}..{}", gap, lo, hi);
+    }
+    for t in &triggers {
+      assert!((layer.min_gain..=layer.max_gain).contains(&t.gain));
+      assert!((-layer.pan_spread..=layer.pan_spread).contains(&t.pan));
+      assert!((0.0..1.0).contains(&t.offset));
+    }
+  }
+
+  #[test]
+  fn gaps_are_never_shorter_than_min_interval() {
+    // `validate` would refuse this layer; `next_gap` clamps regardless.
+    let layer = EventLayer {
+      interval: 0.6,
+      jitter: 0.99,
+      ..EventLayer::thunder()
+    };
+    let mut rng = Rng::new(1);
+    for _ in 0..10_000 {
+      assert!(next_gap(&layer, &mut rng) >= MIN_INTERVAL);
+    }
+    let triggers = drain(&mut EventScheduler::new(layer, 1), 600.0);
+    for pair in triggers.windows(2) {
+      // Times are sums of gaps, so allow for rounding.
+      assert!(pair[1].at - pair[0].at >= MIN_INTERVAL - 1e-9);
+    }
+  }
+
+  #[test]
+  fn polls_nothing_before_the_next_trigger() {
+    let mut scheduler = EventScheduler::new(EventLayer::thunder(), 3);
+    let first = scheduler.poll(3600.0).unwrap();
+    assert!(scheduler.poll(first.at).is_none());
+  }
+
+  #[test]
+  fn rejects_intervals_below_min_interval() {
+    let layer = EventLayer {
+      interval: 1.0,
+      jitter: 0.6,
+      ..EventLayer::thunder()
+    };
+    assert!(layer.validate().is_err());
+    assert!(EventLayer::thunder().validate().is_ok());
+  }
+}
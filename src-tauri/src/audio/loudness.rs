@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::decoder::SampleBuffer;
+use crate::util;
+
+/// Level every sound is normalized to. Close to the median of the bundled
+/// loops (about -29.6 LUFS), so normalizing moves each one by a few dB
+/// rather than making the whole mix louder or quieter.
+pub const TARGET_LUFS: f64 = -28.0;
+/// Limits on the correction so a near-silent or broken file can't be
+/// boosted into noise.
+const MAX_BOOST_DB: f64 = 12.0;
+const MAX_CUT_DB: f64 = -24.0;
+
+/// Bump when the analysis changes so cached results are recomputed.
+const MANIFEST_VERSION: u32 = 1;
+
+// BS.1770-4 gating: 400 ms blocks, 75% overlap.
+const BLOCK_SECONDS: f64 = 0.4;
+const BLOCK_STEP: f64 = 0.1;
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+#[derive(Clone, Copy, Debug)]
+struct Biquad {
+  b: [f64; 3],
+  a: [f64; 2],
+}
+
+// Both K-weighting stages are derived from their analog prototypes (as in
+// libebur128) so they're valid at any sample rate; BS.1770 only tabulates
+// coefficients for 48 kHz.
+impl Biquad {
+  /// Stage 1: the head's acoustic effect, a +4 dB high shelf.
+  fn shelf(sample_rate: f64) -> Self {
+    let (f0, gain_db, q) = (1_681.974_450_955_533, 3.999_843_853_973_347, 0.707_175_236_955_419_6);
+    let k = (PI * f0 / sample_rate).tan();
+    let vh = 10f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.499_666_774_154_541_6);
+    let a0 = 1.0 + k / q + k * k;
+    Self {
+      b: [
+        (vh + vb * k / q + k * k) / a0,
+        2.0 * (k * k - vh) / a0,
+        (vh - vb * k / q + k * k) / a0,
+      ],
+      a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    }
+  }
+
+  /// Stage 2: the RLB high-pass.
+  fn highpass(sample_rate: f64) -> Self {
+    let (f0, q) = (38.135_470_876_024_44, 0.500_327_037_323_877_3);
+    let k = (PI * f0 / sample_rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    Self {
+      b: [1.0, -2.0, 1.0],
+      a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    }
+  }
+}
+
+/// Direct form I state for one channel.
+#[derive(Clone, Copy, Default)]
+struct FilterState {
+  x: [f64; 2],
+  y: [f64; 2],
+}
+
+impl FilterState {
+  #[inline]
+  fn tick(&mut self, f: &Biquad, x: f64) -> f64 {
+    let y = f.b[0] * x + f.b[1] * self.x[0] + f.b[2] * self.x[1] - f.a[0] * self.y[0] - f.a[1] * self.y[1];
+    self.x = [x, self.x[0]];
+    self.y = [y, self.y[0]];
+    y
+  }
+}
+
+/// Result of analysing one sound.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Loudness {
+  /// Gated integrated loudness (LUFS); `None` when everything is below the
+  /// absolute gate.
+  pub integrated: Option<f64>,
+  /// Sample peak, linear.
+  pub peak: f32,
+}
+
+impl Loudness {
+  /// Linear gain that brings the sound to [`TARGET_LUFS`], limited so a
+  /// full-volume slider can't push its peaks past full scale.
+  pub fn gain(&self) -> f32 {
+    let Some(integrated) = self.integrated else {
+      return 1.0;
+    };
+    let db = (TARGET_LUFS - integrated).clamp(MAX_CUT_DB, MAX_BOOST_DB);
+    let mut gain = 10f64.powf(db / 20.0);
+    if self.peak > 0.0 {
+      gain = gain.min(1.0 / self.peak as f64);
+    }
+    gain as f32
+  }
+}
+
+/// ITU-R BS.1770-4 / EBU R128 integrated loudness of a stereo buffer.
+pub fn analyze(buffer: &SampleBuffer) -> Loudness {
+  let rate = buffer.sample_rate as f64;
+  let shelf = Biquad::shelf(rate);
+  let highpass = Biquad::highpass(rate);
+  let mut stages = [[FilterState::default(); 2]; 2];
+
+  let step = ((BLOCK_STEP * rate).round() as usize).max(1);
+  let block = (BLOCK_SECONDS * rate).round() as usize;
+  let frames = buffer.frames();
+
+  // Mean square of the K-weighted signal per 100 ms step, summed over both
+  // channels (L/R weight 1). Blocks are then four consecutive steps.
+  let mut steps: Vec<f64> = Vec::with_capacity(frames / step.max(1) + 1);
+  let mut acc = 0.0;
+  let mut peak = 0.0f32;
+  for (i, frame) in buffer.samples.chunks_exact(2).enumerate() {
+    for (ch, &sample) in frame.iter().enumerate() {
+      peak = peak.max(sample.abs());
+      let [s1, s2] = &mut stages[ch];
+      let y = s2.tick(&highpass, s1.tick(&shelf, sample as f64));
+      acc += y * y;
+    }
+    if (i + 1) % step == 0 {
+      steps.push(acc / step as f64);
+      acc = 0.0;
+    }
+  }
+
+  let per_block = block / step;
+  let blocks: Vec<f64> = steps
+    .windows(per_block)
+    .map(|w| w.iter().sum::<f64>() / per_block as f64)
+    .collect();
+
+  Loudness {
+    integrated: gated_loudness(&blocks),
+    peak,
+  }
+}
+
+fn block_loudness(mean_square: f64) -> f64 {
+  -0.691 + 10.0 * mean_square.log10()
+}
+
+fn gated_loudness(blocks: &[f64]) -> Option<f64> {
+  let above_absolute: Vec<f64> = blocks
+    .iter()
+    .copied()
+    .filter(|&z| z > 0.0 && block_loudness(z) > ABSOLUTE_GATE_LUFS)
+    .collect();
+  if above_absolute.is_empty() {
+    return None;
+  }
+
+  let mean = above_absolute.iter().sum::<f64>() / above_absolute.len() as f64;
+  let relative_gate = block_loudness(mean) + RELATIVE_GATE_LU;
+  let gated: Vec<f64> = above_absolute
+    .into_iter()
+    .filter(|&z| block_loudness(z) > relative_gate)
+    .collect();
+  if gated.is_empty() {
+    return None;
+  }
+  Some(block_loudness(gated.iter().sum::<f64>() / gated.len() as f64))
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+  /// Identifies the analysed file, so a replaced asset is measured again.
+  pub fingerprint: String,
+  #[serde(flatten)]
+  pub loudness: Loudness,
+  pub gain: f32,
+}
+
+/// Analysis results cached on disk, keyed by sound id.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LoudnessManifest {
+  version: u32,
+  entries: HashMap<String, ManifestEntry>,
+  #[serde(skip)]
+  path: Option<PathBuf>,
+}
+
+impl LoudnessManifest {
+  /// Load the manifest at `path`; a missing, unreadable or outdated file
+  /// starts empty.
+  pub fn load(path: PathBuf) -> Self {
+    let mut manifest = std::fs::read(&path)
+      .ok()
+      .and_then(|bytes| serde_json::from_slice::<Self>(&bytes).ok())
+      .filter(|m| m.version == MANIFEST_VERSION)
+      .unwrap_or_default();
+    manifest.version = MANIFEST_VERSION;
+    manifest.path = Some(path);
+    manifest
+  }
+
+  pub fn entries(&self) -> &HashMap<String, ManifestEntry> {
+    &self.entries
+  }
+
+  /// Cached entry for `sound_id` if it was computed from the same file.
+  pub fn get(&self, sound_id: &str, fingerprint: &str) -> Option<&ManifestEntry> {
+    self.entries.get(sound_id).filter(|e| e.fingerprint == fingerprint)
+  }
+
+  pub fn insert(&mut self, sound_id: &str, fingerprint: String, loudness: Loudness) -> ManifestEntry {
+    let entry = ManifestEntry {
+      fingerprint,
+      gain: loudness.gain(),
+      loudness,
+    };
+    self.entries.insert(sound_id.to_string(), entry.clone());
+    entry
+  }
+
+  pub fn save(&self) -> Result<(), String> {
+    let Some(path) = &self.path else {
+      return Ok(());
+    };
+    let json = serde_json::to_vec_pretty(self).map_err(|e| e.to_string())?;
+    util::write_atomic(path, &json).map_err(|e| e.to_string())
+  }
+}
+
+/// Cheap content fingerprint (length + FNV-1a) of an encoded file.
+pub fn fingerprint(bytes: &[u8]) -> String {
+  let hash = bytes
+    .iter()
+    .fold(0xcbf2_9ce4_8422_2325u64, |h, &b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3));
+  format!("{:x}-{:016x}", bytes.len(), hash)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RATE: u32 = 48_000;
+
+  /// A 1 kHz sine on both channels with the given RMS, in dBFS.
+  fn sine(rms_db: f64, seconds: f64) -> Vec<f32> {
+    let amplitude = 10f64.powf(rms_db / 20.0) * 2f64.sqrt();
+    (0..(seconds * RATE as f64) as usize)
+      .flat_map(|i| {
+        let s = (amplitude * (2.0 * PI * 1000.0 * i as f64 / RATE as f64).sin()) as f32;
+        [s, s]
+      })
+      .collect()
+  }
+
+  fn silence(seconds: f64) -> Vec<f32> {
+    vec![0.0; (seconds * RATE as f64) as usize * 2]
+  }
+
+  fn loudness(samples: Vec<f32>) -> Loudness {
+    analyze(&SampleBuffer {
+      sample_rate: RATE,
+      samples,
+    })
+  }
+
+  #[test]
+  fn k_weighting_matches_bs_1770_at_48_khz() {
+    let shelf = Biquad::shelf(48_000.0);
+    let expected = [1.535_124_859_586_97, -2.691_696_189_406_38, 1.198_392_810_852_85];
+    for (b, e) in shelf.b.iter().zip(expected) {
+      assert!((b - e).abs() < 1e-8, "{} != {}", b, e);
+    }
+    for (a, e) in shelf.a.iter().zip([-1.690_659_293_182_41, 0.732_480_774_215_85]) {
+      assert!((a - e).abs() < 1e-8, "{} != {}", a, e);
+    }
+
+    let highpass = Biquad::highpass(48_000.0);
+    assert_eq!(highpass.b, [1.0, -2.0, 1.0]);
+    for (a, e) in highpass.a.iter().zip([-1.990_047_454_833_98, 0.990_072_250_366_21]) {
+      assert!((a - e).abs() < 1e-8, "{} != {}", a, e);
+    }
+  }
+
+  #[test]
+  fn a_1_khz_sine_reads_its_level() {
+    // K-weighting adds about 0.69 dB at 1 kHz, which the -0.691 offset
+    // cancels; two channels add 3 dB over the per-channel RMS.
+    for rms_db in [-20.0, -26.0, -40.0] {
+      let measured = loudness(sine(rms_db, 5.0));
+      let lufs = measured.integrated.unwrap();
+      assert!((lufs - (rms_db + 3.01)).abs() < 0.1, "{} dBFS RMS: {} LUFS", rms_db, lufs);
+      assert!((measured.peak as f64 - 10f64.powf(rms_db / 20.0) * 2f64.sqrt()).abs() < 1e-3);
+    }
+  }
+
+  #[test]
+  fn silence_is_gated_out() {
+    let silent = loudness(silence(5.0));
+    assert_eq!(silent.integrated, None);
+    assert_eq!(silent.peak, 0.0);
+    assert_eq!(silent.gain(), 1.0);
+
+    // Silence around a tone doesn't lower it: only the few blocks that
+    // overlap the tone's edges count besides the tone's own.
+    let alone = loudness(sine(-26.0, 20.0)).integrated.unwrap();
+    let padded: Vec<f32> = [silence(20.0), sine(-26.0, 20.0), silence(20.0)].concat();
+    let padded = loudness(padded).integrated.unwrap();
+    assert!((padded - alone).abs() < 0.1, "{} != {}", padded, alone);
+
+    // Below the absolute gate (-70 LUFS), a tone is as good as silence.
+    assert_eq!(loudness(sine(-80.0, 5.0)).integrated, None);
+  }
+}
@@ -0,0 +1,407 @@
+use std::collections::HashMap;
+use std::f32::consts::SQRT_2;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::automation::Automation;
+use super::decoder::SampleBuffer;
+use super::dsp::{pan_gains, EffectChain, Smoothed, SoundEffect};
+use super::events::{EventLayer, EventScheduler, Rng, Trigger};
+
+/// Time constant for volume/mute changes (seconds).
+const GAIN_SMOOTHING: f32 = 0.05;
+/// Below this a fading-out track is considered silent and released.
+const SILENCE: f32 = 1e-4;
+/// Longest fade applied to the edges of an event-layer slice (seconds).
+const MAX_EVENT_FADE: f64 = 1.5;
+
+/// Section of a sound that repeats. Playback starts at the top of the file
+/// and wraps back to `start` once it reaches `end` (or the end of the file).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoopRegion {
+  /// Seconds.
+  pub start: f64,
+  /// Seconds; `None` loops to the end of the file.
+  #[serde(default)]
+  pub end: Option<f64>,
+}
+
+struct Track {
+  buffer: Option<Arc<SampleBuffer>>,
+  /// Loop bounds in frames of the attached buffer.
+  loop_start: usize,
+  loop_end: usize,
+  active: bool,
+  loading: bool,
+  volume: f32,
+  /// Loudness normalization for the loaded buffer.
+  normalization: f32,
+  gain: Smoothed,
+  effect: SoundEffect,
+  chain: EffectChain,
+  position: usize,
+}
+
+/// A one-shot slice of an event-layer sound currently playing.
+struct Voice {
+  sound_id: String,
+  buffer: Arc<SampleBuffer>,
+  start: usize,
+  end: usize,
+  position: usize,
+  fade: usize,
+  gain: f32,
+  pan: (f32, f32),
+}
+
+/// Software mixer rendered from the output stream callback.
+///
+/// The session clock is the number of frames rendered while at least one
+/// sound is active and the mix isn't muted; automation curves and event
+/// layers are evaluated against it, so they stay sample-locked to what the
+/// user hears.
+pub struct Mixer {
+  sample_rate: u32,
+  tracks: HashMap<String, Track>,
+  voices: Vec<Voice>,
+  muted: bool,
+  /// Scales every track; not part of the frontend state (set from the OS
+  /// media controls).
+  master_volume: f32,
+  session_frames: u64,
+  automation: Automation,
+  layers: HashMap<String, EventLayer>,
+  schedulers: HashMap<String, EventScheduler>,
+  seed: u64,
+  gains: Vec<f32>,
+  bus: Vec<f32>,
+}
+
+impl Mixer {
+  pub fn new(
+    sample_rate: u32,
+    automation: Automation,
+    layers: HashMap<String, EventLayer>,
+    seed: u64,
+  ) -> Self {
+    Self {
+      sample_rate,
+      tracks: HashMap::new(),
+      voices: Vec::new(),
+      muted: false,
+      master_volume: 1.0,
+      session_frames: 0,
+      automation,
+      layers,
+      schedulers: HashMap::new(),
+      seed,
+      gains: Vec::new(),
+      bus: Vec::new(),
+    }
+  }
+
+  pub fn sample_rate(&self) -> u32 {
+    self.sample_rate
+  }
+
+  pub fn session_seconds(&self) -> f64 {
+    self.session_frames as f64 / self.sample_rate as f64
+  }
+
+  pub fn master_volume(&self) -> f32 {
+    self.master_volume
+  }
+
+  pub fn set_master_volume(&mut self, volume: f32) {
+    self.master_volume = if volume.is_finite() { volume.clamp(0.0, 1.0) } else { 1.0 };
+  }
+
+  pub fn automation(&self) -> &Automation {
+    &self.automation
+  }
+
+  pub fn automation_mut(&mut self) -> &mut Automation {
+    &mut self.automation
+  }
+
+  pub fn event_layers(&self) -> &HashMap<String, EventLayer> {
+    &self.layers
+  }
+
+  pub fn set_event_layer(&mut self, sound_id: &str, layer: Option<EventLayer>) {
+    self.schedulers.remove(sound_id);
+    match layer {
+      Some(layer) => {
+        self.layers.insert(sound_id.to_string(), layer);
+      }
+      None => {
+        self.layers.remove(sound_id);
+        self.voices.retain(|v| v.sound_id != sound_id);
+      }
+    }
+    if let Some(track) = self.tracks.get_mut(sound_id) {
+      track.position = 0;
+    }
+  }
+
+  /// Apply the frontend's playback state. Returns the ids whose audio still
+  /// needs to be decoded (see [`Mixer::take_pending_loads`]).
+  pub fn set_state(
+    &mut self,
+    active: &[String],
+    volumes: &HashMap<String, f32>,
+    effects: &HashMap<String, SoundEffect>,
+    muted: bool,
+  ) -> Vec<String> {
+    self.muted = muted;
+
+    for (id, track) in self.tracks.iter_mut() {
+      track.active = active.contains(id);
+    }
+
+    for id in active {
+      let sample_rate = self.sample_rate;
+      self.tracks.entry(id.clone()).or_insert_with(|| Track {
+        buffer: None,
+        loop_start: 0,
+        loop_end: 0,
+        active: true,
+        loading: false,
+        volume: 0.0,
+        normalization: 1.0,
+        gain: Smoothed::new(0.0, GAIN_SMOOTHING, sample_rate),
+        effect: SoundEffect::default(),
+        chain: EffectChain::new(SoundEffect::default(), sample_rate),
+        position: 0,
+      });
+    }
+
+    for (id, track) in self.tracks.iter_mut() {
+      if let Some(volume) = volumes.get(id) {
+        track.volume = volume.clamp(0.0, 1.0);
+      }
+      let effect = effects.get(id).copied().unwrap_or_default().sanitized();
+      if effect != track.effect {
+        track.effect = effect;
+        track.chain.configure(effect, self.sample_rate);
+      }
+    }
+
+    // Turning every sound off ends the session; the next one starts fresh.
+    if active.is_empty() {
+      self.session_frames = 0;
+      self.schedulers.clear();
+      self.seed = Rng::new(self.seed).next_u64();
+    }
+
+    self.take_pending_loads()
+  }
+
+  /// Active tracks without audio. They're marked as loading so callers only
+  /// spawn one loader per sound.
+  pub fn take_pending_loads(&mut self) -> Vec<String> {
+    self
+      .tracks
+      .iter_mut()
+      .filter(|(_, t)| t.active && t.buffer.is_none() && !t.loading)
+      .map(|(id, t)| {
+        t.loading = true;
+        id.clone()
+      })
+      .collect()
+  }
+
+  /// Switch to a new output rate. Decoded audio is dropped and has to be
+  /// loaded again at the new rate via [`Mixer::take_pending_loads`].
+  pub fn set_sample_rate(&mut self, sample_rate: u32) {
+    if sample_rate == self.sample_rate {
+      return;
+    }
+    let seconds = self.session_seconds();
+    self.sample_rate = sample_rate;
+    self.session_frames = (seconds * sample_rate as f64) as u64;
+    self.voices.clear();
+    for track in self.tracks.values_mut() {
+      track.buffer = None;
+      track.loading = false;
+      track.position = 0;
+      track.gain = Smoothed::new(0.0, GAIN_SMOOTHING, sample_rate);
+      track.chain = EffectChain::new(track.effect, sample_rate);
+    }
+  }
+
+  /// Hand a decoded (and already resampled) buffer to its track, along with
+  /// its loudness normalization gain and loop points.
+  pub fn attach(
+    &mut self,
+    sound_id: &str,
+    buffer: Arc<SampleBuffer>,
+    normalization: f32,
+    loop_region: Option<LoopRegion>,
+  ) {
+    if let Some(track) = self.tracks.get_mut(sound_id) {
+      track.loading = false;
+      // A loader that raced a device switch resampled to the old rate.
+      if track.active && buffer.sample_rate == self.sample_rate {
+        let frames = buffer.frames();
+        let to_frame = |seconds: f64| ((seconds.max(0.0) * buffer.sample_rate as f64) as usize).min(frames);
+        let (start, end) = match loop_region {
+          Some(region) => (to_frame(region.start), region.end.map(to_frame).unwrap_or(frames)),
+          None => (0, frames),
+        };
+        // A degenerate region falls back to looping the whole file.
+        (track.loop_start, track.loop_end) = if start < end { (start, end) } else { (0, frames) };
+        track.buffer = Some(buffer);
+        track.normalization = normalization;
+        track.position = 0;
+      }
+    }
+  }
+
+  pub fn load_failed(&mut self, sound_id: &str) {
+    if let Some(track) = self.tracks.get_mut(sound_id) {
+      track.loading = false;
+    }
+  }
+
+  /// Render interleaved stereo into `out`.
+  pub fn render(&mut self, out: &mut [f32]) {
+    out.fill(0.0);
+    let frames = out.len() / 2;
+    if frames == 0 {
+      return;
+    }
+
+    let any_active = self.tracks.values().any(|t| t.active);
+    let clock_running = any_active && !self.muted;
+    let t0 = self.session_seconds();
+    let t1 = t0 + frames as f64 / self.sample_rate as f64;
+
+    self.gains.resize(frames, 0.0);
+    self.bus.resize(frames * 2, 0.0);
+
+    for (id, track) in self.tracks.iter_mut() {
+      let Some(buffer) = track.buffer.clone() else {
+        continue;
+      };
+
+      let automated = self.automation.gain(id, t0);
+      let target = if track.active && !self.muted {
+        track.volume * track.normalization * automated * self.master_volume
+      } else {
+        0.0
+      };
+      for g in self.gains.iter_mut() {
+        *g = track.gain.next(target);
+      }
+
+      // Source -> bus: either the loop or this layer's one-shot voices.
+      self.bus.fill(0.0);
+      if let Some(layer) = self.layers.get(id) {
+        if track.active && clock_running {
+          let seed = self.seed ^ hash_id(id);
+          let scheduler = self
+            .schedulers
+            .entry(id.clone())
+            .or_insert_with(|| EventScheduler::new(*layer, seed));
+          while let Some(trigger) = scheduler.poll(t1) {
+            self.voices.push(spawn_voice(id, &buffer, scheduler.layer(), &trigger, self.sample_rate));
+          }
+        }
+        for voice in self.voices.iter_mut().filter(|v| &v.sound_id == id) {
+          mix_voice(voice, &mut self.bus);
+        }
+      } else {
+        mix_loop(&buffer, &mut track.position, track.loop_start, track.loop_end, &mut self.bus);
+      }
+
+      // Bus -> insert chain -> fader -> master.
+      for ((dst, src), gain) in out.chunks_exact_mut(2).zip(self.bus.chunks_exact(2)).zip(&self.gains) {
+        let (l, r) = track.chain.process(src[0], src[1]);
+        dst[0] += l * gain;
+        dst[1] += r * gain;
+      }
+
+      if !track.active && track.gain.value() < SILENCE {
+        track.buffer = None;
+        track.position = 0;
+        self.voices.retain(|v| &v.sound_id != id);
+      }
+    }
+
+    self.voices.retain(|v| v.position < v.end);
+    self.tracks.retain(|_, t| t.active || t.buffer.is_some() || t.loading);
+
+    for s in out.iter_mut() {
+      *s = s.clamp(-1.0, 1.0);
+    }
+
+    if clock_running {
+      self.session_frames += frames as u64;
+    }
+  }
+}
+
+fn mix_loop(buffer: &SampleBuffer, position: &mut usize, loop_start: usize, loop_end: usize, out: &mut [f32]) {
+  let end = loop_end.min(buffer.frames());
+  if loop_start >= end {
+    return;
+  }
+  for frame in out.chunks_exact_mut(2) {
+    if *position >= end {
+      *position = loop_start;
+    }
+    let (l, r) = buffer.frame(*position);
+    frame[0] += l;
+    frame[1] += r;
+    *position += 1;
+  }
+}
+
+fn spawn_voice(sound_id: &str, buffer: &Arc<SampleBuffer>, layer: &EventLayer, trigger: &Trigger, sample_rate: u32) -> Voice {
+  let len = buffer.frames();
+  let slice = if layer.duration > 0.0 {
+    ((layer.duration * sample_rate as f64) as usize).min(len)
+  } else {
+    len
+  };
+  let start = ((len - slice) as f64 * trigger.offset) as usize;
+  let fade = ((MAX_EVENT_FADE * sample_rate as f64) as usize).min(slice / 4).max(1);
+  let (pan_l, pan_r) = pan_gains(trigger.pan);
+
+  Voice {
+    sound_id: sound_id.to_string(),
+    buffer: buffer.clone(),
+    start,
+    end: start + slice,
+    position: start,
+    fade,
+    gain: trigger.gain,
+    // Normalized so a centred trigger plays at unity on both sides.
+    pan: (pan_l * SQRT_2, pan_r * SQRT_2),
+  }
+}
+
+fn mix_voice(voice: &mut Voice, out: &mut [f32]) {
+  for frame in out.chunks_exact_mut(2) {
+    if voice.position >= voice.end {
+      break;
+    }
+    let from_start = voice.position - voice.start;
+    let to_end = voice.end - voice.position;
+    let edge = from_start.min(to_end).min(voice.fade) as f32 / voice.fade as f32;
+
+    let (l, r) = voice.buffer.frame(voice.position);
+    // Events are positioned as point sources, so fold to mono before panning.
+    let mono = (l + r) * 0.5 * voice.gain * edge;
+    frame[0] += mono * voice.pan.0;
+    frame[1] += mono * voice.pan.1;
+    voice.position += 1;
+  }
+}
+
+/// FNV-1a, used to give every layer its own stream from the session seed.
+fn hash_id(id: &str) -> u64 {
+  id.bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
@@ -0,0 +1,517 @@
+pub mod automation;
+pub mod decoder;
+pub mod devices;
+pub mod dsp;
+pub mod engine;
+pub mod events;
+pub mod loudness;
+pub mod mixer;
+pub mod playback;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::watch;
+
+use crate::catalog::{self, SoundAsset};
+use crate::{history, util};
+
+use automation::{Automation, Envelope, Keyframe};
+use devices::{OutputDevice, OutputStatus};
+use dsp::SoundEffect;
+use engine::Engine;
+use events::EventLayer;
+use loudness::{LoudnessManifest, ManifestEntry};
+use mixer::{LoopRegion, Mixer};
+use playback::PlaybackState;
+
+const AUDIO_STORE_FILE: &str = "audio.json";
+const AUTOMATION_STORE_KEY: &str = "automation";
+const EVENT_LAYERS_STORE_KEY: &str = "eventLayers";
+const OUTPUT_DEVICE_STORE_KEY: &str = "outputDevice";
+const MASTER_VOLUME_STORE_KEY: &str = "masterVolume";
+/// Cached loudness analysis, kept in the app cache dir.
+const LOUDNESS_MANIFEST_FILE: &str = "loudness.json";
+
+/// cpal has no portable hotplug notification, so default-device changes
+/// are picked up by polling.
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Rate the mixer starts at before an output device has been opened.
+const DEFAULT_SAMPLE_RATE: u32 = 48_000;
+
+pub struct AudioState {
+  mixer: Arc<Mutex<Mixer>>,
+  output: Mutex<Output>,
+  loudness: Arc<Mutex<LoudnessManifest>>,
+  playback: watch::Sender<PlaybackState>,
+}
+
+impl AudioState {
+  /// Notified whenever the selection, mute or master volume changes.
+  pub fn subscribe_playback(&self) -> watch::Receiver<PlaybackState> {
+    self.playback.subscribe()
+  }
+}
+
+struct Output {
+  /// Device name picked by the user; `None` follows the system default.
+  selected: Option<String>,
+  /// Dropping the engine stops output.
+  engine: Option<Engine>,
+  default_device: Option<String>,
+}
+
+impl Output {
+  fn status(&self) -> OutputStatus {
+    OutputStatus {
+      selected: self.selected.clone(),
+      active: self.engine.as_ref().map(|e| e.device_name().to_string()),
+      default_device: self.default_device.clone(),
+    }
+  }
+}
+
+fn default_event_layers() -> HashMap<String, EventLayer> {
+  HashMap::from([("thunder".to_string(), EventLayer::thunder())])
+}
+
+/// Open the output device and register audio state. Playback failing to
+/// start (e.g. no output device) is logged, not fatal: the mixer still
+/// tracks state so the UI keeps working.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+  let store = app.store(AUDIO_STORE_FILE).ok();
+  let automation = store
+    .as_ref()
+    .and_then(|s| s.get(AUTOMATION_STORE_KEY))
+    .and_then(|v| serde_json::from_value::<Automation>(v).ok())
+    .unwrap_or_default();
+  let layers = store
+    .as_ref()
+    .and_then(|s| s.get(EVENT_LAYERS_STORE_KEY))
+    .and_then(|v| serde_json::from_value::<HashMap<String, EventLayer>>(v).ok())
+    .unwrap_or_else(default_event_layers);
+  let selected = store
+    .as_ref()
+    .and_then(|s| s.get(OUTPUT_DEVICE_STORE_KEY))
+    .and_then(|v| v.as_str().map(str::to_string));
+  let master_volume = store
+    .as_ref()
+    .and_then(|s| s.get(MASTER_VOLUME_STORE_KEY))
+    .and_then(|v| v.as_f64())
+    .unwrap_or(1.0) as f32;
+
+  let seed = util::now_millis();
+  let mut mixer = Mixer::new(DEFAULT_SAMPLE_RATE, automation, layers, seed);
+  mixer.set_master_volume(master_volume);
+  let playback = PlaybackState {
+    volume: mixer.master_volume(),
+    ..Default::default()
+  };
+  let mixer = Arc::new(Mutex::new(mixer));
+
+  let engine = start_engine(&mixer, selected.as_deref());
+  let output = Output {
+    selected,
+    engine,
+    default_device: devices::default_output_name(),
+  };
+
+  let manifest_path = app
+    .path()
+    .app_cache_dir()
+    .map(|dir| dir.join(LOUDNESS_MANIFEST_FILE))
+    .unwrap_or_else(|_| std::env::temp_dir().join(LOUDNESS_MANIFEST_FILE));
+
+  app.manage(AudioState {
+    mixer,
+    output: Mutex::new(output),
+    loudness: Arc::new(Mutex::new(LoudnessManifest::load(manifest_path))),
+    playback: watch::Sender::new(playback),
+  });
+  spawn_device_watcher(app.clone());
+}
+
+fn start_engine(mixer: &Arc<Mutex<Mixer>>, device: Option<&str>) -> Option<Engine> {
+  match Engine::start(mixer.clone(), device) {
+    Ok(engine) => {
+      log::info!(
+        "Audio output started on {:?} at {} Hz",
+        engine.device_name(),
+        engine.sample_rate()
+      );
+      Some(engine)
+    }
+    Err(e) => {
+      log::error!("Failed to start audio output: {}", e);
+      None
+    }
+  }
+}
+
+/// Reopen the output on the currently selected device. Sounds that were
+/// decoded for a different rate are reloaded.
+fn restart_output<R: Runtime>(app: &AppHandle<R>, state: &AudioState, output: &mut Output) {
+  // Close the old stream first; some backends won't open a device twice.
+  output.engine = None;
+  output.engine = start_engine(&state.mixer, output.selected.as_deref());
+
+  let pending = state
+    .mixer
+    .lock()
+    .map(|mut m| m.take_pending_loads())
+    .unwrap_or_default();
+  spawn_loaders(app, state, pending);
+}
+
+/// Follow the system default device, fall back when the selected device is
+/// unplugged and switch back when it returns.
+fn spawn_device_watcher<R: Runtime>(app: AppHandle<R>) {
+  std::thread::spawn(move || loop {
+    std::thread::sleep(DEVICE_POLL_INTERVAL);
+    let state = app.state::<AudioState>();
+    let Ok(mut output) = state.output.lock() else {
+      return;
+    };
+
+    let before = output.status();
+    let default_device = devices::default_output_name();
+    let default_changed = default_device != output.default_device;
+    output.default_device = default_device;
+
+    let active = output.engine.as_ref().map(|e| e.device_name().to_string());
+    let lost = output.engine.as_ref().map(|e| e.is_lost()).unwrap_or(false);
+    let needs_restart = lost
+      || match output.selected.as_deref() {
+        // Following the default: move whenever it moves.
+        None => default_changed,
+        // Pinned but fell back: go back as soon as the device returns.
+        Some(selected) => active.as_deref() != Some(selected) && (default_changed || devices::is_available(selected)),
+      };
+    if needs_restart {
+      restart_output(&app, &state, &mut output);
+    }
+
+    let after = output.status();
+    if after != before {
+      let _ = app.emit("audio-device-changed", after);
+    }
+  });
+}
+
+/// Encoded audio of a sound, ready to decode.
+struct SoundFile {
+  bytes: Vec<u8>,
+  /// Extension, used as a decoder hint.
+  ext: String,
+  loop_region: Option<LoopRegion>,
+}
+
+impl SoundFile {
+  fn read(path: &std::path::Path, loop_region: Option<LoopRegion>) -> Result<Self, String> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_string();
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(Self { bytes, ext, loop_region })
+  }
+
+  fn bundled(bytes: Vec<u8>) -> Self {
+    Self {
+      bytes,
+      ext: "m4a".to_string(),
+      loop_region: None,
+    }
+  }
+}
+
+fn read_sound<R: Runtime>(app: &AppHandle<R>, sound_id: &str) -> Result<SoundFile, String> {
+  let sound = catalog::find(app, sound_id).ok_or_else(|| format!("Unknown sound: {}", sound_id))?;
+  read_catalog_sound(app, sound)
+}
+
+fn read_catalog_sound<R: Runtime>(app: &AppHandle<R>, sound: catalog::CatalogSound) -> Result<SoundFile, String> {
+  let path = match sound.asset {
+    SoundAsset::File { path } => return SoundFile::read(&path, sound.loop_region),
+    SoundAsset::Bundled { path } => path,
+  };
+
+  if let Some(asset) = app.asset_resolver().get(path.clone()) {
+    return Ok(SoundFile::bundled(asset.bytes));
+  }
+
+  // `npm run dev` serves from `public/` and never produces the dist folder.
+  #[cfg(debug_assertions)]
+  {
+    let dev_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../public").join(&path[1..]);
+    if let Ok(bytes) = std::fs::read(dev_path) {
+      return Ok(SoundFile::bundled(bytes));
+    }
+  }
+
+  Err(format!("Sound asset not found: {}", path))
+}
+
+/// Decode `sound_ids` off the main thread and hand them to the mixer.
+fn spawn_loaders<R: Runtime>(app: &AppHandle<R>, state: &AudioState, sound_ids: Vec<String>) {
+  for sound_id in sound_ids {
+    let app = app.clone();
+    let mixer = state.mixer.clone();
+    let loudness = state.loudness.clone();
+    std::thread::spawn(move || {
+      let target_rate = mixer.lock().map(|m| m.sample_rate()).unwrap_or(DEFAULT_SAMPLE_RATE);
+      let loaded = read_sound(&app, &sound_id).and_then(|file| {
+        let fingerprint = loudness::fingerprint(&file.bytes);
+        let buffer = decoder::decode(file.bytes, Some(&file.ext))?;
+        let normalization = normalization_gain(&loudness, &sound_id, fingerprint, &buffer);
+        Ok((Arc::new(buffer.resampled(target_rate)), normalization, file.loop_region))
+      });
+
+      let Ok(mut m) = mixer.lock() else {
+        return;
+      };
+      match loaded {
+        Ok((buffer, normalization, loop_region)) => m.attach(&sound_id, buffer, normalization, loop_region),
+        Err(e) => {
+          log::error!("Failed to load sound {}: {}", sound_id, e);
+          m.load_failed(&sound_id);
+        }
+      }
+    });
+  }
+}
+
+/// Look up the sound's loudness in the manifest, analysing (and caching) it
+/// on first use.
+fn normalization_gain(
+  manifest: &Mutex<LoudnessManifest>,
+  sound_id: &str,
+  fingerprint: String,
+  buffer: &decoder::SampleBuffer,
+) -> f32 {
+  if let Some(entry) = manifest.lock().ok().and_then(|m| m.get(sound_id, &fingerprint).cloned()) {
+    return entry.gain;
+  }
+
+  let measured = loudness::analyze(buffer);
+  let Ok(mut manifest) = manifest.lock() else {
+    return measured.gain();
+  };
+  let entry = manifest.insert(sound_id, fingerprint, measured);
+  if let Err(e) = manifest.save() {
+    log::warn!("Failed to save loudness manifest: {}", e);
+  }
+  log::info!(
+    "Measured {}: {:?} LUFS, normalization {:.2}",
+    sound_id,
+    entry.loudness.integrated,
+    entry.gain
+  );
+  entry.gain
+}
+
+fn persist<R: Runtime, T: serde::Serialize>(app: &AppHandle<R>, key: &str, value: &T) -> Result<(), String> {
+  let store = app.store(AUDIO_STORE_FILE).map_err(|e| e.to_string())?;
+  let value = serde_json::to_value(value).map_err(|e| e.to_string())?;
+  store.set(key, value);
+  store.save().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_audio_devices() -> Result<Vec<OutputDevice>, String> {
+  devices::list_output_devices()
+}
+
+#[tauri::command]
+pub fn get_audio_device(state: tauri::State<AudioState>) -> Result<OutputStatus, String> {
+  Ok(state.output.lock().map_err(|e| e.to_string())?.status())
+}
+
+/// Send ambient audio to `name`, or back to the system default when it's
+/// `null`. The choice is remembered by name, so unplugging the device falls
+/// back to the default until it's connected again.
+#[tauri::command]
+pub fn set_audio_device<R: Runtime>(
+  app: AppHandle<R>,
+  state: tauri::State<AudioState>,
+  name: Option<String>,
+) -> Result<OutputStatus, String> {
+  let status = {
+    let mut output = state.output.lock().map_err(|e| e.to_string())?;
+    if output.selected != name {
+      output.selected = name;
+      restart_output(&app, &state, &mut output);
+    }
+    output.status()
+  };
+  persist(&app, OUTPUT_DEVICE_STORE_KEY, &status.selected)?;
+  let _ = app.emit("audio-device-changed", status.clone());
+  Ok(status)
+}
+
+/// Master volume (0-1) applied on top of every sound's own volume.
+pub fn set_master_volume<R: Runtime>(app: &AppHandle<R>, volume: f32) -> Result<(), String> {
+  let state = app.state::<AudioState>();
+  let volume = {
+    let mut mixer = state.mixer.lock().map_err(|e| e.to_string())?;
+    mixer.set_master_volume(volume);
+    mixer.master_volume()
+  };
+  state.playback.send_if_modified(|p| {
+    let changed = p.volume != volume;
+    p.volume = volume;
+    changed
+  });
+  persist(app, MASTER_VOLUME_STORE_KEY, &volume)
+}
+
+/// Mirror the frontend's sound selection into the mixer.
+#[tauri::command]
+pub fn sync_audio_state<R: Runtime>(
+  app: AppHandle<R>,
+  state: tauri::State<AudioState>,
+  active_sounds: Vec<String>,
+  volumes: HashMap<String, f32>,
+  effects: HashMap<String, SoundEffect>,
+  is_muted: bool,
+) -> Result<(), String> {
+  let known = catalog::sounds(&app);
+  let active: Vec<String> = active_sounds
+    .into_iter()
+    .filter(|id| known.iter().any(|s| &s.id == id))
+    .collect();
+
+  let to_load = state
+    .mixer
+    .lock()
+    .map_err(|e| e.to_string())?
+    .set_state(&active, &volumes, &effects, is_muted);
+  spawn_loaders(&app, &state, to_load);
+  history::playback_changed(&app, &active, &volumes, is_muted);
+
+  state.playback.send_if_modified(|p| {
+    let changed = p.active_sounds != active || p.muted != is_muted;
+    p.active_sounds = active;
+    p.muted = is_muted;
+    changed
+  });
+  Ok(())
+}
+
+/// Measure every sound in the catalog that isn't in the loudness
+/// manifest yet (or all of them with `force`) and return the manifest.
+#[tauri::command]
+pub async fn analyze_loudness<R: Runtime>(
+  app: AppHandle<R>,
+  force: Option<bool>,
+) -> Result<HashMap<String, ManifestEntry>, String> {
+  tauri::async_runtime::spawn_blocking(move || {
+    let state = app.state::<AudioState>();
+    for sound in catalog::sounds(&app) {
+      let sound_id = sound.id.clone();
+      let file = read_catalog_sound(&app, sound)?;
+      let fingerprint = loudness::fingerprint(&file.bytes);
+      let cached = state
+        .loudness
+        .lock()
+        .map(|m| m.get(&sound_id, &fingerprint).is_some())
+        .unwrap_or(false);
+      if cached && !force.unwrap_or(false) {
+        continue;
+      }
+
+      let measured = loudness::analyze(&decoder::decode(file.bytes, Some(&file.ext))?);
+      state
+        .loudness
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(&sound_id, fingerprint, measured);
+    }
+
+    let manifest = state.loudness.lock().map_err(|e| e.to_string())?;
+    manifest.save()?;
+    Ok(manifest.entries().clone())
+  })
+  .await
+  .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn get_loudness_manifest(state: tauri::State<AudioState>) -> HashMap<String, ManifestEntry> {
+  state
+    .loudness
+    .lock()
+    .map(|m| m.entries().clone())
+    .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_sound_automation(state: tauri::State<AudioState>) -> HashMap<String, Envelope> {
+  state
+    .mixer
+    .lock()
+    .map(|m| m.automation().envelopes().clone())
+    .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn set_sound_automation<R: Runtime>(
+  app: AppHandle<R>,
+  state: tauri::State<AudioState>,
+  sound_id: String,
+  keyframes: Vec<Keyframe>,
+) -> Result<(), String> {
+  let envelope = Envelope::new(keyframes)?;
+  let automation = {
+    let mut mixer = state.mixer.lock().map_err(|e| e.to_string())?;
+    mixer.automation_mut().set(sound_id, envelope);
+    mixer.automation().clone()
+  };
+  persist(&app, AUTOMATION_STORE_KEY, &automation)
+}
+
+#[tauri::command]
+pub fn clear_sound_automation<R: Runtime>(
+  app: AppHandle<R>,
+  state: tauri::State<AudioState>,
+  sound_id: String,
+) -> Result<(), String> {
+  let automation = {
+    let mut mixer = state.mixer.lock().map_err(|e| e.to_string())?;
+    if !mixer.automation_mut().remove(&sound_id) {
+      return Ok(());
+    }
+    mixer.automation().clone()
+  };
+  persist(&app, AUTOMATION_STORE_KEY, &automation)
+}
+
+#[tauri::command]
+pub fn get_event_layers(state: tauri::State<AudioState>) -> HashMap<String, EventLayer> {
+  state
+    .mixer
+    .lock()
+    .map(|m| m.event_layers().clone())
+    .unwrap_or_default()
+}
+
+/// Turn a sound into a randomized one-shot layer, or back into a plain loop
+/// when `layer` is `null`.
+#[tauri::command]
+pub fn set_event_layer<R: Runtime>(
+  app: AppHandle<R>,
+  state: tauri::State<AudioState>,
+  sound_id: String,
+  layer: Option<EventLayer>,
+) -> Result<(), String> {
+  if let Some(layer) = &layer {
+    layer.validate()?;
+  }
+  let layers = {
+    let mut mixer = state.mixer.lock().map_err(|e| e.to_string())?;
+    mixer.set_event_layer(&sound_id, layer);
+    mixer.event_layers().clone()
+  };
+  persist(&app, EVENT_LAYERS_STORE_KEY, &layers)
+}
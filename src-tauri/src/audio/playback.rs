@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Frontend event carrying a [`PlaybackCommand`] from outside the window
+/// (OS media controls, the command line). The frontend owns the sound
+/// selection, so it applies the command and syncs the result back through
+/// `sync_audio_state`.
+pub const PLAYBACK_COMMAND_EVENT: &str = "playback-command";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum PlaybackCommand {
+  Play,
+  Pause,
+  PlayPause,
+  /// Turn every sound off.
+  Stop,
+  /// Play exactly these sounds, unmuted.
+  Scene { sounds: Vec<String> },
+  /// Turn one sound on or off and/or set its volume (0-1).
+  Sound {
+    sound: String,
+    on: Option<bool>,
+    volume: Option<f32>,
+  },
+}
+
+pub fn send_command<R: Runtime>(app: &AppHandle<R>, command: PlaybackCommand) {
+  if let Err(e) = app.emit(PLAYBACK_COMMAND_EVENT, command) {
+    log::warn!("Failed to emit {}: {}", PLAYBACK_COMMAND_EVENT, e);
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaybackStatus {
+  Playing,
+  /// Sounds are selected but the mix is muted.
+  Paused,
+  /// Nothing selected.
+  Stopped,
+}
+
+/// What's playing right now, as last synced from the frontend.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaybackState {
+  pub active_sounds: Vec<String>,
+  pub muted: bool,
+  /// Master volume, 0-1.
+  pub volume: f32,
+}
+
+impl Default for PlaybackState {
+  fn default() -> Self {
+    Self {
+      active_sounds: Vec::new(),
+      muted: false,
+      volume: 1.0,
+    }
+  }
+}
+
+impl PlaybackState {
+  pub fn status(&self) -> PlaybackStatus {
+    match (self.active_sounds.is_empty(), self.muted) {
+      (true, _) => PlaybackStatus::Stopped,
+      (false, true) => PlaybackStatus::Paused,
+      (false, false) => PlaybackStatus::Playing,
+    }
+  }
+}
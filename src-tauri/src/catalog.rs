@@ -0,0 +1,176 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::audio::mixer::LoopRegion;
+
+/// Emitted whenever sounds are imported, removed, or a pack is installed,
+/// toggled or uninstalled, so the frontend can reload [`list_sounds`].
+pub const CATALOG_CHANGED_EVENT: &str = "sound-catalog-changed";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+  Nature,
+  Life,
+  Places,
+  Work,
+}
+
+impl Category {
+  pub const ALL: [Category; 4] = [Category::Nature, Category::Life, Category::Places, Category::Work];
+
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Category::Nature => "nature",
+      Category::Life => "life",
+      Category::Places => "places",
+      Category::Work => "work",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|c| c.as_str() == name)
+  }
+}
+
+struct BuiltinSound {
+  id: &'static str,
+  label: &'static str,
+  icon: &'static str,
+  category: Category,
+}
+
+/// Bundled loops, served from the frontend assets as `/sounds/<id>.m4a`.
+/// The order here is the order of the sound buttons.
+const BUILTIN_SOUNDS: &[BuiltinSound] = &[
+  BuiltinSound { id: "rain", label: "Rain", icon: "cloud-rain", category: Category::Nature },
+  BuiltinSound { id: "wind", label: "Wind", icon: "wind", category: Category::Nature },
+  BuiltinSound { id: "waves", label: "Waves", icon: "waves", category: Category::Nature },
+  BuiltinSound { id: "forest", label: "Forest", icon: "bird", category: Category::Life },
+  BuiltinSound { id: "stream", label: "Stream", icon: "droplets", category: Category::Life },
+  BuiltinSound { id: "fire", label: "Fire", icon: "flame", category: Category::Life },
+  BuiltinSound { id: "flight", label: "Flight", icon: "plane", category: Category::Places },
+  BuiltinSound { id: "train", label: "Train", icon: "train-front", category: Category::Places },
+  BuiltinSound { id: "night", label: "Night", icon: "moon", category: Category::Places },
+  BuiltinSound { id: "keyboard", label: "Keyboard", icon: "keyboard", category: Category::Work },
+  BuiltinSound { id: "thunder", label: "Thunder", icon: "cloud-lightning", category: Category::Work },
+  BuiltinSound { id: "glass-fruit", label: "Glass Fruit", icon: "utensils", category: Category::Work },
+];
+
+/// Icon of imported sounds, and of pack sounds that don't name one.
+const DEFAULT_ICON: &str = "music";
+
+pub fn is_builtin(sound_id: &str) -> bool {
+  BUILTIN_SOUNDS.iter().any(|s| s.id == sound_id)
+}
+
+/// Where a sound came from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SoundSource {
+  Builtin,
+  Custom,
+  #[serde(rename_all = "camelCase")]
+  Pack { pack_id: String },
+}
+
+/// Where a sound's audio lives. The frontend loads bundled assets by URL and
+/// files through the asset protocol (`convertFileSrc`).
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SoundAsset {
+  /// Path in the frontend assets, e.g. `/sounds/rain.m4a`.
+  Bundled { path: String },
+  /// Absolute path in the app data dir.
+  File { path: PathBuf },
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogSound {
+  /// `rain` for built-ins, `custom-<slug>` for imports, `<pack>.<sound>` for
+  /// pack sounds.
+  pub id: String,
+  pub label: String,
+  /// Lucide icon name (kebab-case).
+  pub icon: String,
+  pub category: Option<Category>,
+  pub source: SoundSource,
+  pub asset: SoundAsset,
+  #[serde(skip)]
+  pub loop_region: Option<LoopRegion>,
+}
+
+/// Every playable sound: built-ins, then imported sounds, then sounds of
+/// enabled packs in install order.
+pub fn sounds<R: Runtime>(app: &AppHandle<R>) -> Vec<CatalogSound> {
+  let builtin = BUILTIN_SOUNDS.iter().map(|s| CatalogSound {
+    id: s.id.to_string(),
+    label: s.label.to_string(),
+    icon: s.icon.to_string(),
+    category: Some(s.category),
+    source: SoundSource::Builtin,
+    asset: SoundAsset::Bundled {
+      path: format!("/sounds/{}.m4a", s.id),
+    },
+    loop_region: None,
+  });
+
+  let custom_dir = crate::import::sounds_dir(app).ok();
+  let custom = crate::import::custom_sounds(app).into_iter().filter_map(|s| {
+    Some(CatalogSound {
+      asset: SoundAsset::File {
+        path: custom_dir.as_ref()?.join(&s.file),
+      },
+      id: s.id,
+      label: s.label,
+      icon: DEFAULT_ICON.to_string(),
+      category: None,
+      source: SoundSource::Custom,
+      loop_region: None,
+    })
+  });
+
+  let packs_dir = crate::packs::packs_dir(app).ok();
+  let packs = crate::packs::installed_packs(app)
+    .into_iter()
+    .filter(|p| p.enabled)
+    .flat_map(|p| {
+      let pack_id = p.manifest.id;
+      let dir = packs_dir.as_ref().map(|d| d.join(&pack_id));
+      p.manifest.sounds.into_iter().filter_map(move |s| {
+        Some(CatalogSound {
+          id: crate::packs::sound_id(&pack_id, &s.id),
+          asset: SoundAsset::File {
+            path: dir.as_ref()?.join(&s.file),
+          },
+          label: s.label,
+          icon: s.icon.unwrap_or_else(|| DEFAULT_ICON.to_string()),
+          category: s.category.as_deref().and_then(Category::from_name),
+          source: SoundSource::Pack {
+            pack_id: pack_id.clone(),
+          },
+          loop_region: s.loop_region,
+        })
+      })
+    });
+
+  builtin.chain(custom).chain(packs).collect()
+}
+
+pub fn find<R: Runtime>(app: &AppHandle<R>, sound_id: &str) -> Option<CatalogSound> {
+  sounds(app).into_iter().find(|s| s.id == sound_id)
+}
+
+pub fn notify_changed<R: Runtime>(app: &AppHandle<R>) {
+  if let Err(e) = app.emit(CATALOG_CHANGED_EVENT, ()) {
+    log::warn!("Failed to emit {}: {}", CATALOG_CHANGED_EVENT, e);
+  }
+}
+
+#[tauri::command]
+pub fn list_sounds<R: Runtime>(app: AppHandle<R>) -> Vec<CatalogSound> {
+  sounds(&app)
+}
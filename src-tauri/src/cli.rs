@@ -0,0 +1,178 @@
+//! `zerohz` command-line options. Anything given on the command line is
+//! forwarded to the running instance when there is one (see
+//! [`crate::instance`]).
+
+use clap::Parser;
+
+use crate::deeplink;
+use crate::instance::{self, Request};
+use crate::remote::{Action, Response, Status};
+use crate::timer::TimerMode;
+
+#[derive(Debug, Parser)]
+#[command(name = "zerohz", version, about = "Ambient sounds and a focus timer")]
+struct Cli {
+  /// A zerohz:// link to open. Windows and Linux launch the app with the
+  /// link as its only argument.
+  #[arg(value_name = "LINK", conflicts_with_all = ["start", "scene", "stop"])]
+  link: Option<String>,
+
+  /// Start a countdown from a timer preset (pomodoro, short-break, long-break, focus, hour)
+  #[arg(long, value_name = "PRESET")]
+  start: Option<String>,
+
+  /// Task to record with the session started by --start
+  #[arg(long, value_name = "TEXT", requires = "start")]
+  task: Option<String>,
+
+  /// Play a scene (rain-fire, deep-work, storm, ...) or a mix of sounds such as rain+waves
+  #[arg(long, value_name = "SCENE")]
+  scene: Option<String>,
+
+  /// Stop the timer and every sound
+  #[arg(long, conflicts_with_all = ["start", "scene"])]
+  stop: bool,
+
+  /// Print the timer and sound status of the running instance
+  #[arg(long)]
+  status: bool,
+
+  /// Print the response as JSON
+  #[arg(long)]
+  json: bool,
+}
+
+impl Cli {
+  fn actions(&self) -> Result<Vec<Action>, String> {
+    if let Some(link) = &self.link {
+      return deeplink::parse(link);
+    }
+    let mut actions = Vec::new();
+    if self.stop {
+      actions.push(Action::Stop);
+    }
+    if let Some(scene) = &self.scene {
+      actions.push(Action::Scene { scene: scene.clone() });
+    }
+    if let Some(preset) = &self.start {
+      actions.push(Action::Start {
+        preset: preset.clone(),
+        task: self.task.clone(),
+      });
+    }
+    // Launching the app again just brings it to the front.
+    if actions.is_empty() && !self.status {
+      actions.push(Action::Show);
+    }
+    Ok(actions)
+  }
+
+  /// Print `response` and return the exit code.
+  fn report(&self, actions: &[Action], response: &Response) -> i32 {
+    let code = if response.ok { 0 } else { 1 };
+    if self.json {
+      println!("{}", serde_json::to_string_pretty(response).unwrap_or_default());
+      return code;
+    }
+    if let Some(error) = &response.error {
+      eprintln!("zerohz: {}", error);
+    }
+    let quiet = !self.status && !actions.iter().any(Action::changes_state);
+    if let (Some(status), false) = (&response.status, quiet) {
+      print!("{}", describe(status));
+    }
+    code
+  }
+}
+
+fn format_seconds(seconds: u32) -> String {
+  format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn describe(status: &Status) -> String {
+  let timer = &status.timer;
+  let mut timer_parts = vec![match timer.mode {
+    TimerMode::Countdown => format!(
+      "{} left of {}",
+      format_seconds(timer.current_seconds),
+      format_seconds(timer.target_seconds)
+    ),
+    TimerMode::Stopwatch => format!("{} elapsed", format_seconds(timer.current_seconds)),
+  }];
+  timer_parts.push(
+    match (timer.is_running, timer.is_paused) {
+      (true, false) => "running",
+      (true, true) => "paused",
+      (false, _) => "stopped",
+    }
+    .to_string(),
+  );
+  if let Some(preset) = &timer.preset {
+    timer_parts.push(preset.clone());
+  }
+  if let Some(task) = &timer.task {
+    timer_parts.push(format!("\"{}\"", task));
+  }
+
+  let sounds = if status.audio.active_sounds.is_empty() {
+    "off".to_string()
+  } else {
+    let mut parts = Vec::new();
+    if let Some(scene) = &status.scene {
+      parts.push(scene.clone());
+    }
+    parts.push(if status.audio.muted { "muted" } else { "playing" }.to_string());
+    parts.push(format!("volume {}%", (status.audio.volume * 100.0).round()));
+    format!("{} ({})", status.title, parts.join(", "))
+  };
+
+  format!("Timer:  {}\nSounds: {}\n", timer_parts.join(", "), sounds)
+}
+
+/// Windows release builds have no console of their own; borrow the one
+/// `zerohz` was run from so output and `--help` are visible.
+#[cfg(windows)]
+fn attach_console() {
+  use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+  unsafe {
+    AttachConsole(ATTACH_PARENT_PROCESS);
+  }
+}
+
+/// Parse the command line and hand it to the running instance, exiting
+/// with its response. Returns the actions to apply at startup when this
+/// process is the first instance.
+pub fn forward_or_start() -> Vec<Action> {
+  #[cfg(windows)]
+  if std::env::args_os().len() > 1 {
+    attach_console();
+  }
+
+  // macOS before 10.9 passes a process serial number to apps opened from
+  // the Finder.
+  let cli = Cli::parse_from(std::env::args_os().filter(|arg| !arg.to_string_lossy().starts_with("-psn_")));
+  let actions = match cli.actions() {
+    Ok(actions) => actions,
+    Err(e) => std::process::exit(cli.report(&[], &Response::failed(e))),
+  };
+
+  match instance::forward(&Request {
+    actions: actions.clone(),
+  }) {
+    Some(Ok(response)) => std::process::exit(cli.report(&actions, &response)),
+    Some(Err(e)) => {
+      eprintln!("zerohz: no response from the running instance: {}", e);
+      std::process::exit(1);
+    }
+    None => {}
+  }
+
+  // Nothing to report on or stop without a running instance.
+  if cli.status {
+    std::process::exit(cli.report(&actions, &Response::failed("ZeroHz is not running")));
+  }
+  if cli.stop {
+    std::process::exit(0);
+  }
+  actions
+}
@@ -0,0 +1,307 @@
+//! What can be set in `policy.toml` and `config.toml`, and how each
+//! value is checked.
+
+use std::collections::{HashMap, HashSet};
+
+use tauri::Url;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+  UpdatesEnabled,
+  UpdateChannel,
+  UpdateEndpoint,
+  AnalyticsEnabled,
+  AnalyticsHost,
+  License,
+  LicenseGraceDays,
+  StartAtLogin,
+  LocalApi,
+  ShellHooks,
+}
+
+impl Key {
+  pub const ALL: [Key; 10] = [
+    Key::UpdatesEnabled,
+    Key::UpdateChannel,
+    Key::UpdateEndpoint,
+    Key::AnalyticsEnabled,
+    Key::AnalyticsHost,
+    Key::License,
+    Key::LicenseGraceDays,
+    Key::StartAtLogin,
+    Key::LocalApi,
+    Key::ShellHooks,
+  ];
+
+  /// `section.name`, as written in the files and in `locked`.
+  pub fn name(self) -> &'static str {
+    match self {
+      Key::UpdatesEnabled => "updates.enabled",
+      Key::UpdateChannel => "updates.channel",
+      Key::UpdateEndpoint => "updates.endpoint",
+      Key::AnalyticsEnabled => "analytics.enabled",
+      Key::AnalyticsHost => "analytics.host",
+      Key::License => "license.key",
+      Key::LicenseGraceDays => "license.grace_days",
+      Key::StartAtLogin => "settings.start_at_login",
+      Key::LocalApi => "settings.local_api",
+      Key::ShellHooks => "settings.shell_hooks",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|k| k.name() == name)
+  }
+
+  /// Settings a user could use to get around what the app allows, which
+  /// only the system policy can set.
+  fn is_policy_only(self) -> bool {
+    matches!(self, Key::LicenseGraceDays)
+  }
+
+  fn check(self, value: &toml::Value) -> Result<Value, String> {
+    match self {
+      Key::UpdatesEnabled | Key::AnalyticsEnabled | Key::StartAtLogin | Key::LocalApi | Key::ShellHooks => {
+        value.as_bool().map(Value::Bool).ok_or_else(|| "expected true or false".to_string())
+      }
+      Key::UpdateChannel => match value.as_str() {
+        Some(channel) if Channel::from_name(channel).is_some() => Ok(Value::Text(channel.to_string())),
+        _ => Err("expected \"stable\" or \"beta\"".to_string()),
+      },
+      Key::UpdateEndpoint | Key::AnalyticsHost => {
+        let text = value.as_str().ok_or("expected an https:// URL")?;
+        let url = Url::parse(text).map_err(|e| format!("invalid URL: {}", e))?;
+        if url.scheme() != "https" || url.host_str().is_none() {
+          return Err("expected an https:// URL".to_string());
+        }
+        Ok(Value::Text(text.to_string()))
+      }
+      Key::License => match value.as_str().map(str::trim) {
+        Some(key) if !key.is_empty() => Ok(Value::Text(key.to_string())),
+        _ => Err("expected a license key".to_string()),
+      },
+      Key::LicenseGraceDays => match value.as_integer().map(u64::try_from) {
+        Some(Ok(days)) => Ok(Value::Number(days)),
+        _ => Err("expected a number of days".to_string()),
+      },
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+  Stable,
+  Beta,
+}
+
+impl Channel {
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "stable" => Some(Channel::Stable),
+      "beta" => Some(Channel::Beta),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+  Bool(bool),
+  Number(u64),
+  Text(String),
+}
+
+/// One file's settings.
+#[derive(Debug, Default)]
+pub struct Layer {
+  pub values: HashMap<Key, Value>,
+  pub locked: HashSet<Key>,
+}
+
+/// Read `text`, checking each setting on its own so one mistake doesn't
+/// throw away the rest. What's wrong is added to `problems`, prefixed with
+/// `origin`. Only the policy may lock settings or set policy-only ones.
+pub fn parse(text: &str, origin: &str, is_policy: bool, problems: &mut Vec<String>) -> Layer {
+  let mut layer = Layer::default();
+  let table = match text.parse::<toml::Table>() {
+    Ok(table) => table,
+    Err(e) => {
+      problems.push(format!("{}: ignored, not valid TOML: {}", origin, e.to_string().trim()));
+      return layer;
+    }
+  };
+
+  for (section, entries) in &table {
+    if section == "locked" {
+      if !is_policy {
+        problems.push(format!("{}: locked: only the system policy can lock settings", origin));
+        continue;
+      }
+      let Some(names) = entries.as_array() else {
+        problems.push(format!("{}: locked: expected a list of settings", origin));
+        continue;
+      };
+      for name in names {
+        let Some(name) = name.as_str() else {
+          problems.push(format!("{}: locked: expected a list of settings", origin));
+          continue;
+        };
+        match Key::from_name(name) {
+          Some(key) => {
+            layer.locked.insert(key);
+          }
+          None => problems.push(format!("{}: locked: unknown setting {}", origin, name)),
+        }
+      }
+      continue;
+    }
+
+    let Some(entries) = entries.as_table() else {
+      problems.push(format!("{}: {}: unknown setting", origin, section));
+      continue;
+    };
+    for (name, value) in entries {
+      let full_name = format!("{}.{}", section, name);
+      // TOML puts keys after a `[section]` header in that section.
+      if name == "locked" {
+        problems.push(format!("{}: {}: locked must come before the first [section]", origin, full_name));
+        continue;
+      }
+      let Some(key) = Key::from_name(&full_name) else {
+        problems.push(format!("{}: {}: unknown setting", origin, full_name));
+        continue;
+      };
+      if key.is_policy_only() && !is_policy {
+        problems.push(format!("{}: {}: only the system policy can set this", origin, full_name));
+        continue;
+      }
+      match key.check(value) {
+        Ok(value) => {
+          layer.values.insert(key, value);
+        }
+        Err(e) => problems.push(format!("{}: {}: {}", origin, full_name, e)),
+      }
+    }
+  }
+  layer
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_policy(text: &str) -> (Layer, Vec<String>) {
+    let mut problems = Vec::new();
+    let layer = parse(text, "policy.toml", true, &mut problems);
+    (layer, problems)
+  }
+
+  #[test]
+  fn reads_every_setting() {
+    let (layer, problems) = parse_policy(
+      r#"
+      locked = ["updates.channel", "settings.local_api"]
+
+      [updates]
+      enabled = true
+      channel = "beta"
+      endpoint = "https://updates.example.com/latest.json"
+
+      [analytics]
+      enabled = false
+      host = "https://eu.i.posthog.com"
+
+      [license]
+      key = "  ABC  "
+      grace_days = 30
+
+      [settings]
+      start_at_login = true
+      local_api = false
+      shell_hooks = false
+      "#,
+    );
+    assert_eq!(problems, Vec::<String>::new());
+    assert_eq!(layer.values.len(), Key::ALL.len());
+    assert_eq!(layer.values[&Key::UpdateChannel], Value::Text("beta".to_string()));
+    assert_eq!(layer.values[&Key::AnalyticsEnabled], Value::Bool(false));
+    assert_eq!(layer.values[&Key::License], Value::Text("ABC".to_string()));
+    assert_eq!(layer.values[&Key::LicenseGraceDays], Value::Number(30));
+    assert_eq!(layer.locked, HashSet::from([Key::UpdateChannel, Key::LocalApi]));
+  }
+
+  #[test]
+  fn skips_only_the_settings_that_are_wrong() {
+    let (layer, problems) = parse_policy(
+      r#"
+      [updates]
+      enabled = "yes"
+      channel = "nightly"
+      endpoint = "http://updates.example.com/latest.json"
+
+      [analytics]
+      host = "https://eu.i.posthog.com"
+      colour = "blue"
+
+      [license]
+      key = " "
+      "#,
+    );
+    assert_eq!(
+      layer.values,
+      HashMap::from([(Key::AnalyticsHost, Value::Text("https://eu.i.posthog.com".to_string()))])
+    );
+    assert_eq!(problems.len(), 5);
+    assert!(problems.contains(&"policy.toml: updates.endpoint: expected an https:// URL".to_string()));
+    assert!(problems.contains(&"policy.toml: analytics.colour: unknown setting".to_string()));
+  }
+
+  #[test]
+  fn ignores_a_file_that_is_not_toml() {
+    let (layer, problems) = parse_policy("[updates\nenabled = false");
+    assert!(layer.values.is_empty());
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].starts_with("policy.toml: ignored, not valid TOML"));
+  }
+
+  #[test]
+  fn only_the_policy_locks() {
+    let mut problems = Vec::new();
+    let layer = parse(r#"locked = ["updates.enabled"]"#, "config.toml", false, &mut problems);
+    assert!(layer.locked.is_empty());
+    assert_eq!(problems, vec!["config.toml: locked: only the system policy can lock settings"]);
+  }
+
+  #[test]
+  fn only_the_policy_sets_the_grace_period() {
+    let mut problems = Vec::new();
+    let layer = parse("[license]\ngrace_days = 365", "config.toml", false, &mut problems);
+    assert!(layer.values.is_empty());
+    assert_eq!(problems, vec!["config.toml: license.grace_days: only the system policy can set this"]);
+
+    let (layer, problems) = parse_policy("[license]\ngrace_days = -1");
+    assert!(layer.values.is_empty());
+    assert_eq!(problems, vec!["policy.toml: license.grace_days: expected a number of days"]);
+  }
+
+  #[test]
+  fn checks_the_locked_list() {
+    let (layer, problems) = parse_policy(
+      r#"
+      locked = ["updates.enabled", "updates.everything", 3]
+
+      [settings]
+      locked = ["settings.local_api"]
+      "#,
+    );
+    assert_eq!(layer.locked, HashSet::from([Key::UpdatesEnabled]));
+    assert_eq!(
+      problems,
+      vec![
+        "policy.toml: locked: unknown setting updates.everything",
+        "policy.toml: locked: expected a list of settings",
+        "policy.toml: settings.locked: locked must come before the first [section]",
+      ]
+    );
+  }
+}
@@ -0,0 +1,304 @@
+//! Runtime configuration for fleet deployment: a system-wide
+//! `policy.toml` written by an administrator and a per-user `config.toml`,
+//! over what the build was made with (see `docs/11-configuration.md`).
+//!
+//! For each setting, a value the policy locks wins, then the user's file,
+//! then the policy, then the built-in default. A policy that turns
+//! something off locks it, listed or not, so a fleet can't have updates or
+//! analytics turned back on per user. Mistakes are logged and skipped one
+//! setting at a time.
+
+mod file;
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+#[cfg(feature = "updater")]
+use tauri::Url;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::{api, hooks};
+#[cfg(feature = "updater")]
+use file::Channel;
+pub use file::Key;
+use file::{Layer, Value};
+
+const POLICY_FILE: &str = "policy.toml";
+const USER_FILE: &str = "config.toml";
+/// Where `beta` builds are published, as a rolling `beta` release.
+#[cfg(feature = "updater")]
+const BETA_ENDPOINT: &str = "https://github.com/Aweeesome-lab/ZeroHz/releases/download/beta/latest.json";
+
+pub struct Config {
+  values: HashMap<Key, Value>,
+  locked: HashSet<Key>,
+  /// Files that were read, for the log.
+  sources: Vec<PathBuf>,
+  problems: Vec<String>,
+}
+
+impl Config {
+  fn bool(&self, key: Key) -> Option<bool> {
+    match self.values.get(&key) {
+      Some(Value::Bool(value)) => Some(*value),
+      _ => None,
+    }
+  }
+
+  fn number(&self, key: Key) -> Option<u64> {
+    match self.values.get(&key) {
+      Some(Value::Number(value)) => Some(*value),
+      _ => None,
+    }
+  }
+
+  fn text(&self, key: Key) -> Option<&str> {
+    match self.values.get(&key) {
+      Some(Value::Text(value)) => Some(value),
+      _ => None,
+    }
+  }
+
+  /// Whether the user can't change `key`, in `config.toml` or the tray.
+  pub fn is_locked(&self, key: Key) -> bool {
+    self.locked.contains(&key)
+  }
+
+  #[cfg(feature = "updater")]
+  pub fn updates_enabled(&self) -> bool {
+    self.bool(Key::UpdatesEnabled).unwrap_or(true)
+  }
+
+  /// Where to look for updates instead of `tauri.conf.json`'s endpoint:
+  /// `updates.endpoint` if set, else the channel's.
+  #[cfg(feature = "updater")]
+  pub fn update_endpoint(&self) -> Option<Url> {
+    let url = match self.text(Key::UpdateEndpoint) {
+      Some(url) => url,
+      None => match self.text(Key::UpdateChannel).and_then(Channel::from_name) {
+        Some(Channel::Beta) => BETA_ENDPOINT,
+        Some(Channel::Stable) | None => return None,
+      },
+    };
+    Url::parse(url).ok()
+  }
+
+  /// `false` turns analytics off without asking; `true` only leaves it to
+  /// the user, who is still asked.
+  #[cfg(feature = "analytics")]
+  pub fn analytics_enabled(&self) -> bool {
+    self.bool(Key::AnalyticsEnabled).unwrap_or(true)
+  }
+
+  #[cfg(feature = "analytics")]
+  pub fn analytics_host(&self) -> Option<&str> {
+    self.text(Key::AnalyticsHost)
+  }
+
+  /// Activated at startup when no license is saved yet.
+  pub fn license_key(&self) -> Option<&str> {
+    self.text(Key::License)
+  }
+
+  /// How long Pro keeps working offline, as set by the policy.
+  pub fn license_grace_days(&self) -> Option<u64> {
+    self.number(Key::LicenseGraceDays)
+  }
+}
+
+/// Apply the precedence rules to the two files.
+fn resolve(policy: Layer, user: Layer, problems: &mut Vec<String>) -> (HashMap<Key, Value>, HashSet<Key>) {
+  let mut locked = policy.locked;
+  locked.extend(
+    policy
+      .values
+      .iter()
+      .filter(|(_, value)| **value == Value::Bool(false))
+      .map(|(key, _)| *key),
+  );
+  let mut values = HashMap::new();
+  for key in Key::ALL {
+    let value = if locked.contains(&key) {
+      if user.values.get(&key).is_some_and(|v| policy.values.get(&key) != Some(v)) {
+        problems.push(format!("{}: {}: ignored, locked by the system policy", USER_FILE, key.name()));
+      }
+      policy.values.get(&key)
+    } else {
+      user.values.get(&key).or_else(|| policy.values.get(&key))
+    };
+    if let Some(value) = value {
+      values.insert(key, value.clone());
+    }
+  }
+  (values, locked)
+}
+
+/// Set by administrators, so outside anything the user owns.
+fn policy_path() -> PathBuf {
+  // Debug builds can try out a policy without installing it.
+  #[cfg(debug_assertions)]
+  if let Some(path) = std::env::var_os("ZEROHZ_POLICY_FILE") {
+    return PathBuf::from(path);
+  }
+  #[cfg(windows)]
+  let dir = PathBuf::from(std::env::var_os("ProgramData").unwrap_or_else(|| "C:\\ProgramData".into())).join("ZeroHz");
+  #[cfg(target_os = "macos")]
+  let dir = PathBuf::from("/Library/Application Support/ZeroHz");
+  #[cfg(not(any(windows, target_os = "macos")))]
+  let dir = PathBuf::from("/etc/zerohz");
+  dir.join(POLICY_FILE)
+}
+
+fn read_layer(path: &std::path::Path, is_policy: bool, sources: &mut Vec<PathBuf>, problems: &mut Vec<String>) -> Layer {
+  let origin = path.display().to_string();
+  match std::fs::read_to_string(path) {
+    Ok(text) => {
+      sources.push(path.to_path_buf());
+      file::parse(&text, &origin, is_policy, problems)
+    }
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Layer::default(),
+    Err(e) => {
+      problems.push(format!("{}: ignored, can't be read: {}", origin, e));
+      Layer::default()
+    }
+  }
+}
+
+fn load<R: Runtime>(app: &AppHandle<R>) -> Config {
+  let mut sources = Vec::new();
+  let mut problems = Vec::new();
+  let policy = read_layer(&policy_path(), true, &mut sources, &mut problems);
+  let user = match app.path().app_config_dir() {
+    Ok(dir) => read_layer(&dir.join(USER_FILE), false, &mut sources, &mut problems),
+    Err(e) => {
+      problems.push(format!("No place for {}: {}", USER_FILE, e));
+      Layer::default()
+    }
+  };
+  let (values, locked) = resolve(policy, user, &mut problems);
+  Config {
+    values,
+    locked,
+    sources,
+    problems,
+  }
+}
+
+/// Read once, the first time anything asks; plugins set up before the
+/// app do.
+pub fn get<R: Runtime>(app: &AppHandle<R>) -> &'static Config {
+  static CONFIG: OnceLock<Config> = OnceLock::new();
+  CONFIG.get_or_init(|| load(app))
+}
+
+/// Log where the configuration came from and what was wrong with it.
+/// Called once the log plugin is up, which is after `get` first runs.
+pub fn report<R: Runtime>(app: &AppHandle<R>) {
+  let config = get(app);
+  for source in &config.sources {
+    log::info!("Using configuration from {}", source.display());
+  }
+  for problem in &config.problems {
+    log::warn!("{}", problem);
+  }
+}
+
+/// Make the settings the configuration sets true again, whatever was
+/// changed in the tray last run. Before the local API is started.
+pub fn apply_settings<R: Runtime>(app: &AppHandle<R>) {
+  let config = get(app);
+  if let Some(enabled) = config.bool(Key::StartAtLogin) {
+    let autostart = app.autolaunch();
+    if autostart.is_enabled().unwrap_or(false) != enabled {
+      let result = if enabled { autostart.enable() } else { autostart.disable() };
+      if let Err(e) = result {
+        log::warn!("Failed to apply {}: {}", Key::StartAtLogin.name(), e);
+      }
+    }
+  }
+  if let Some(enabled) = config.bool(Key::LocalApi) {
+    if let Err(e) = api::set_enabled(app, enabled) {
+      log::warn!("Failed to apply {}: {}", Key::LocalApi.name(), e);
+    }
+  }
+  if let Some(enabled) = config.bool(Key::ShellHooks) {
+    if hooks::is_enabled(app) != enabled {
+      if let Err(e) = hooks::set_enabled(app, enabled) {
+        log::warn!("Failed to apply {}: {}", Key::ShellHooks.name(), e);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn resolve_files(policy: &str, user: &str) -> (HashMap<Key, Value>, HashSet<Key>, Vec<String>) {
+    let mut problems = Vec::new();
+    let policy = file::parse(policy, POLICY_FILE, true, &mut problems);
+    let user = file::parse(user, USER_FILE, false, &mut problems);
+    let (values, locked) = resolve(policy, user, &mut problems);
+    (values, locked, problems)
+  }
+
+  #[test]
+  fn user_file_overrides_unlocked_policy_values() {
+    let (values, locked, problems) = resolve_files(
+      "[updates]\nchannel = \"stable\"\nenabled = true",
+      "[updates]\nchannel = \"beta\"\nenabled = false",
+    );
+    assert_eq!(values[&Key::UpdateChannel], Value::Text("beta".to_string()));
+    assert_eq!(values[&Key::UpdatesEnabled], Value::Bool(false));
+    assert!(locked.is_empty());
+    assert!(problems.is_empty());
+  }
+
+  #[test]
+  fn policy_fills_in_what_the_user_file_leaves_out() {
+    let (values, _, _) = resolve_files("[analytics]\nhost = \"https://eu.i.posthog.com\"", "");
+    assert_eq!(values[&Key::AnalyticsHost], Value::Text("https://eu.i.posthog.com".to_string()));
+    assert!(!values.contains_key(&Key::UpdateChannel));
+  }
+
+  #[test]
+  fn locked_policy_values_win_and_the_override_is_logged() {
+    let (values, locked, problems) = resolve_files(
+      "locked = [\"updates.channel\", \"settings.start_at_login\"]\n[updates]\nchannel = \"stable\"",
+      "[updates]\nchannel = \"beta\"\n[settings]\nstart_at_login = true",
+    );
+    assert_eq!(values[&Key::UpdateChannel], Value::Text("stable".to_string()));
+    // Locked without a value: the built-in default, not the user's.
+    assert!(!values.contains_key(&Key::StartAtLogin));
+    assert!(locked.contains(&Key::StartAtLogin));
+    assert_eq!(
+      problems,
+      vec![
+        "config.toml: updates.channel: ignored, locked by the system policy",
+        "config.toml: settings.start_at_login: ignored, locked by the system policy",
+      ]
+    );
+  }
+
+  #[test]
+  fn policy_that_turns_something_off_is_locked() {
+    let (values, locked, problems) = resolve_files(
+      "[updates]\nenabled = false\n[analytics]\nenabled = false\n[settings]\nlocal_api = false",
+      "[updates]\nenabled = true\n[analytics]\nenabled = false\n[settings]\nlocal_api = true",
+    );
+    for key in [Key::UpdatesEnabled, Key::AnalyticsEnabled, Key::LocalApi] {
+      assert_eq!(values[&key], Value::Bool(false), "{}", key.name());
+      assert!(locked.contains(&key), "{}", key.name());
+    }
+    // Agreeing with the policy isn't worth a warning.
+    assert_eq!(
+      problems,
+      vec![
+        "config.toml: updates.enabled: ignored, locked by the system policy",
+        "config.toml: settings.local_api: ignored, locked by the system policy",
+      ]
+    );
+  }
+}
@@ -0,0 +1,101 @@
+//! `zerohz://` links, e.g. `zerohz://start?preset=focus&scene=deep-work&task=Review`.
+//!
+//! Links can come from any web page, so only the actions below are
+//! accepted, each with a fixed set of parameters; anything else is rejected
+//! rather than ignored.
+//!
+//! | Action | Parameters |
+//! |---|---|
+//! | `show` | |
+//! | `start` | `preset`, optional `task` and `scene` |
+//! | `play` | `scene` |
+//! | `stop` | |
+//! | `session-history`, `usage`, `audio-output`, `sound-packs`, `webhooks`, `license` | |
+
+use tauri::Url;
+
+use crate::remote::{Action, Panel};
+
+pub const SCHEME: &str = "zerohz";
+const MAX_LINK_LEN: usize = 2048;
+/// Longer than anything typed into the task field.
+const MAX_TASK_LEN: usize = 200;
+
+/// Query parameters, each taken at most once.
+struct Params(Vec<(String, String)>);
+
+impl Params {
+  fn new(url: &Url) -> Result<Self, String> {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    for (key, value) in url.query_pairs() {
+      if pairs.iter().any(|(k, _)| *k == key) {
+        return Err(format!("Duplicate parameter '{}'", key));
+      }
+      pairs.push((key.into_owned(), value.into_owned()));
+    }
+    Ok(Self(pairs))
+  }
+
+  fn optional(&mut self, key: &str) -> Option<String> {
+    let index = self.0.iter().position(|(k, _)| k == key)?;
+    Some(self.0.remove(index).1)
+  }
+
+  fn required(&mut self, key: &str) -> Result<String, String> {
+    self
+      .optional(key)
+      .filter(|v| !v.is_empty())
+      .ok_or_else(|| format!("Missing parameter '{}'", key))
+  }
+
+  fn finish(self) -> Result<(), String> {
+    match self.0.first() {
+      Some((key, _)) => Err(format!("Unexpected parameter '{}'", key)),
+      None => Ok(()),
+    }
+  }
+}
+
+/// Parse a link into the actions it asks for.
+pub fn parse(link: &str) -> Result<Vec<Action>, String> {
+  if link.len() > MAX_LINK_LEN {
+    return Err("Link is too long".to_string());
+  }
+  let url = Url::parse(link).map_err(|e| format!("Invalid link: {}", e))?;
+  if url.scheme() != SCHEME {
+    return Err(format!("Not a {}:// link", SCHEME));
+  }
+  if !url.username().is_empty() || url.password().is_some() || url.port().is_some() || !matches!(url.path(), "" | "/")
+  {
+    return Err("Links take the form zerohz://<action>?<parameters>".to_string());
+  }
+
+  let action = url.host_str().unwrap_or_default();
+  let mut params = Params::new(&url)?;
+  let actions = match action {
+    "show" => vec![Action::Show],
+    "start" => {
+      let preset = params.required("preset")?;
+      let task = params.optional("task");
+      if task.as_ref().is_some_and(|t| t.chars().count() > MAX_TASK_LEN) {
+        return Err(format!("Task is longer than {} characters", MAX_TASK_LEN));
+      }
+      let mut actions = Vec::new();
+      if let Some(scene) = params.optional("scene") {
+        actions.push(Action::Scene { scene });
+      }
+      actions.push(Action::Start { preset, task });
+      actions
+    }
+    "play" => vec![Action::Scene {
+      scene: params.required("scene")?,
+    }],
+    "stop" => vec![Action::Stop],
+    _ => match Panel::from_name(action) {
+      Some(panel) => vec![Action::Open { panel }],
+      None => return Err(format!("Unsupported action '{}'", action)),
+    },
+  };
+  params.finish()?;
+  Ok(actions)
+}
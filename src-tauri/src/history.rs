@@ -0,0 +1,458 @@
+//! Listening history: what was played, when and how loud, kept on this
+//! machine only, whether or not analytics are shared (see
+//! `docs/10-listening-history.md`).
+//!
+//! A session runs while sounds play, unmuted. Within it, each change of
+//! the mix (which sounds, at what volume) starts a new segment. The open
+//! session is checkpointed every minute, so a crash loses at most that,
+//! and time the machine spent asleep isn't counted.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{Datelike, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::util::{self, now_millis};
+
+const HISTORY_FILE: &str = "listening-history.json";
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(60);
+/// A checkpoint this late means the machine was asleep: the session ends
+/// at the previous one.
+const SUSPEND_GAP_MILLIS: u64 = 3 * 60 * 1000;
+/// Playing again this soon after stopping continues the same session.
+const RESUME_GAP_MILLIS: u64 = 2 * 60 * 1000;
+/// A mix that lasted less than this is replaced rather than kept, so
+/// dragging a volume slider doesn't leave a trail of segments.
+const MIN_SEGMENT_MILLIS: u64 = 5 * 1000;
+const RETENTION_MILLIS: u64 = 2 * 365 * 24 * 60 * 60 * 1000;
+
+/// Sound id to volume (0-1), rounded to hundredths.
+type Mix = BTreeMap<String, f32>;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Segment {
+  /// Unix time in milliseconds.
+  started_at: u64,
+  ended_at: u64,
+  sounds: Mix,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Session {
+  started_at: u64,
+  ended_at: u64,
+  segments: Vec<Segment>,
+}
+
+#[derive(Default)]
+struct History {
+  sessions: Vec<Session>,
+  /// Whether the last session is still being played.
+  open: bool,
+}
+
+impl History {
+  /// The mix changed to `mix`, or playing stopped if `None`.
+  fn update(&mut self, mix: Option<Mix>, now: u64) {
+    if self.open {
+      let Some(session) = self.sessions.last_mut() else {
+        self.open = false;
+        return self.update(mix, now);
+      };
+      session.ended_at = now;
+      let Some(segment) = session.segments.last_mut() else {
+        return;
+      };
+      segment.ended_at = now;
+      match mix {
+        None => self.open = false,
+        Some(mix) if mix == segment.sounds => {}
+        Some(mix) if now.saturating_sub(segment.started_at) < MIN_SEGMENT_MILLIS => {
+          segment.sounds = mix;
+        }
+        Some(mix) => session.segments.push(Segment {
+          started_at: now,
+          ended_at: now,
+          sounds: mix,
+        }),
+      }
+      return;
+    }
+
+    let Some(mix) = mix else {
+      return;
+    };
+    let segment = Segment {
+      started_at: now,
+      ended_at: now,
+      sounds: mix,
+    };
+    match self.sessions.last_mut() {
+      Some(session) if now.saturating_sub(session.ended_at) <= RESUME_GAP_MILLIS => {
+        session.ended_at = now;
+        session.segments.push(segment);
+      }
+      _ => self.sessions.push(Session {
+        started_at: now,
+        ended_at: now,
+        segments: vec![segment],
+      }),
+    }
+    self.open = true;
+  }
+
+  /// Extend the open session to `now`, or close it where it was if the
+  /// machine was asleep and start over with the same mix.
+  fn checkpoint(&mut self, now: u64) {
+    if !self.open {
+      return;
+    }
+    let Some(session) = self.sessions.last_mut() else {
+      return;
+    };
+    if now.saturating_sub(session.ended_at) <= SUSPEND_GAP_MILLIS {
+      session.ended_at = now;
+      if let Some(segment) = session.segments.last_mut() {
+        segment.ended_at = now;
+      }
+      return;
+    }
+    // Pushed directly rather than through `update`, which would resume the
+    // session just closed if the gap were short enough.
+    let mix = session.segments.last().map(|s| s.sounds.clone());
+    self.open = false;
+    if let Some(mix) = mix {
+      self.sessions.push(Session {
+        started_at: now,
+        ended_at: now,
+        segments: vec![Segment {
+          started_at: now,
+          ended_at: now,
+          sounds: mix,
+        }],
+      });
+      self.open = true;
+    }
+  }
+
+  fn prune(&mut self, now: u64) {
+    let cutoff = now.saturating_sub(RETENTION_MILLIS);
+    self.sessions.retain(|s| s.ended_at >= cutoff);
+  }
+
+  fn stats(&self, since: u64) -> ListeningStats {
+    let mut stats = ListeningStats::default();
+    let mut by_sound: HashMap<&str, u64> = HashMap::new();
+    for session in self.sessions.iter().filter(|s| s.ended_at > since) {
+      let mut session_millis = 0;
+      for segment in &session.segments {
+        let millis = segment.ended_at.saturating_sub(segment.started_at.max(since));
+        session_millis += millis;
+        for sound in segment.sounds.keys() {
+          *by_sound.entry(sound).or_default() += millis;
+        }
+      }
+      if session_millis == 0 {
+        continue;
+      }
+      stats.session_count += 1;
+      stats.total_seconds += session_millis / 1000;
+      stats.longest_session_seconds = stats.longest_session_seconds.max(session_millis / 1000);
+    }
+    stats.sounds = by_sound
+      .into_iter()
+      .map(|(sound, millis)| SoundTime {
+        sound: sound.to_string(),
+        seconds: millis / 1000,
+      })
+      .filter(|s| s.seconds > 0)
+      .collect();
+    stats
+      .sounds
+      .sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.sound.cmp(&b.sound)));
+    stats
+  }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoundTime {
+  pub sound: String,
+  pub seconds: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListeningStats {
+  pub total_seconds: u64,
+  pub session_count: u32,
+  pub longest_session_seconds: u64,
+  /// Most played first. Sounds played together each count the full time.
+  pub sounds: Vec<SoundTime>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+  Today,
+  Week,
+  Month,
+  All,
+}
+
+pub struct HistoryState {
+  history: Mutex<History>,
+  path: Option<PathBuf>,
+}
+
+/// Local midnight starting `period`, weeks starting on Monday.
+fn period_start(period: Period) -> u64 {
+  let today = Local::now().date_naive();
+  let day = match period {
+    Period::Today => today,
+    Period::Week => today - chrono::Days::new(today.weekday().num_days_from_monday() as u64),
+    Period::Month => today.with_day(1).unwrap_or(today),
+    Period::All => return 0,
+  };
+  day
+    .and_hms_opt(0, 0, 0)
+    .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+    .map(|start| start.timestamp_millis().max(0) as u64)
+    .unwrap_or_default()
+}
+
+fn mix(active_sounds: &[String], volumes: &HashMap<String, f32>, is_muted: bool) -> Option<Mix> {
+  if is_muted || active_sounds.is_empty() {
+    return None;
+  }
+  Some(
+    active_sounds
+      .iter()
+      .map(|id| {
+        let volume = volumes.get(id).copied().unwrap_or(1.0).clamp(0.0, 1.0);
+        (id.clone(), (volume * 100.0).round() / 100.0)
+      })
+      .collect(),
+  )
+}
+
+fn load(path: &Path) -> Vec<Session> {
+  let bytes = match std::fs::read(path) {
+    Ok(bytes) => bytes,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+    Err(e) => {
+      log::warn!("Failed to read {}: {}", path.display(), e);
+      return Vec::new();
+    }
+  };
+  serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+    log::warn!("Ignoring unreadable {}: {}", path.display(), e);
+    Vec::new()
+  })
+}
+
+fn save(path: &Path, sessions: &[Session]) -> Result<(), String> {
+  let json = serde_json::to_vec(sessions).map_err(|e| e.to_string())?;
+  util::write_atomic(path, &json).map_err(|e| format!("Failed to save the listening history: {}", e))
+}
+
+fn persist<R: Runtime>(app: &AppHandle<R>) {
+  let state = app.state::<HistoryState>();
+  let Some(path) = &state.path else {
+    return;
+  };
+  let sessions = match state.history.lock() {
+    Ok(history) => history.sessions.clone(),
+    Err(_) => return,
+  };
+  if let Err(e) = save(path, &sessions) {
+    log::warn!("{}", e);
+  }
+}
+
+/// Follow the frontend's sound selection, from `sync_audio_state`.
+pub fn playback_changed<R: Runtime>(
+  app: &AppHandle<R>,
+  active_sounds: &[String],
+  volumes: &HashMap<String, f32>,
+  is_muted: bool,
+) {
+  let Some(state) = app.try_state::<HistoryState>() else {
+    return;
+  };
+  let stopped = {
+    let Ok(mut history) = state.history.lock() else {
+      return;
+    };
+    let was_open = history.open;
+    history.update(mix(active_sounds, volumes, is_muted), now_millis());
+    was_open && !history.open
+  };
+  // Mix changes are saved with the next checkpoint.
+  if stopped {
+    persist(app);
+  }
+}
+
+async fn keep_checkpointing<R: Runtime>(app: AppHandle<R>) {
+  let mut tick = tokio::time::interval(CHECKPOINT_INTERVAL);
+  tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+  loop {
+    tick.tick().await;
+    let open = match app.state::<HistoryState>().history.lock() {
+      Ok(mut history) => {
+        history.checkpoint(now_millis());
+        history.open
+      }
+      Err(_) => continue,
+    };
+    if open {
+      persist(&app);
+    }
+  }
+}
+
+/// Close the open session before the app exits.
+pub fn flush<R: Runtime>(app: &AppHandle<R>) {
+  let Some(state) = app.try_state::<HistoryState>() else {
+    return;
+  };
+  if let Ok(mut history) = state.history.lock() {
+    history.update(None, now_millis());
+  }
+  persist(app);
+}
+
+/// Load the history and start checkpointing.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+  let path = match app.path().app_data_dir() {
+    Ok(dir) => Some(dir.join(HISTORY_FILE)),
+    Err(e) => {
+      log::warn!("No place for the listening history: {}", e);
+      None
+    }
+  };
+  let mut history = History {
+    sessions: path.as_deref().map(load).unwrap_or_default(),
+    open: false,
+  };
+  history.prune(now_millis());
+  app.manage(HistoryState {
+    history: Mutex::new(history),
+    path,
+  });
+  tauri::async_runtime::spawn(keep_checkpointing(app.clone()));
+}
+
+#[tauri::command]
+pub fn get_listening_stats(state: tauri::State<HistoryState>, period: Period) -> Result<ListeningStats, String> {
+  let history = state.history.lock().map_err(|e| e.to_string())?;
+  Ok(history.stats(period_start(period)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SECOND: u64 = 1000;
+  const MINUTE: u64 = 60 * SECOND;
+
+  fn sounds(entries: &[(&str, f32)]) -> Option<Mix> {
+    Some(entries.iter().map(|(id, volume)| (id.to_string(), *volume)).collect())
+  }
+
+  #[test]
+  fn quick_changes_replace_the_segment() {
+    let mut history = History::default();
+    history.update(sounds(&[("rain", 0.5)]), 0);
+    history.update(sounds(&[("rain", 0.6)]), MIN_SEGMENT_MILLIS - 1);
+    history.update(sounds(&[("rain", 0.6), ("fire", 1.0)]), MIN_SEGMENT_MILLIS + 10 * SECOND);
+
+    let segments = &history.sessions[0].segments;
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[0].sounds, sounds(&[("rain", 0.6)]).unwrap());
+    assert_eq!(segments[0].ended_at, MIN_SEGMENT_MILLIS + 10 * SECOND);
+    assert_eq!(segments[1].started_at, MIN_SEGMENT_MILLIS + 10 * SECOND);
+  }
+
+  #[test]
+  fn playing_again_soon_continues_the_session() {
+    let mut history = History::default();
+    history.update(sounds(&[("rain", 1.0)]), 0);
+    history.update(None, MINUTE);
+    assert!(!history.open);
+
+    history.update(sounds(&[("rain", 1.0)]), MINUTE + RESUME_GAP_MILLIS);
+    assert_eq!(history.sessions.len(), 1);
+    assert_eq!(history.sessions[0].segments.len(), 2);
+
+    history.update(None, 2 * MINUTE + RESUME_GAP_MILLIS);
+    history.update(sounds(&[("rain", 1.0)]), 2 * MINUTE + 2 * RESUME_GAP_MILLIS + 1);
+    assert_eq!(history.sessions.len(), 2);
+  }
+
+  #[test]
+  fn checkpoint_after_a_suspend_splits_the_session() {
+    let mut history = History::default();
+    history.update(sounds(&[("rain", 1.0)]), 0);
+    history.checkpoint(MINUTE);
+    assert_eq!(history.sessions[0].ended_at, MINUTE);
+
+    let woke = MINUTE + SUSPEND_GAP_MILLIS + 1;
+    history.checkpoint(woke);
+    assert_eq!(history.sessions.len(), 2);
+    assert_eq!(history.sessions[0].ended_at, MINUTE);
+    assert_eq!(history.sessions[1].started_at, woke);
+    assert_eq!(history.sessions[1].segments[0].sounds, sounds(&[("rain", 1.0)]).unwrap());
+    assert!(history.open);
+  }
+
+  #[test]
+  fn checkpoint_does_nothing_when_stopped() {
+    let mut history = History::default();
+    history.update(sounds(&[("rain", 1.0)]), 0);
+    history.update(None, MINUTE);
+    history.checkpoint(2 * MINUTE);
+    assert_eq!(history.sessions[0].ended_at, MINUTE);
+  }
+
+  #[test]
+  fn stats_count_time_since_the_start_of_the_period() {
+    let mut history = History::default();
+    history.update(sounds(&[("rain", 1.0)]), 0);
+    history.update(sounds(&[("rain", 1.0), ("fire", 0.5)]), 2 * MINUTE);
+    history.update(None, 3 * MINUTE);
+    history.update(sounds(&[("fire", 1.0)]), 10 * MINUTE);
+    history.update(None, 15 * MINUTE);
+
+    let all = history.stats(0);
+    assert_eq!(all.session_count, 2);
+    assert_eq!(all.total_seconds, 8 * 60);
+    assert_eq!(all.longest_session_seconds, 5 * 60);
+    assert_eq!(
+      all.sounds,
+      vec![
+        SoundTime {
+          sound: "fire".to_string(),
+          seconds: 6 * 60,
+        },
+        SoundTime {
+          sound: "rain".to_string(),
+          seconds: 3 * 60,
+        },
+      ]
+    );
+
+    // Only the part of the first session after the cutoff counts.
+    let recent = history.stats(MINUTE);
+    assert_eq!(recent.total_seconds, 7 * 60);
+    assert_eq!(recent.session_count, 2);
+    assert_eq!(history.stats(15 * MINUTE), ListeningStats::default());
+  }
+}
@@ -0,0 +1,298 @@
+//! Shell hooks: the user's own commands, run when a session starts, pauses,
+//! resumes, completes or is abandoned, or when a break starts (see
+//! `docs/07-shell-hooks.md`).
+//!
+//! The commands are read from `<app config>/protected/hooks.json` on every
+//! event. Since that runs arbitrary commands, nothing runs until the user
+//! turns hooks on from the tray. That choice is kept next to it in
+//! `hooks-settings.json`. Both are [`protected`] files, which page scripts
+//! can't write, so they can neither add commands nor skip the confirmation.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::timer::{SessionEvent, TimerMode, TimerSession, BREAK_PRESETS};
+use crate::{protected, util};
+
+const HOOKS_FILE: &str = "hooks.json";
+const SETTINGS_FILE: &str = "hooks-settings.json";
+const DEFAULT_TIMEOUT_SECONDS: u64 = 10;
+const MAX_TIMEOUT_SECONDS: u64 = 300;
+/// Output beyond this is cut from the log.
+const MAX_LOGGED_OUTPUT: usize = 2000;
+
+/// Contents of `hooks.json`: commands per hook, run one after another
+/// through the shell.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+struct HookConfig {
+  timeout_seconds: Option<u64>,
+  start: Vec<String>,
+  pause: Vec<String>,
+  resume: Vec<String>,
+  complete: Vec<String>,
+  abandon: Vec<String>,
+  #[serde(rename = "break")]
+  break_: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Hook {
+  Start,
+  Pause,
+  Resume,
+  Complete,
+  Abandon,
+  Break,
+}
+
+impl Hook {
+  fn for_event(event: SessionEvent, session: &TimerSession) -> Self {
+    match event {
+      SessionEvent::Started if session.preset.as_deref().is_some_and(|p| BREAK_PRESETS.contains(&p)) => Hook::Break,
+      SessionEvent::Started => Hook::Start,
+      SessionEvent::Paused => Hook::Pause,
+      SessionEvent::Resumed => Hook::Resume,
+      SessionEvent::Completed => Hook::Complete,
+      SessionEvent::Abandoned => Hook::Abandon,
+    }
+  }
+
+  fn name(self) -> &'static str {
+    match self {
+      Hook::Start => "start",
+      Hook::Pause => "pause",
+      Hook::Resume => "resume",
+      Hook::Complete => "complete",
+      Hook::Abandon => "abandon",
+      Hook::Break => "break",
+    }
+  }
+}
+
+impl HookConfig {
+  fn commands(&self, hook: Hook) -> &[String] {
+    match hook {
+      Hook::Start => &self.start,
+      Hook::Pause => &self.pause,
+      Hook::Resume => &self.resume,
+      Hook::Complete => &self.complete,
+      Hook::Abandon => &self.abandon,
+      Hook::Break => &self.break_,
+    }
+  }
+
+  fn timeout(&self) -> Duration {
+    let seconds = self
+      .timeout_seconds
+      .unwrap_or(DEFAULT_TIMEOUT_SECONDS)
+      .clamp(1, MAX_TIMEOUT_SECONDS);
+    Duration::from_secs(seconds)
+  }
+}
+
+/// Off until turned on from the tray. Earlier builds kept the switch where
+/// the webview could write it, so it isn't carried over from there.
+pub fn is_enabled<R: Runtime>(app: &AppHandle<R>) -> bool {
+  let Ok(path) = protected::path(app, SETTINGS_FILE) else {
+    return false;
+  };
+  util::read_switch(&path).unwrap_or(false)
+}
+
+pub fn set_enabled<R: Runtime>(app: &AppHandle<R>, enabled: bool) -> Result<(), String> {
+  let path = protected::path(app, SETTINGS_FILE)?;
+  util::write_switch(&path, enabled).map_err(|e| format!("Failed to save {}: {}", path.display(), e))
+}
+
+pub fn config_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+  protected::path(app, HOOKS_FILE)
+}
+
+/// Write an empty `hooks.json` for the user to fill in, unless there is
+/// one already.
+pub fn create_config<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+  let path = config_path(app)?;
+  if path.exists() {
+    return Ok(path);
+  }
+  if let Some(dir) = path.parent() {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+  }
+  let template = HookConfig {
+    timeout_seconds: Some(DEFAULT_TIMEOUT_SECONDS),
+    ..Default::default()
+  };
+  let json = serde_json::to_string_pretty(&template).map_err(|e| e.to_string())?;
+  std::fs::write(&path, json).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+  Ok(path)
+}
+
+fn load_config(path: &Path) -> Result<HookConfig, String> {
+  let bytes = match std::fs::read(path) {
+    Ok(bytes) => bytes,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HookConfig::default()),
+    Err(e) => return Err(e.to_string()),
+  };
+  serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+}
+
+/// `ZEROHZ_*` variables describing the session.
+fn environment(hook: Hook, session: &TimerSession) -> Vec<(&'static str, String)> {
+  let mode = match session.mode {
+    TimerMode::Stopwatch => "stopwatch",
+    TimerMode::Countdown => "countdown",
+  };
+  vec![
+    ("ZEROHZ_EVENT", hook.name().to_string()),
+    ("ZEROHZ_MODE", mode.to_string()),
+    ("ZEROHZ_PRESET", session.preset.clone().unwrap_or_default()),
+    ("ZEROHZ_TASK", session.task_description.clone().unwrap_or_default()),
+    ("ZEROHZ_DURATION_SECONDS", session.target_seconds.to_string()),
+    ("ZEROHZ_ELAPSED_SECONDS", session.actual_seconds.to_string()),
+    ("ZEROHZ_STARTED_AT", session.started_at.to_string()),
+    ("ZEROHZ_SOUNDS", session.active_sounds.join(",")),
+    ("ZEROHZ_IS_BREAK", if hook == Hook::Break { "1" } else { "0" }.to_string()),
+    ("ZEROHZ_SESSION_JSON", serde_json::to_string(session).unwrap_or_default()),
+  ]
+}
+
+fn shell(command: &str) -> tokio::process::Command {
+  #[cfg(windows)]
+  {
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let mut cmd = tokio::process::Command::new("cmd");
+    cmd.arg("/C").arg(command).creation_flags(CREATE_NO_WINDOW);
+    cmd
+  }
+  #[cfg(not(windows))]
+  {
+    let mut cmd = tokio::process::Command::new("sh");
+    // Its own process group, so a timeout can kill whatever the shell started.
+    cmd.arg("-c").arg(command).process_group(0);
+    cmd
+  }
+}
+
+/// Kill the hook and, on Unix, everything else in its process group. The
+/// shell itself may be gone already while something it started keeps the
+/// output open, so the group id is the one taken at spawn.
+fn kill(child: &mut tokio::process::Child, group: Option<u32>) {
+  #[cfg(unix)]
+  if let Some(group) = group {
+    // SAFETY: plain syscall on the group created for this hook.
+    unsafe {
+      libc::killpg(group as libc::pid_t, libc::SIGKILL);
+    }
+  }
+  #[cfg(not(unix))]
+  let _ = group;
+  let _ = child.start_kill();
+}
+
+fn captured(name: &str, bytes: &[u8]) -> String {
+  let text = String::from_utf8_lossy(bytes);
+  let text = text.trim();
+  if text.is_empty() {
+    return String::new();
+  }
+  let mut end = text.len().min(MAX_LOGGED_OUTPUT);
+  while !text.is_char_boundary(end) {
+    end -= 1;
+  }
+  let cut = if end < text.len() { " …" } else { "" };
+  format!("\n{}: {}{}", name, &text[..end], cut)
+}
+
+async fn read_all(pipe: Option<impl AsyncRead + Unpin>) -> Vec<u8> {
+  let mut bytes = Vec::new();
+  if let Some(mut pipe) = pipe {
+    let _ = pipe.read_to_end(&mut bytes).await;
+  }
+  bytes
+}
+
+async fn run(hook: Hook, command: &str, env: &[(&'static str, String)], timeout: Duration) {
+  let mut cmd = shell(command);
+  cmd
+    .envs(env.iter().map(|(k, v)| (*k, v.as_str())))
+    .stdin(Stdio::null())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .kill_on_drop(true);
+  let mut child = match cmd.spawn() {
+    Ok(child) => child,
+    Err(e) => {
+      log::warn!("Failed to run {} hook `{}`: {}", hook.name(), command, e);
+      return;
+    }
+  };
+
+  let group = child.id();
+  let stdout = child.stdout.take();
+  let stderr = child.stderr.take();
+  let output = async {
+    let (status, stdout, stderr) = tokio::join!(child.wait(), read_all(stdout), read_all(stderr));
+    status.map(|status| (status, stdout, stderr))
+  };
+  let result = tokio::time::timeout(timeout, output).await;
+  if result.is_err() {
+    kill(&mut child, group);
+  }
+  match result {
+    Ok(Ok((status, stdout, stderr))) => {
+      let level = if status.success() {
+        log::Level::Info
+      } else {
+        log::Level::Warn
+      };
+      log::log!(
+        level,
+        "{} hook `{}` exited with {}{}{}",
+        hook.name(),
+        command,
+        status,
+        captured("stdout", &stdout),
+        captured("stderr", &stderr)
+      );
+    }
+    Ok(Err(e)) => log::warn!("{} hook `{}` failed: {}", hook.name(), command, e),
+    Err(_) => log::warn!(
+      "{} hook `{}` was killed after {}s",
+      hook.name(),
+      command,
+      timeout.as_secs()
+    ),
+  }
+}
+
+/// Run the commands for `event` in the background, if hooks are on.
+pub fn session_event<R: Runtime>(app: &AppHandle<R>, event: SessionEvent, session: &TimerSession) {
+  if !is_enabled(app) {
+    return;
+  }
+  let config = match config_path(app).and_then(|path| load_config(&path)) {
+    Ok(config) => config,
+    Err(e) => {
+      log::warn!("Skipping shell hooks, {} is invalid: {}", HOOKS_FILE, e);
+      return;
+    }
+  };
+  let hook = Hook::for_event(event, session);
+  let commands = config.commands(hook).to_vec();
+  if commands.is_empty() {
+    return;
+  }
+  let env = environment(hook, session);
+  let timeout = config.timeout();
+  tauri::async_runtime::spawn(async move {
+    for command in &commands {
+      run(hook, command, &env, timeout).await;
+    }
+  });
+}
@@ -0,0 +1,229 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+use tauri_plugin_store::StoreExt;
+
+use crate::audio::decoder;
+use crate::{catalog, util};
+
+/// Shared with [`crate::packs`], which keeps its registry next to ours.
+pub const SOUNDS_STORE_FILE: &str = "sounds.json";
+const CUSTOM_SOUNDS_STORE_KEY: &str = "custom";
+/// Imported files live in `<app data>/sounds/<id>.<ext>`.
+const SOUNDS_DIR: &str = "sounds";
+
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["wav", "mp3", "ogg", "flac", "m4a"];
+const MAX_FILE_SIZE: u64 = 200 * 1024 * 1024;
+/// Anything shorter loops audibly; anything longer is decoded to a few GB.
+const MIN_DURATION: f64 = 1.0;
+const MAX_DURATION: f64 = 60.0 * 60.0;
+const MAX_CUSTOM_SOUNDS: usize = 100;
+const MAX_LABEL_LEN: usize = 40;
+
+/// A sound the user imported from disk.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomSound {
+  pub id: String,
+  pub label: String,
+  /// File name inside the sounds dir.
+  pub file: String,
+  /// Length in seconds.
+  pub duration: f64,
+  /// Unix time in milliseconds.
+  pub imported_at: u64,
+}
+
+pub fn custom_sounds<R: Runtime>(app: &AppHandle<R>) -> Vec<CustomSound> {
+  app
+    .store(SOUNDS_STORE_FILE)
+    .ok()
+    .and_then(|s| s.get(CUSTOM_SOUNDS_STORE_KEY))
+    .and_then(|v| serde_json::from_value(v).ok())
+    .unwrap_or_default()
+}
+
+fn save_custom_sounds<R: Runtime>(app: &AppHandle<R>, sounds: &[CustomSound]) -> Result<(), String> {
+  let store = app.store(SOUNDS_STORE_FILE).map_err(|e| e.to_string())?;
+  store.set(
+    CUSTOM_SOUNDS_STORE_KEY,
+    serde_json::to_value(sounds).map_err(|e| e.to_string())?,
+  );
+  store.save().map_err(|e| e.to_string())
+}
+
+pub fn sounds_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+  app
+    .path()
+    .app_data_dir()
+    .map(|dir| dir.join(SOUNDS_DIR))
+    .map_err(|e| e.to_string())
+}
+
+fn extension_of(path: &Path) -> Option<String> {
+  path
+    .extension()
+    .and_then(|e| e.to_str())
+    .map(|e| e.to_ascii_lowercase())
+    .filter(|e| SUPPORTED_EXTENSIONS.contains(&e.as_str()))
+}
+
+/// "My Rain_Recording (2)" -> "my-rain-recording-2"
+fn slugify(name: &str) -> String {
+  let mut slug = String::new();
+  for c in name.chars() {
+    if c.is_ascii_alphanumeric() {
+      slug.push(c.to_ascii_lowercase());
+    } else if !slug.is_empty() && !slug.ends_with('-') {
+      slug.push('-');
+    }
+  }
+  let slug = slug.trim_end_matches('-');
+  if slug.is_empty() {
+    "sound".to_string()
+  } else {
+    slug.chars().take(32).collect::<String>().trim_end_matches('-').to_string()
+  }
+}
+
+/// Ids are `custom-<slug>`, suffixed on collision, so they can never shadow
+/// a built-in.
+fn unique_id(stem: &str, existing: &[CustomSound]) -> String {
+  let base = format!("custom-{}", slugify(stem));
+  let taken = |id: &str| catalog::is_builtin(id) || existing.iter().any(|s| s.id == id);
+  if !taken(&base) {
+    return base;
+  }
+  (2..)
+    .map(|n| format!("{}-{}", base, n))
+    .find(|id| !taken(id))
+    .unwrap_or(base)
+}
+
+fn label_from(stem: &str) -> String {
+  let label = stem
+    .replace(['_', '-'], " ")
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ");
+  if label.is_empty() {
+    "Custom Sound".to_string()
+  } else {
+    label.chars().take(MAX_LABEL_LEN).collect()
+  }
+}
+
+fn import_file<R: Runtime>(app: &AppHandle<R>, source: &Path) -> Result<CustomSound, String> {
+  let ext = extension_of(source).ok_or_else(|| {
+    format!(
+      "Unsupported file type (expected one of: {})",
+      SUPPORTED_EXTENSIONS.join(", ")
+    )
+  })?;
+  let size = std::fs::metadata(source)
+    .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?
+    .len();
+  if size > MAX_FILE_SIZE {
+    return Err(format!("File is larger than {} MB", MAX_FILE_SIZE / 1024 / 1024));
+  }
+
+  let mut sounds = custom_sounds(app);
+  if sounds.len() >= MAX_CUSTOM_SOUNDS {
+    return Err(format!("Cannot import more than {} sounds", MAX_CUSTOM_SOUNDS));
+  }
+
+  // Decode the whole file up front: a file that imports is a file that plays.
+  let bytes = std::fs::read(source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+  let decoded = decoder::decode(bytes, Some(&ext))?;
+  let duration = decoded.frames() as f64 / decoded.sample_rate as f64;
+  if duration < MIN_DURATION {
+    return Err(format!("Sound is shorter than {} second", MIN_DURATION));
+  }
+  if duration > MAX_DURATION {
+    return Err(format!("Sound is longer than {} minutes", MAX_DURATION / 60.0));
+  }
+
+  let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+  let id = unique_id(stem, &sounds);
+  let file = format!("{}.{}", id, ext);
+
+  // Every supported format is decoded natively, so the original is kept
+  // as-is rather than transcoded.
+  let dir = sounds_dir(app)?;
+  std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+  std::fs::copy(source, dir.join(&file)).map_err(|e| format!("Failed to copy sound: {}", e))?;
+
+  let sound = CustomSound {
+    id,
+    label: label_from(stem),
+    file,
+    duration,
+    imported_at: util::now_millis(),
+  };
+  sounds.push(sound.clone());
+  save_custom_sounds(app, &sounds)?;
+  log::info!("Imported {} as {}", source.display(), sound.id);
+  catalog::notify_changed(app);
+  Ok(sound)
+}
+
+/// Import an audio file as a custom sound. Without `path` a file picker is
+/// shown (and failures are reported in a dialog too); cancelling it resolves
+/// to `null`.
+#[tauri::command]
+pub async fn import_sound<R: Runtime>(app: AppHandle<R>, path: Option<String>) -> Result<Option<CustomSound>, String> {
+  tauri::async_runtime::spawn_blocking(move || {
+    let interactive = path.is_none();
+    let source = match path {
+      Some(path) => PathBuf::from(path),
+      None => {
+        let picked = app
+          .dialog()
+          .file()
+          .add_filter("Audio", SUPPORTED_EXTENSIONS)
+          .blocking_pick_file();
+        match picked {
+          Some(file) => file.into_path().map_err(|e| e.to_string())?,
+          None => return Ok(None),
+        }
+      }
+    };
+    let result = import_file(&app, &source);
+    if let (true, Err(e)) = (interactive, &result) {
+      app
+        .dialog()
+        .message(e)
+        .title("Couldn't import sound")
+        .kind(MessageDialogKind::Error)
+        .show(|_| {});
+    }
+    result.map(Some)
+  })
+  .await
+  .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn list_custom_sounds<R: Runtime>(app: AppHandle<R>) -> Vec<CustomSound> {
+  custom_sounds(&app)
+}
+
+#[tauri::command]
+pub fn remove_custom_sound<R: Runtime>(app: AppHandle<R>, sound_id: String) -> Result<(), String> {
+  let mut sounds = custom_sounds(&app);
+  let Some(index) = sounds.iter().position(|s| s.id == sound_id) else {
+    return Ok(());
+  };
+  let sound = sounds.remove(index);
+  save_custom_sounds(&app, &sounds)?;
+
+  if let Ok(dir) = sounds_dir(&app) {
+    if let Err(e) = std::fs::remove_file(dir.join(&sound.file)) {
+      log::warn!("Failed to delete {}: {}", sound.file, e);
+    }
+  }
+  catalog::notify_changed(&app);
+  Ok(())
+}
@@ -0,0 +1,109 @@
+//! Single-instance handling. The first ZeroHz listens on a per-user local
+//! socket; later invocations forward their request to it, print the
+//! response and exit instead of opening a second window.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use interprocess::local_socket::{prelude::*, ListenerOptions, Name, Stream};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+use crate::remote::{self, Action, Response};
+
+/// Requests are one JSON line; anything longer isn't one of ours.
+const MAX_REQUEST_LEN: u64 = 64 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Request {
+  pub actions: Vec<Action>,
+}
+
+/// Debug builds get their own socket so they can run next to an installed
+/// release.
+fn socket_name() -> io::Result<Name<'static>> {
+  let base = if cfg!(debug_assertions) { "zerohz-dev" } else { "zerohz" };
+
+  // A socket file rather than an abstract (Linux) socket, which any local
+  // user could connect to. The runtime dir is private to the user; the temp
+  // dir fallback is per-user on macOS.
+  #[cfg(unix)]
+  {
+    use interprocess::local_socket::GenericFilePath;
+    let path = match std::env::var_os("XDG_RUNTIME_DIR") {
+      Some(dir) => std::path::PathBuf::from(dir).join(format!("{}.sock", base)),
+      None => {
+        let user = std::env::var("USER").unwrap_or_default();
+        std::env::temp_dir().join(format!("{}-{}.sock", base, user))
+      }
+    };
+    path.to_fs_name::<GenericFilePath>()
+  }
+
+  #[cfg(windows)]
+  {
+    use interprocess::local_socket::GenericNamespaced;
+    let user = std::env::var("USERNAME").unwrap_or_default();
+    format!("{}-{}", base, user).to_ns_name::<GenericNamespaced>()
+  }
+}
+
+/// Send `request` to the running instance. `None` when there isn't one.
+pub fn forward(request: &Request) -> Option<io::Result<Response>> {
+  let stream = Stream::connect(socket_name().ok()?).ok()?;
+  let mut stream = BufReader::new(stream);
+  let mut exchange = || -> io::Result<Response> {
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.get_mut().write_all(line.as_bytes())?;
+
+    let mut reply = String::new();
+    stream.read_line(&mut reply)?;
+    Ok(serde_json::from_str(&reply)?)
+  };
+  Some(exchange())
+}
+
+/// Start accepting requests from later invocations. Only called once no
+/// running instance answered, so a leftover socket file is stale and is
+/// replaced.
+pub fn listen<R: Runtime>(app: &AppHandle<R>) {
+  let listener = socket_name().and_then(|name| ListenerOptions::new().name(name).try_overwrite(true).create_sync());
+  let listener = match listener {
+    Ok(listener) => listener,
+    Err(e) => {
+      log::warn!("Command-line control unavailable: {}", e);
+      return;
+    }
+  };
+
+  let app = app.clone();
+  std::thread::spawn(move || {
+    for stream in listener.incoming() {
+      match stream {
+        Ok(stream) => {
+          let app = app.clone();
+          std::thread::spawn(move || {
+            if let Err(e) = serve(&app, stream) {
+              log::warn!("Failed to serve command-line request: {}", e);
+            }
+          });
+        }
+        Err(e) => log::warn!("Failed to accept command-line connection: {}", e),
+      }
+    }
+  });
+}
+
+fn serve<R: Runtime>(app: &AppHandle<R>, stream: Stream) -> io::Result<()> {
+  let mut stream = BufReader::new(stream);
+  let mut line = String::new();
+  (&mut stream).take(MAX_REQUEST_LEN).read_line(&mut line)?;
+
+  let response = match serde_json::from_str::<Request>(&line) {
+    Ok(request) => remote::handle(app, &request.actions),
+    Err(e) => Response::failed(format!("Invalid request: {}", e)),
+  };
+  let mut reply = serde_json::to_string(&response)?;
+  reply.push('\n');
+  stream.get_mut().write_all(reply.as_bytes())
+}
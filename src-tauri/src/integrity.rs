@@ -0,0 +1,62 @@
+//! Keyed checksums for state ZeroHz saves but the user shouldn't change by
+//! hand, like the cached license check.
+//!
+//! The key is derived from the machine id, so a file copied to another
+//! computer doesn't verify either. It stops edits, not someone reading the
+//! source; anything that matters more is checked by a server.
+
+use std::sync::OnceLock;
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::util;
+
+const SALT: &[u8] = b"zerohz/integrity/v1";
+
+fn machine_id() -> &'static str {
+  static ID: OnceLock<String> = OnceLock::new();
+  ID.get_or_init(|| match machine_uid::get() {
+    Ok(id) => id.trim().to_string(),
+    Err(e) => {
+      log::warn!("No machine id, checksums won't be device-bound: {}", e);
+      String::new()
+    }
+  })
+}
+
+/// One key per `purpose`, so a checksum can't be reused for other data.
+fn mac(purpose: &str) -> Hmac<Sha256> {
+  let mut key = Sha256::new();
+  key.update(SALT);
+  key.update(machine_id().as_bytes());
+  key.update([0]);
+  key.update(purpose.as_bytes());
+  Hmac::<Sha256>::new_from_slice(&key.finalize()).expect("HMAC accepts keys of any length")
+}
+
+/// Hex checksum of `data`.
+pub fn seal(purpose: &str, data: &[u8]) -> String {
+  let mut mac = mac(purpose);
+  mac.update(data);
+  util::hex(&mac.finalize().into_bytes())
+}
+
+pub fn verify(purpose: &str, data: &[u8], checksum: &str) -> bool {
+  let Some(expected) = decode_hex(checksum) else {
+    return false;
+  };
+  let mut mac = mac(purpose);
+  mac.update(data);
+  mac.verify_slice(&expected).is_ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+  if hex.len() % 2 != 0 {
+    return None;
+  }
+  (0..hex.len())
+    .step_by(2)
+    .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+    .collect()
+}
@@ -1,20 +1,45 @@
+#[cfg(feature = "analytics")]
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use tauri::{
-  menu::{Menu, MenuItem, MenuItemBuilder, PredefinedMenuItem, CheckMenuItemBuilder, CheckMenuItem, Submenu},
+  menu::{IsMenuItem, Menu, MenuItem, MenuItemBuilder, PredefinedMenuItem, CheckMenuItemBuilder, CheckMenuItem, Submenu},
   tray::TrayIconBuilder,
-  Emitter, Manager, PhysicalPosition, Runtime, Wry,
+  Emitter, Listener, Manager, PhysicalPosition, Runtime, Wry,
 };
 use tauri_plugin_autostart::ManagerExt;
-use tauri_plugin_updater::UpdaterExt;
+use tauri_plugin_deep_link::DeepLinkExt;
 use tauri_plugin_process::init as process_init;
 
-#[derive(Clone)]
-enum UpdateState {
-  Checking,
-  Available(String),
-  Latest(String),
-  Failed,
-}
+#[cfg(feature = "analytics")]
+mod analytics;
+mod api;
+mod audio;
+mod catalog;
+mod cli;
+mod config;
+mod deeplink;
+mod history;
+mod hooks;
+mod import;
+mod instance;
+mod integrity;
+mod license;
+#[cfg(target_os = "linux")]
+mod mpris;
+mod packs;
+mod protected;
+mod remote;
+mod scenes;
+#[cfg(unix)]
+mod statusbar;
+mod timer;
+#[cfg(feature = "updater")]
+mod updater;
+mod usage;
+#[cfg(test)]
+mod test_support;
+mod util;
+mod webhooks;
 
 struct TrayMenuState<R: Runtime> {
   ko: CheckMenuItem<R>,
@@ -22,20 +47,43 @@ struct TrayMenuState<R: Runtime> {
   show_window: CheckMenuItem<R>,
   session_history: MenuItem<R>,
   usage: MenuItem<R>,
+  audio_output: MenuItem<R>,
+  sound_packs: MenuItem<R>,
+  webhooks: MenuItem<R>,
   autostart: CheckMenuItem<R>,
+  local_api: CheckMenuItem<R>,
+  shell_hooks: CheckMenuItem<R>,
+  #[cfg(feature = "analytics")]
+  share_usage_data: CheckMenuItem<R>,
   activate_license: MenuItem<R>,
   language_submenu: Submenu<R>,
+  #[cfg(feature = "updater")]
   check_update: MenuItem<R>,
+  #[cfg(feature = "updater")]
   update_status: MenuItem<R>,
   version_item: MenuItem<R>,
   quit: MenuItem<R>,
   current_lang: Mutex<String>,
-  update_state: Mutex<UpdateState>,
+  license_labels: Mutex<LicenseLabels>,
+  /// Whether the analytics consent prompt was shown this run.
+  #[cfg(feature = "analytics")]
+  consent_asked: AtomicBool,
+  #[cfg(feature = "updater")]
+  update_state: Mutex<updater::UpdateState>,
   version_str: String,
 }
 
+/// The language the frontend last synced, for dialogs shown from Rust.
+pub(crate) fn current_lang<R: Runtime>(app: &tauri::AppHandle<R>) -> String {
+  app
+    .try_state::<TrayMenuState<R>>()
+    .and_then(|state| state.current_lang.lock().ok().map(|l| l.clone()))
+    .unwrap_or_else(|| "en".to_string())
+}
+
 #[tauri::command]
-fn sync_language_tray<R: Runtime>(_app: tauri::AppHandle<R>, state: tauri::State<TrayMenuState<R>>, lang: String) {
+#[cfg_attr(not(feature = "analytics"), allow(unused_variables))]
+fn sync_language_tray<R: Runtime>(app: tauri::AppHandle<R>, state: tauri::State<TrayMenuState<R>>, lang: String) {
   let _ = state.ko.set_checked(lang == "ko");
   let _ = state.en.set_checked(lang == "en");
   let current_lang = {
@@ -53,10 +101,30 @@ fn sync_language_tray<R: Runtime>(_app: tauri::AppHandle<R>, state: tauri::State
   let _ = state.version_item.set_text(&version_label);
 
   // Re-apply update status for new language (avoid double-locking)
+  #[cfg(feature = "updater")]
   if let Some(st) = state.update_state.lock().ok().map(|s| s.clone()) {
-    let locale = get_update_locale(&current_lang);
-    apply_update_state(&state, &locale, st);
+    let locale = updater::get_update_locale(&current_lang);
+    updater::apply_update_state(&state, &locale, st);
   }
+
+  // The first sync is the first moment the prompt can be in the user's
+  // language.
+  #[cfg(feature = "analytics")]
+  if config::get(&app).analytics_enabled()
+    && analytics::consent(&app).is_none()
+    && !state.consent_asked.swap(true, Ordering::SeqCst)
+  {
+    ask_analytics_consent(&app, &current_lang);
+  }
+}
+
+/// Labels of `activate_license`, which manages the license once Pro.
+struct LicenseLabels {
+  activate: String,
+  manage: String,
+  /// Managing while in the offline grace period; `{days}` is replaced
+  /// with the days left.
+  manage_offline: String,
 }
 
 #[derive(serde::Deserialize)]
@@ -64,152 +132,274 @@ struct TrayLabels {
   show_window: String,
   session_history: String,
   usage: String,
+  audio_output: String,
+  sound_packs: String,
+  webhooks: String,
   start_at_login: String,
+  local_api: String,
+  shell_hooks: String,
+  #[cfg(feature = "analytics")]
+  share_usage_data: String,
   activate_license: String,
+  manage_license: String,
+  manage_license_offline: String,
   language: String,
+  #[cfg(feature = "updater")]
   check_for_updates: String,
   quit: String,
 }
 
 #[tauri::command]
-fn update_tray_menu<R: Runtime>(_app: tauri::AppHandle<R>, state: tauri::State<TrayMenuState<R>>, labels: TrayLabels) {
+fn update_tray_menu<R: Runtime>(app: tauri::AppHandle<R>, state: tauri::State<TrayMenuState<R>>, labels: TrayLabels) {
   let _ = state.show_window.set_text(&labels.show_window);
   let _ = state.session_history.set_text(&labels.session_history);
   let _ = state.usage.set_text(&labels.usage);
+  let _ = state.audio_output.set_text(&labels.audio_output);
+  let _ = state.sound_packs.set_text(&labels.sound_packs);
+  let _ = state.webhooks.set_text(&labels.webhooks);
   let _ = state.autostart.set_text(&labels.start_at_login);
-  let _ = state.activate_license.set_text(&labels.activate_license);
+  let _ = state.local_api.set_text(&labels.local_api);
+  let _ = state.shell_hooks.set_text(&labels.shell_hooks);
+  #[cfg(feature = "analytics")]
+  let _ = state.share_usage_data.set_text(&labels.share_usage_data);
+  if let Ok(mut license_labels) = state.license_labels.lock() {
+    *license_labels = LicenseLabels {
+      activate: labels.activate_license,
+      manage: labels.manage_license,
+      manage_offline: labels.manage_license_offline,
+    };
+  }
+  apply_pro_status(&state, &license::status(&app));
   let _ = state.language_submenu.set_text(&labels.language);
+  #[cfg(feature = "updater")]
   let _ = state.check_update.set_text(&labels.check_for_updates);
   let _ = state.quit.set_text(&labels.quit);
 }
 
-#[tauri::command]
-fn sync_pro_status<R: Runtime>(_app: tauri::AppHandle<R>, state: tauri::State<TrayMenuState<R>>, is_pro: bool, label: String) {
-  let _ = state.activate_license.set_text(&label);
-  let _ = state.activate_license.set_enabled(!is_pro);
+/// "Manage License" once licensed, with the days left while the license
+/// server can't be reached; the license module decides, not the frontend.
+fn apply_pro_status<R: Runtime>(state: &TrayMenuState<R>, status: &license::LicenseStatus) {
+  if let Ok(labels) = state.license_labels.lock() {
+    let text = match (status.is_pro, status.offline_until) {
+      (false, _) => labels.activate.clone(),
+      (true, None) => labels.manage.clone(),
+      (true, Some(until)) => {
+        let days = until.saturating_sub(util::now_millis()).div_ceil(24 * 60 * 60 * 1000);
+        labels.manage_offline.replace("{days}", &days.to_string())
+      }
+    };
+    let _ = state.activate_license.set_text(&text);
+  }
 }
 
-struct UpdateLocale<'a> {
-  title_available: &'a str,
-  message_available: &'a str,
-  button_install: &'a str,
-  button_later: &'a str,
-  title_ready: &'a str,
-  message_ready: &'a str,
-  title_error_download: &'a str,
-  message_error_download: &'a str,
-  title_error_check: &'a str,
-  message_error_check: &'a str,
-  title_no_update: &'a str,
-  message_no_update: &'a str,
-  title_init_error: &'a str,
-  message_init_error: &'a str,
-  status_checking: &'a str,
-  status_available: &'a str,
-  status_latest: &'a str,
-  status_failed: &'a str,
-  install_menu_label: &'a str,
-  check_menu_label: &'a str,
-  retry_menu_label: &'a str,
+/// Tell the user where to reach the local API after turning it on, or why
+/// it couldn't start.
+fn show_api_result<R: Runtime>(app: &tauri::AppHandle<R>, result: &Result<api::Endpoint, String>) {
+  use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+  let lang = app
+    .try_state::<TrayMenuState<R>>()
+    .and_then(|state| state.current_lang.lock().ok().map(|l| l.clone()))
+    .unwrap_or_else(|| "en".to_string());
+  let ko = lang == "ko";
+  let (title, message, kind) = match result {
+    Ok(endpoint) => (
+      if ko { "로컬 API 켜짐" } else { "Local API Enabled" },
+      if ko {
+        format!(
+          "자동화 API가 {} 에서 실행 중입니다.\n\n요청에는 다음 파일에 저장된 토큰이 필요합니다:\n{}",
+          endpoint.url,
+          endpoint.token_path.display()
+        )
+      } else {
+        format!(
+          "The automation API is listening on {}.\n\nRequests need the token saved in:\n{}",
+          endpoint.url,
+          endpoint.token_path.display()
+        )
+      },
+      MessageDialogKind::Info,
+    ),
+    Err(e) => (
+      if ko { "로컬 API 오류" } else { "Local API Error" },
+      e.clone(),
+      MessageDialogKind::Error,
+    ),
+  };
+  app.dialog().message(message).title(title).kind(kind).show(|_| {});
 }
 
-fn get_update_locale(lang: &str) -> UpdateLocale<'static> {
-  match lang {
-    "ko" => UpdateLocale {
-      title_available: "업데이트 가능",
-      message_available: "새 버전 {version}이 준비되었습니다. 지금 다운로드하고 설치할까요?",
-      button_install: "설치",
-      button_later: "나중에",
-      title_ready: "업데이트 완료",
-      message_ready: "업데이트가 설치되었습니다. ZeroHz를 다시 시작합니다.",
-      title_error_download: "업데이트 오류",
-      message_error_download: "업데이트 다운로드/설치에 실패했습니다. 다시 시도해 주세요.",
-      title_error_check: "업데이트 확인 실패",
-      message_error_check: "업데이트 확인에 실패했습니다. 인터넷 연결을 확인한 뒤 다시 시도해 주세요.",
-      title_no_update: "최신 버전입니다",
-      message_no_update: "이미 최신 버전(v{version})을 사용 중입니다.",
-      title_init_error: "업데이트 초기화 실패",
-      message_init_error: "업데이트 모듈을 초기화하지 못했습니다. 잠시 후 다시 시도해 주세요.",
-      status_checking: "… 업데이트 확인 중",
-      status_available: "⬆ 업데이트 가능: v{version}",
-      status_latest: "✓ 최신 버전",
-      status_failed: "⚠ 업데이트 확인 실패",
-      install_menu_label: "업데이트 설치 (v{version})",
-      check_menu_label: "업데이트 확인",
-      retry_menu_label: "업데이트 다시 확인",
-    },
-    _ => UpdateLocale {
-      title_available: "Update Available",
-      message_available: "New version {version} is available. Download and install now?",
-      button_install: "Install",
-      button_later: "Later",
-      title_ready: "Update Ready",
-      message_ready: "Update installed. ZeroHz will restart to apply it.",
-      title_error_download: "Update Error",
-      message_error_download: "Failed to download or install the update. Please try again.",
-      title_error_check: "Update Check Failed",
-      message_error_check: "Failed to check for updates. Please check your connection and try again.",
-      title_no_update: "No Updates Available",
-      message_no_update: "You are already running the latest version (v{version}).",
-      title_init_error: "Updater Error",
-      message_init_error: "Failed to initialize the updater. Please try again later.",
-      status_checking: "… Checking for updates",
-      status_available: "⬆ Update available: v{version}",
-      status_latest: "✓ Up to date",
-      status_failed: "⚠ Update check failed",
-      install_menu_label: "Install update (v{version})",
-      check_menu_label: "Check for Updates",
-      retry_menu_label: "Retry update check",
-    },
-  }
+/// Shell hooks run whatever is in `hooks.json`, so ask before turning them
+/// on. The file is created first and opened once the user agrees.
+fn confirm_shell_hooks(app: &tauri::AppHandle<Wry>) {
+  use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+  let lang = app
+    .try_state::<TrayMenuState<Wry>>()
+    .and_then(|state| state.current_lang.lock().ok().map(|l| l.clone()))
+    .unwrap_or_else(|| "en".to_string());
+  let ko = lang == "ko";
+  let path = match hooks::create_config(app) {
+    Ok(path) => path,
+    Err(e) => {
+      app
+        .dialog()
+        .message(e)
+        .title(if ko { "셸 훅 오류" } else { "Shell Hooks Error" })
+        .kind(MessageDialogKind::Error)
+        .show(|_| {});
+      return;
+    }
+  };
+  let message = if ko {
+    format!(
+      "타이머가 시작, 일시정지, 재개, 완료, 중단될 때와 휴식이 시작될 때 다음 파일의 명령이 실행됩니다:\n{}\n\n직접 작성했거나 신뢰하는 명령만 넣으세요.",
+      path.display()
+    )
+  } else {
+    format!(
+      "Commands in this file will run when a timer starts, pauses, resumes, completes or is abandoned, and when a break starts:\n{}\n\nOnly add commands you wrote or trust.",
+      path.display()
+    )
+  };
+  let handle = app.clone();
+  app
+    .dialog()
+    .message(message)
+    .title(if ko { "셸 훅을 켤까요?" } else { "Enable Shell Hooks?" })
+    .kind(MessageDialogKind::Warning)
+    .buttons(MessageDialogButtons::OkCancelCustom(
+      (if ko { "켜기" } else { "Enable" }).to_string(),
+      (if ko { "취소" } else { "Cancel" }).to_string(),
+    ))
+    .show(move |confirmed| {
+      if !confirmed {
+        return;
+      }
+      if let Err(e) = hooks::set_enabled(&handle, true) {
+        log::warn!("Failed to enable shell hooks: {}", e);
+        return;
+      }
+      if let Some(state) = handle.try_state::<TrayMenuState<Wry>>() {
+        let _ = state.shell_hooks.set_checked(true);
+      }
+      use tauri_plugin_opener::OpenerExt;
+      if let Err(e) = handle.opener().open_path(path.to_string_lossy(), None::<&str>) {
+        log::warn!("Failed to open {}: {}", path.display(), e);
+      }
+    });
 }
 
-fn apply_update_state<R: Runtime>(state: &TrayMenuState<R>, locale: &UpdateLocale<'_>, new_state: UpdateState) {
-  if let Ok(mut st) = state.update_state.lock() {
-    *st = new_state.clone();
-  }
+/// Asked once, on first run. Nothing is sent until the user agrees, and
+/// closing the dialog counts as no.
+#[cfg(feature = "analytics")]
+fn ask_analytics_consent<R: Runtime>(app: &tauri::AppHandle<R>, lang: &str) {
+  use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+  let ko = lang == "ko";
+  let message = if ko {
+    "어떤 사운드를 얼마나 재생하는지 같은 익명 사용 데이터를 보내 ZeroHz 개선을 도와주시겠어요?\n\n이름, 파일, 라이센스 키는 보내지 않습니다. 트레이 메뉴의 \"익명 사용 데이터 공유\"에서 언제든 바꿀 수 있습니다."
+  } else {
+    "Help improve ZeroHz by sharing anonymous usage data, such as which sounds are played and for how long?\n\nNo names, files or license keys are sent. You can change this any time with \"Share Anonymous Usage Data\" in the tray menu."
+  };
+  let handle = app.clone();
+  app
+    .dialog()
+    .message(message)
+    .title(if ko { "익명 사용 데이터 공유" } else { "Share Anonymous Usage Data" })
+    .kind(MessageDialogKind::Info)
+    .buttons(MessageDialogButtons::OkCancelCustom(
+      (if ko { "공유" } else { "Share" }).to_string(),
+      (if ko { "공유 안 함" } else { "Don't Share" }).to_string(),
+    ))
+    .show(move |agreed| {
+      if let Err(e) = analytics::set_consent(&handle, agreed) {
+        log::warn!("Failed to save analytics consent: {}", e);
+      }
+      if let Some(state) = handle.try_state::<TrayMenuState<R>>() {
+        let _ = state.share_usage_data.set_checked(analytics::is_enabled(&handle));
+      }
+    });
+}
 
-  match new_state {
-    UpdateState::Checking => {
-      let _ = state.update_status.set_text(locale.status_checking);
-      let _ = state.check_update.set_text(locale.check_menu_label);
-    }
-    UpdateState::Available(v) => {
-      let _ = state.update_status.set_text(locale.status_available.replace("{version}", &v));
-      let _ = state.check_update.set_text(locale.install_menu_label.replace("{version}", &v));
-    }
-    UpdateState::Latest(v) => {
-      let _ = state.update_status.set_text(locale.status_latest.replace("{version}", &v));
-      let _ = state.check_update.set_text(locale.check_menu_label);
-    }
-    UpdateState::Failed => {
-      let _ = state.update_status.set_text(locale.status_failed);
-      let _ = state.check_update.set_text(locale.retry_menu_label);
-    }
-  }
+/// The `posthog` plugin's permissions are only granted in builds that
+/// have it; see `build.rs`.
+#[cfg(feature = "analytics")]
+fn context() -> tauri::Context<Wry> {
+  tauri::generate_context!(capabilities = ["./optional-capabilities/analytics.json"])
+}
+
+#[cfg(not(feature = "analytics"))]
+fn context() -> tauri::Context<Wry> {
+  tauri::generate_context!()
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-  tauri::Builder::default()
+  let startup_actions = cli::forward_or_start();
+
+  // First, so nothing logged while the rest starts up is lost.
+  let builder = tauri::Builder::default()
+    .plugin(
+      tauri_plugin_log::Builder::default()
+        .level(log::LevelFilter::Info)
+        .build(),
+    )
+    .manage(timer::TimerState::default())
+    .manage(remote::RemoteState::default())
+    .manage(api::ApiState::default())
     .plugin(tauri_plugin_autostart::init(
       tauri_plugin_autostart::MacosLauncher::LaunchAgent,
       None, // No additional arguments
     ))
-    .plugin(tauri_plugin_updater::Builder::new().build())
     .plugin(tauri_plugin_dialog::init())
     .plugin(process_init())
     .plugin(tauri_plugin_store::Builder::default().build())
+    .plugin(protected::plugin())
     .plugin(tauri_plugin_opener::init())
-    .plugin(tauri_plugin_posthog::init(
-      tauri_plugin_posthog::PostHogConfig {
-          api_key: option_env!("NEXT_PUBLIC_POSTHOG_KEY").unwrap_or("").to_string(),
-          api_host: option_env!("NEXT_PUBLIC_POSTHOG_HOST").unwrap_or("https://app.posthog.com").to_string(),
-          options: None,
-      }
-    ))
-    .invoke_handler(tauri::generate_handler![sync_language_tray, update_tray_menu, sync_pro_status])
-    .setup(|app| {
+    .plugin(tauri_plugin_deep_link::init());
+  #[cfg(feature = "updater")]
+  let builder = builder.plugin(tauri_plugin_updater::Builder::new().build());
+  #[cfg(feature = "analytics")]
+  let builder = builder.plugin(analytics::plugin());
+
+  builder
+    .invoke_handler(tauri::generate_handler![
+      sync_language_tray,
+      update_tray_menu,
+      audio::sync_audio_state,
+      audio::get_sound_automation,
+      audio::set_sound_automation,
+      audio::clear_sound_automation,
+      audio::get_event_layers,
+      audio::set_event_layer,
+      audio::list_audio_devices,
+      audio::get_audio_device,
+      audio::set_audio_device,
+      audio::analyze_loudness,
+      audio::get_loudness_manifest,
+      catalog::list_sounds,
+      import::import_sound,
+      import::list_custom_sounds,
+      import::remove_custom_sound,
+      packs::install_sound_pack,
+      packs::list_sound_packs,
+      packs::set_sound_pack_enabled,
+      packs::uninstall_sound_pack,
+      timer::sync_timer_state,
+      license::get_license_status,
+      usage::get_usage,
+      history::get_listening_stats,
+      license::activate_license,
+      license::deactivate_license,
+      license::get_license_activations,
+      license::deactivate_license_device,
+      remote::remote_ready,
+      webhooks::list_webhooks,
+      webhooks::add_webhook,
+      webhooks::remove_webhook,
+      webhooks::set_webhook_enabled,
+      webhooks::send_test_webhook,
+      timer::report_session_event,
+    ])
+    .setup(move |app| {
       let window = app.get_webview_window("main").unwrap();
 
       // Position window at top center of the screen
@@ -269,10 +459,51 @@ pub fn run() {
         let _ = window.set_shadow(false);
       }
 
+      audio::init(app.handle());
+      webhooks::init(app.handle());
+      license::init(app.handle());
+      usage::init(app.handle());
+      history::init(app.handle());
+      #[cfg(unix)]
+      statusbar::start(app.handle());
+      #[cfg(target_os = "linux")]
+      mpris::start(app.handle());
+
+      instance::listen(app.handle());
+      config::apply_settings(app.handle());
+      api::start_if_enabled(app.handle());
+      if let Err(e) = remote::apply(app.handle(), &startup_actions) {
+        eprintln!("zerohz: {}", e);
+      }
+
+      // Windows and Linux pass links on the command line (see `cli`); make
+      // sure the scheme points at this binary even when it wasn't installed
+      // by a package (AppImage, dev builds).
+      #[cfg(any(windows, target_os = "linux"))]
+      if let Err(e) = app.deep_link().register_all() {
+        log::warn!("Failed to register {}:// links: {}", deeplink::SCHEME, e);
+      }
+      // macOS delivers links as events, including the one the app was
+      // launched with.
+      #[cfg(target_os = "macos")]
+      {
+        let handle = app.handle().clone();
+        app.deep_link().on_open_url(move |event| {
+          for url in event.urls() {
+            let result = deeplink::parse(url.as_str()).and_then(|actions| remote::apply(&handle, &actions));
+            if let Err(e) = result {
+              log::warn!("Ignoring link {}: {}", url, e);
+            }
+          }
+        });
+      }
+
       // Get app version from Cargo.toml
       let version = env!("CARGO_PKG_VERSION");
       let version_text = format!("Version {}", version);
 
+      let config = config::get(app.handle());
+
       // Check current autostart status
       let autostart_manager = app.autolaunch();
       let is_enabled = autostart_manager.is_enabled().unwrap_or(false);
@@ -293,8 +524,34 @@ pub fn run() {
       let separator1 = PredefinedMenuItem::separator(app)?;
 
       // === Settings ===
+      let audio_output_item = MenuItemBuilder::new("Audio Output")
+        .build(app)?;
+
+      let sound_packs_item = MenuItemBuilder::new("Sound Packs")
+        .build(app)?;
+
+      let webhooks_item = MenuItemBuilder::new("Webhooks")
+        .build(app)?;
+
+      // Settings the system policy locks are shown but can't be changed.
       let autostart_item = CheckMenuItemBuilder::new("Start at Login")
         .checked(is_enabled)
+        .enabled(!config.is_locked(config::Key::StartAtLogin))
+        .build(app)?;
+
+      let local_api_item = CheckMenuItemBuilder::new("Local API")
+        .checked(api::is_enabled(app.handle()))
+        .enabled(!config.is_locked(config::Key::LocalApi))
+        .build(app)?;
+
+      let shell_hooks_item = CheckMenuItemBuilder::new("Shell Hooks")
+        .checked(hooks::is_enabled(app.handle()))
+        .enabled(!config.is_locked(config::Key::ShellHooks))
+        .build(app)?;
+
+      #[cfg(feature = "analytics")]
+      let share_usage_data_item = CheckMenuItemBuilder::new("Share Anonymous Usage Data")
+        .checked(analytics::is_enabled(app.handle()))
         .build(app)?;
 
       let activate_license_item = MenuItemBuilder::new("Activate License")
@@ -317,8 +574,10 @@ pub fn run() {
         .enabled(false)
         .build(app)?;
 
+      #[cfg(feature = "updater")]
       let check_update_item = MenuItemBuilder::new("Check for Updates")
         .build(app)?;
+      #[cfg(feature = "updater")]
       let update_status_item = MenuItemBuilder::new("Update status")
         .enabled(false)
         .build(app)?;
@@ -330,38 +589,58 @@ pub fn run() {
         .build(app)?;
 
       // Store menu item IDs for use in the closure
+      #[cfg(feature = "updater")]
       let check_update_id = check_update_item.id().clone();
       let autostart_id = autostart_item.id().clone();
+      let local_api_id = local_api_item.id().clone();
+      let shell_hooks_id = shell_hooks_item.id().clone();
+      #[cfg(feature = "analytics")]
+      let share_usage_data_id = share_usage_data_item.id().clone();
       let activate_license_id = activate_license_item.id().clone();
       let show_window_id = show_window_item.id().clone();
       let session_history_id = session_history_item.id().clone();
       let usage_id = usage_item.id().clone();
+      let audio_output_id = audio_output_item.id().clone();
+      let sound_packs_id = sound_packs_item.id().clone();
+      let webhooks_id = webhooks_item.id().clone();
       let quit_id = quit_item.id().clone();
       let lang_ko_id = lang_ko_item.id().clone();
       let lang_en_id = lang_en_item.id().clone();
 
-      let menu = Menu::with_items(
-        app,
-        &[
-          // Primary Actions
-          &show_window_item,
-          &session_history_item,
-          &usage_item,
-          &separator1,
-          // Settings
-          &autostart_item,
-          &activate_license_item,
-          &language_submenu,
-          &separator2,
-          // App Info
-          &update_status_item,
-          &version_item,
-          &check_update_item,
-          &separator3,
-          // Exit
-          &quit_item
-        ],
-      )?;
+      // Items behind cargo features are left out of builds without them.
+      let mut items: Vec<&dyn IsMenuItem<Wry>> = vec![
+        // Primary Actions
+        &show_window_item,
+        &session_history_item,
+        &usage_item,
+        &separator1,
+        // Settings
+        &audio_output_item,
+        &sound_packs_item,
+        &webhooks_item,
+        &autostart_item,
+        &local_api_item,
+        &shell_hooks_item,
+      ];
+      // Left out too when the configuration turns them off.
+      #[cfg(feature = "analytics")]
+      if config.analytics_enabled() {
+        items.push(&share_usage_data_item);
+      }
+      items.extend([&activate_license_item as &dyn IsMenuItem<Wry>, &language_submenu, &separator2]);
+      // App Info
+      #[cfg(feature = "updater")]
+      if config.updates_enabled() {
+        items.push(&update_status_item);
+      }
+      items.push(&version_item);
+      #[cfg(feature = "updater")]
+      if config.updates_enabled() {
+        items.push(&check_update_item);
+      }
+      // Exit
+      items.extend([&separator3 as &dyn IsMenuItem<Wry>, &quit_item]);
+      let menu = Menu::with_items(app, &items)?;
 
       // Register tray menu state for i18n updates
       app.manage(TrayMenuState {
@@ -370,18 +649,44 @@ pub fn run() {
         show_window: show_window_item.clone(),
         session_history: session_history_item.clone(),
         usage: usage_item.clone(),
+        audio_output: audio_output_item.clone(),
+        sound_packs: sound_packs_item.clone(),
+        webhooks: webhooks_item.clone(),
         autostart: autostart_item.clone(),
+        local_api: local_api_item.clone(),
+        shell_hooks: shell_hooks_item.clone(),
+        #[cfg(feature = "analytics")]
+        share_usage_data: share_usage_data_item.clone(),
         activate_license: activate_license_item.clone(),
         language_submenu: language_submenu.clone(),
+        #[cfg(feature = "updater")]
         check_update: check_update_item.clone(),
+        #[cfg(feature = "updater")]
         update_status: update_status_item.clone(),
         version_item: version_item.clone(),
         quit: quit_item.clone(),
         current_lang: Mutex::new("en".to_string()),
-        update_state: Mutex::new(UpdateState::Latest(version.to_string())),
+        license_labels: Mutex::new(LicenseLabels {
+          activate: "Activate License".to_string(),
+          manage: "Manage License".to_string(),
+          manage_offline: "Manage License (offline, {days}d left)".to_string(),
+        }),
+        #[cfg(feature = "analytics")]
+        consent_asked: AtomicBool::new(false),
+        #[cfg(feature = "updater")]
+        update_state: Mutex::new(updater::UpdateState::Latest(version.to_string())),
         version_str: version.to_string(),
       });
 
+      // The tray follows the license, wherever it was changed from.
+      let license_handle = app.handle().clone();
+      app.listen(license::LICENSE_EVENT, move |_| {
+        if let Some(state) = license_handle.try_state::<TrayMenuState<Wry>>() {
+          apply_pro_status(&state, &license::status(&license_handle));
+        }
+      });
+      apply_pro_status(&app.state::<TrayMenuState<Wry>>(), &license::status(app.handle()));
+
       // Load and decode the tray icon PNG
       let tray_icon_bytes = include_bytes!("../icons/tray-icon-template.png");
       let tray_img = image::load_from_memory(tray_icon_bytes)
@@ -395,113 +700,23 @@ pub fn run() {
         .icon_as_template(true)  // macOS template icon for theme adaptation
         .menu(&menu)
         .on_menu_event(move |app, event| {
+          #[cfg(feature = "updater")]
           if event.id == check_update_id {
-            println!("Checking for updates...");
-            let app_handle = app.clone();
-            tauri::async_runtime::spawn(async move {
-              let lang = app_handle
-                .try_state::<TrayMenuState<Wry>>()
-                .and_then(|state| state.current_lang.lock().ok().map(|l| l.clone()))
-                .unwrap_or_else(|| "en".to_string());
-              let locale = get_update_locale(&lang);
-              if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
-                apply_update_state(&state, &locale, UpdateState::Checking);
-              }
-
-              match app_handle.updater() {
-                Ok(updater) => {
-                  match updater.check().await {
-                    Ok(update_response) => {
-                      if let Some(update) = update_response {
-                        println!("Update available: version {}", update.version);
-                        println!("Download URL: {}", update.download_url);
-                        if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
-                          apply_update_state(&state, &locale, UpdateState::Available(update.version.clone()));
-                        }
-                        
-                        // Show dialog to user about available update
-                        use tauri_plugin_dialog::{DialogExt, MessageDialogKind, MessageDialogButtons};
-                        let confirmed = app_handle.dialog()
-                          .message(locale.message_available.replace("{version}", &update.version))
-                          .title(locale.title_available)
-                          .buttons(MessageDialogButtons::OkCancelCustom(
-                            locale.button_install.to_string(),
-                            locale.button_later.to_string()
-                          ))
-                          .blocking_show();
-                        
-                        if confirmed {
-                          // User clicked Install
-                          match update.download_and_install(|chunk_length, content_length| {
-                            if let Some(total) = content_length {
-                              let progress = (chunk_length as f64 / total as f64) * 100.0;
-                              println!("Download progress: {:.1}%", progress);
-                            }
-                          }, || {
-                            println!("Download complete, preparing to install...");
-                          }).await {
-                            Ok(_) => {
-                              println!("Update installed successfully. Restarting app to apply.");
-                              app_handle.dialog()
-                                .message(locale.message_ready)
-                                .title(locale.title_ready)
-                                .kind(MessageDialogKind::Info)
-                                .blocking_show();
-                              app_handle.request_restart();
-                            }
-                            Err(e) => {
-                              println!("Failed to download/install update: {:?}", e);
-                              app_handle.dialog()
-                                .message(locale.message_error_download)
-                                .title(locale.title_error_download)
-                                .kind(MessageDialogKind::Error)
-                                .blocking_show();
-                            }
-                          }
-                        }
-                      } else {
-                        println!("No updates available");
-                        if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
-                          apply_update_state(&state, &locale, UpdateState::Latest(env!("CARGO_PKG_VERSION").to_string()));
-                        }
-                        // Notify user that app is up to date
-                        use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
-                        app_handle.dialog()
-                          .message(locale.message_no_update.replace("{version}", env!("CARGO_PKG_VERSION")))
-                          .title(locale.title_no_update)
-                          .kind(MessageDialogKind::Info)
-                          .blocking_show();
-                      }
-                    }
-                    Err(e) => {
-                      println!("Failed to check for updates: {:?}", e);
-                      if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
-                        apply_update_state(&state, &locale, UpdateState::Failed);
-                      }
-                      use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
-                      app_handle.dialog()
-                        .message(locale.message_error_check)
-                        .title(locale.title_error_check)
-                        .kind(MessageDialogKind::Error)
-                        .blocking_show();
-                    }
-                  }
-                }
-                Err(e) => {
-                  println!("Failed to initialize updater: {:?}", e);
-                  if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
-                    apply_update_state(&state, &locale, UpdateState::Failed);
-                  }
-                  use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
-                  app_handle.dialog()
-                    .message(locale.message_init_error)
-                    .title(locale.title_init_error)
-                    .kind(MessageDialogKind::Error)
-                    .blocking_show();
-                }
-              }
-            });
-          } else if event.id == autostart_id {
+            updater::check_now(app.clone());
+            return;
+          }
+          #[cfg(feature = "analytics")]
+          if event.id == share_usage_data_id {
+            let enabled = !analytics::is_enabled(app);
+            if let Err(e) = analytics::set_consent(app, enabled) {
+              log::warn!("Failed to save analytics consent: {}", e);
+            }
+            if let Some(state) = app.try_state::<TrayMenuState<Wry>>() {
+              let _ = state.share_usage_data.set_checked(analytics::is_enabled(app));
+            }
+            return;
+          }
+          if event.id == autostart_id {
             let autostart_manager = app.autolaunch();
             let is_enabled = autostart_manager.is_enabled().unwrap_or(false);
             
@@ -528,6 +743,32 @@ pub fn run() {
                 }
               }
             }
+          } else if event.id == local_api_id {
+            let enabled = if api::is_enabled(app) {
+              if let Err(e) = api::disable(app) {
+                log::warn!("Failed to disable the local API: {}", e);
+              }
+              api::is_enabled(app)
+            } else {
+              let result = api::enable(app);
+              show_api_result(app, &result);
+              result.is_ok()
+            };
+            if let Some(state) = app.try_state::<TrayMenuState<Wry>>() {
+              let _ = state.local_api.set_checked(enabled);
+            }
+          } else if event.id == shell_hooks_id {
+            if hooks::is_enabled(app) {
+              if let Err(e) = hooks::set_enabled(app, false) {
+                log::warn!("Failed to disable shell hooks: {}", e);
+              }
+            } else {
+              confirm_shell_hooks(app);
+            }
+            // Stays unchecked until the confirmation is accepted.
+            if let Some(state) = app.try_state::<TrayMenuState<Wry>>() {
+              let _ = state.shell_hooks.set_checked(hooks::is_enabled(app));
+            }
           } else if event.id == quit_id {
             app.exit(0);
           } else if event.id == show_window_id {
@@ -540,26 +781,17 @@ pub fn run() {
               }
             }
           } else if event.id == session_history_id {
-            // Show window and emit event to open session history modal
-            if let Some(window) = app.get_webview_window("main") {
-              let _ = window.show();
-              let _ = window.set_focus();
-              let _ = window.emit("open-session-history", ());
-            }
+            remote::open_panel(app, remote::Panel::SessionHistory);
           } else if event.id == activate_license_id {
-            // Show window and emit event to open license input modal
-            if let Some(window) = app.get_webview_window("main") {
-              let _ = window.show();
-              let _ = window.set_focus();
-              let _ = window.emit("open-license-input", ());
-            }
+            remote::open_panel(app, remote::Panel::License);
           } else if event.id == usage_id {
-            // Show window and emit event to open usage modal
-            if let Some(window) = app.get_webview_window("main") {
-              let _ = window.show();
-              let _ = window.set_focus();
-              let _ = window.emit("open-usage", ());
-            }
+            remote::open_panel(app, remote::Panel::Usage);
+          } else if event.id == audio_output_id {
+            remote::open_panel(app, remote::Panel::AudioOutput);
+          } else if event.id == sound_packs_id {
+            remote::open_panel(app, remote::Panel::SoundPacks);
+          } else if event.id == webhooks_id {
+            remote::open_panel(app, remote::Panel::Webhooks);
           } else if event.id == lang_ko_id {
             let _ = app.emit("change-language", "ko");
             // Update menu state
@@ -595,51 +827,22 @@ pub fn run() {
         .build(app)?;
 
       // Initial background update check to surface status in tray without user interaction
-      let app_handle_for_status = app.app_handle();
-      let app_handle_for_status = app_handle_for_status.clone();
-      tauri::async_runtime::spawn(async move {
-        let lang = app_handle_for_status
-          .try_state::<TrayMenuState<Wry>>()
-          .and_then(|state| state.current_lang.lock().ok().map(|l| l.clone()))
-          .unwrap_or_else(|| "en".to_string());
-        let locale = get_update_locale(&lang);
-
-        if let Some(state) = app_handle_for_status.try_state::<TrayMenuState<Wry>>() {
-          apply_update_state(&state, &locale, UpdateState::Checking);
-        }
-
-        match app_handle_for_status.updater() {
-          Ok(updater) => match updater.check().await {
-            Ok(update_response) => {
-              if let Some(update) = update_response {
-                if let Some(state) = app_handle_for_status.try_state::<TrayMenuState<Wry>>() {
-                  apply_update_state(&state, &locale, UpdateState::Available(update.version.clone()));
-                }
-              } else if let Some(state) = app_handle_for_status.try_state::<TrayMenuState<Wry>>() {
-                apply_update_state(&state, &locale, UpdateState::Latest(env!("CARGO_PKG_VERSION").to_string()));
-              }
-            }
-            Err(_) => {
-              if let Some(state) = app_handle_for_status.try_state::<TrayMenuState<Wry>>() {
-                apply_update_state(&state, &locale, UpdateState::Failed);
-              }
-            }
-          },
-          Err(_) => {
-            if let Some(state) = app_handle_for_status.try_state::<TrayMenuState<Wry>>() {
-              apply_update_state(&state, &locale, UpdateState::Failed);
-            }
-          }
-        }
-      });
+      #[cfg(feature = "updater")]
+      if config.updates_enabled() {
+        updater::check_in_background(app.handle().clone());
+      }
 
-      app.handle().plugin(
-        tauri_plugin_log::Builder::default()
-          .level(log::LevelFilter::Info)
-          .build(),
-      )?;
+      config::report(app.handle());
       Ok(())
     })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(context())
+    .expect("error while building tauri application")
+    .run(|app, event| {
+      if let tauri::RunEvent::Exit = event {
+        usage::flush(app);
+        history::flush(app);
+        #[cfg(unix)]
+        statusbar::remove();
+      }
+    });
 }
@@ -0,0 +1,222 @@
+//! Client for the LemonSqueezy License API
+//! (<https://docs.lemonsqueezy.com/api/license-api>).
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.lemonsqueezy.com/v1/licenses";
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Instance {
+  pub id: String,
+  #[serde(default)]
+  pub name: String,
+  #[serde(default)]
+  pub created_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct LicenseKey {
+  /// Missing for unlimited keys.
+  pub activation_limit: Option<u32>,
+  pub activation_usage: u32,
+}
+
+/// Body of all three endpoints, each setting its own flag.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ApiResponse {
+  pub activated: bool,
+  pub valid: bool,
+  pub deactivated: bool,
+  pub error: Option<String>,
+  pub license_key: Option<LicenseKey>,
+  pub instance: Option<Instance>,
+}
+
+impl ApiResponse {
+  pub fn error_or(&self, fallback: &str) -> String {
+    self.error.clone().unwrap_or_else(|| fallback.to_string())
+  }
+}
+
+/// The server couldn't be asked: offline, timed out, rate limited or down.
+/// Unlike an [`ApiResponse`] with an error, this says nothing about the key.
+#[derive(Debug)]
+pub struct Unreachable(pub String);
+
+impl std::fmt::Display for Unreachable {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Couldn't reach the license server: {}", self.0)
+  }
+}
+
+pub struct Client {
+  http: reqwest::Client,
+  base_url: String,
+}
+
+impl Client {
+  /// `base_url` is [`DEFAULT_BASE_URL`] except against a mock server.
+  pub fn new(base_url: &str, user_agent: &str) -> Self {
+    let http = reqwest::Client::builder()
+      .timeout(TIMEOUT)
+      .user_agent(user_agent)
+      .build()
+      .unwrap_or_default();
+    Self {
+      http,
+      base_url: base_url.trim_end_matches('/').to_string(),
+    }
+  }
+
+  pub async fn activate(&self, key: &str, instance_name: &str) -> Result<ApiResponse, Unreachable> {
+    self
+      .post("activate", &[("license_key", key), ("instance_name", instance_name)])
+      .await
+  }
+
+  pub async fn validate(&self, key: &str, instance_id: Option<&str>) -> Result<ApiResponse, Unreachable> {
+    match instance_id {
+      Some(id) => self.post("validate", &[("license_key", key), ("instance_id", id)]).await,
+      None => self.post("validate", &[("license_key", key)]).await,
+    }
+  }
+
+  pub async fn deactivate(&self, key: &str, instance_id: &str) -> Result<ApiResponse, Unreachable> {
+    self
+      .post("deactivate", &[("license_key", key), ("instance_id", instance_id)])
+      .await
+  }
+
+  /// Errors about the key come back as 4xx with a JSON body, which is
+  /// returned like a success.
+  async fn post(&self, endpoint: &str, form: &[(&str, &str)]) -> Result<ApiResponse, Unreachable> {
+    let response = self
+      .http
+      .post(format!("{}/{}", self.base_url, endpoint))
+      .header(reqwest::header::ACCEPT, "application/json")
+      .form(form)
+      .send()
+      .await
+      .map_err(|e| Unreachable(e.to_string()))?;
+    let status = response.status();
+    if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+      return Err(Unreachable(status.to_string()));
+    }
+    let body = response.text().await.map_err(|e| Unreachable(e.to_string()))?;
+    serde_json::from_str(&body).map_err(|e| Unreachable(format!("unexpected response ({}): {}", status, e)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::mock_http::{self, Mock};
+
+  /// A stand-in for the License API. The base URL has a trailing slash,
+  /// which is trimmed.
+  async fn server() -> (Client, Mock) {
+    let mock = Mock::start().await;
+    (Client::new(&mock.url("/v1/licenses/"), "test"), mock)
+  }
+
+  /// Endpoint and form body of the last request.
+  fn last_request(mock: &Mock) -> (String, String) {
+    let request = mock.last_request();
+    let endpoint = request.path.strip_prefix("/v1/licenses/").unwrap();
+    (endpoint.to_string(), request.body)
+  }
+
+  #[tokio::test]
+  async fn activate_sends_the_key_and_reads_the_instance() {
+    let (client, mock) = server().await;
+    mock.reply(
+      200,
+      r#"{"activated":true,"error":null,
+        "license_key":{"activation_limit":3,"activation_usage":1},
+        "instance":{"id":"i-1","name":"Desk","created_at":"2026-10-01T00:00:00.000000Z"}}"#,
+    );
+    let response = client.activate("KEY", "Desk").await.unwrap();
+    assert_eq!(
+      last_request(&mock),
+      ("activate".to_string(), "license_key=KEY&instance_name=Desk".to_string())
+    );
+    assert!(response.activated);
+    assert_eq!(response.instance.unwrap().id, "i-1");
+    let key = response.license_key.unwrap();
+    assert_eq!((key.activation_limit, key.activation_usage), (Some(3), 1));
+  }
+
+  #[tokio::test]
+  async fn validate_sends_the_instance_only_when_there_is_one() {
+    let (client, mock) = server().await;
+    mock.reply(200, r#"{"valid":true,"license_key":{"activation_limit":null}}"#);
+    let response = client.validate("KEY", Some("i-1")).await.unwrap();
+    assert_eq!(last_request(&mock).1, "license_key=KEY&instance_id=i-1");
+    assert!(response.valid);
+    assert_eq!(response.license_key.unwrap().activation_limit, None);
+
+    client.validate("KEY", None).await.unwrap();
+    assert_eq!(
+      last_request(&mock),
+      ("validate".to_string(), "license_key=KEY".to_string())
+    );
+  }
+
+  #[tokio::test]
+  async fn deactivate_sends_the_instance() {
+    let (client, mock) = server().await;
+    mock.reply(200, r#"{"deactivated":true}"#);
+    assert!(client.deactivate("KEY", "i-1").await.unwrap().deactivated);
+    assert_eq!(
+      last_request(&mock),
+      ("deactivate".to_string(), "license_key=KEY&instance_id=i-1".to_string())
+    );
+  }
+
+  #[tokio::test]
+  async fn key_errors_come_back_as_responses() {
+    let (client, mock) = server().await;
+    mock.reply(
+      400,
+      r#"{"activated":false,"error":"This license key has reached the activation limit."}"#,
+    );
+    let response = client.activate("KEY", "Desk").await.unwrap();
+    assert!(!response.activated);
+    assert_eq!(
+      response.error_or("fallback"),
+      "This license key has reached the activation limit."
+    );
+
+    mock.reply(404, r#"{"valid":false}"#);
+    assert_eq!(client.validate("KEY", None).await.unwrap().error_or("fallback"), "fallback");
+  }
+
+  #[tokio::test]
+  async fn outages_and_rate_limits_are_unreachable() {
+    let (client, mock) = server().await;
+    for status in [500, 503, 429] {
+      mock.reply(status, r#"{"error":"busy"}"#);
+      assert!(client.validate("KEY", None).await.is_err(), "{}", status);
+    }
+  }
+
+  #[tokio::test]
+  async fn unexpected_bodies_are_unreachable() {
+    let (client, mock) = server().await;
+    mock.reply(200, "<html>maintenance</html>");
+    let error = client.validate("KEY", None).await.unwrap_err();
+    assert!(error.0.starts_with("unexpected response (200 OK)"), "{}", error);
+  }
+
+  #[tokio::test]
+  async fn a_closed_port_is_unreachable() {
+    let client = Client::new(&format!("http://{}", mock_http::closed_address()), "test");
+    assert!(client.deactivate("KEY", "i-1").await.is_err());
+  }
+}
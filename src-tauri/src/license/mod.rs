@@ -0,0 +1,635 @@
+//! Pro licenses, activated and checked against LemonSqueezy from here
+//! rather than from the webview (see `docs/01-pricing-and-licensing.md`).
+//! Site licenses are signed tokens instead, checked offline by [`token`].
+//!
+//! The key lives in `<app config>/protected/license.json`, a [`protected`]
+//! file the webview can neither read nor replace; the frontend gets a [`LicenseStatus`] through commands and the
+//! [`LICENSE_EVENT`] event. The file also keeps the time of the last
+//! successful check, sealed with [`integrity`], so Pro survives a while
+//! without the server.
+
+mod lemonsqueezy;
+mod token;
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::util::{self, now_millis};
+use crate::{config, integrity, protected};
+use lemonsqueezy::{ApiResponse, Client};
+use token::SiteLicense;
+
+/// Emitted with the new [`LicenseStatus`] whenever it changes.
+pub const LICENSE_EVENT: &str = "license-changed";
+const LICENSE_FILE: &str = "license.json";
+/// Where the frontend used to keep the key (`ProProvider`).
+const LEGACY_STORE_FILE: &str = "pro-settings.json";
+const LEGACY_STORE_KEY: &str = "pro";
+const INTEGRITY_PURPOSE: &str = "license-validation";
+/// See [`grace_period`].
+const DEFAULT_GRACE_DAYS: u64 = 14;
+const GRACE_DAYS: std::ops::RangeInclusive<u64> = 1..=60;
+const REVALIDATE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const OFFLINE_RETRY_INTERVAL: Duration = Duration::from_secs(30 * 60);
+/// A last check further in the future than this means the clock was
+/// turned back, and isn't trusted.
+const MAX_CLOCK_SKEW_MS: u64 = 5 * 60 * 1000;
+
+/// When the last successful check was.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Validation {
+  /// Unix time in milliseconds.
+  at: u64,
+  /// [`integrity::seal`] of the key, instance and `at`.
+  checksum: String,
+}
+
+/// What's saved in [`LICENSE_FILE`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredLicense {
+  key: String,
+  /// LemonSqueezy instance of this device; missing for keys carried over
+  /// from before activation was done here.
+  #[serde(default)]
+  instance_id: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  last_validation: Option<Validation>,
+}
+
+impl StoredLicense {
+  fn validation_data(&self, at: u64) -> Vec<u8> {
+    format!("{}\n{}\n{}", self.key, self.instance_id.as_deref().unwrap_or(""), at).into_bytes()
+  }
+
+  fn mark_validated(&mut self, now: u64) {
+    self.last_validation = Some(Validation {
+      at: now,
+      checksum: integrity::seal(INTEGRITY_PURPOSE, &self.validation_data(now)),
+    });
+  }
+
+  /// When Pro stops working offline, if the last successful check is
+  /// genuine and recent enough.
+  fn offline_until(&self, now: u64, grace: Duration) -> Option<u64> {
+    let validation = self.last_validation.as_ref()?;
+    if !integrity::verify(INTEGRITY_PURPOSE, &self.validation_data(validation.at), &validation.checksum) {
+      log::warn!("Ignoring a cached license check that doesn't match its checksum");
+      return None;
+    }
+    if validation.at > now + MAX_CLOCK_SKEW_MS {
+      return None;
+    }
+    let until = validation.at + grace.as_millis() as u64;
+    (now < until).then_some(until)
+  }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseStatus {
+  pub is_pro: bool,
+  /// Last block of the saved key, for display.
+  pub key_hint: Option<String>,
+  /// Who a site license was issued to.
+  pub licensee: Option<String>,
+  /// The saved key is being checked.
+  pub is_validating: bool,
+  /// Set while Pro holds only because the server couldn't be reached:
+  /// Unix time in milliseconds when the grace period runs out.
+  pub offline_until: Option<u64>,
+}
+
+/// This device's activation of the saved key.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseDevice {
+  /// LemonSqueezy instance ID.
+  pub id: String,
+  pub name: String,
+  pub created_at: Option<String>,
+}
+
+/// How many seats the saved key uses. The License API only counts the
+/// other devices; listing them takes the store's secret API key, which
+/// can't ship in the app.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseActivations {
+  /// Activations in use across all devices.
+  pub activation_usage: u32,
+  /// Missing for unlimited keys.
+  pub activation_limit: Option<u32>,
+  /// Missing for keys carried over from before activation was done here.
+  pub this_device: Option<LicenseDevice>,
+}
+
+pub struct LicenseState {
+  status: Mutex<LicenseStatus>,
+  client: Client,
+  /// Held while the license file is being checked or changed.
+  busy: tokio::sync::Mutex<()>,
+}
+
+/// How long Pro keeps working without reaching the license server, counted
+/// from the last successful check. The system policy can set it
+/// (`license.grace_days`) within [`GRACE_DAYS`].
+fn grace_period<R: Runtime>(app: &AppHandle<R>) -> Duration {
+  let days = config::get(app)
+    .license_grace_days()
+    .unwrap_or(DEFAULT_GRACE_DAYS)
+    .clamp(*GRACE_DAYS.start(), *GRACE_DAYS.end());
+  Duration::from_secs(days * 24 * 60 * 60)
+}
+
+fn api_url() -> String {
+  // Debug builds can be pointed at a mock server.
+  #[cfg(debug_assertions)]
+  if let Ok(url) = std::env::var("ZEROHZ_LICENSE_API_URL") {
+    return url;
+  }
+  lemonsqueezy::DEFAULT_BASE_URL.to_string()
+}
+
+/// LemonSqueezy keys look like `E62A381A-EBD9-4280-97B7-6E47A2AE4532`.
+fn is_key_format(key: &str) -> bool {
+  let groups: Vec<&str> = key.split('-').collect();
+  groups.len() == 5
+    && groups
+      .iter()
+      .zip([8, 4, 4, 4, 12])
+      .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// The last characters of the key, or of a token's signature.
+fn key_hint(key: &str) -> String {
+  let last = key.rsplit(['-', '.']).next().unwrap_or(key);
+  let start = last.len().saturating_sub(4);
+  format!("…{}", last.get(start..).unwrap_or(last))
+}
+
+/// "Janes-MacBook (macOS)", shown next to the activation in LemonSqueezy
+/// and the same every time this machine activates.
+fn instance_name() -> String {
+  let os = match std::env::consts::OS {
+    "macos" => "macOS",
+    "windows" => "Windows",
+    "linux" => "Linux",
+    other => other,
+  };
+  match hostname() {
+    Some(host) => format!("{} ({})", host, os),
+    None => format!("ZeroHz ({})", os),
+  }
+}
+
+fn hostname() -> Option<String> {
+  #[cfg(unix)]
+  let name = {
+    let mut buf = [0u8; 256];
+    // SAFETY: gethostname writes at most buf.len() bytes into buf.
+    if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } != 0 {
+      return None;
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+  };
+  #[cfg(windows)]
+  let name = std::env::var("COMPUTERNAME").ok()?;
+  let name = name.trim().trim_end_matches(".local");
+  (!name.is_empty()).then(|| name.to_string())
+}
+
+fn license_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+  protected::path(app, LICENSE_FILE)
+}
+
+/// Earlier builds kept the file directly in the app config folder, where
+/// the webview could reach it. The key still has to pass the server, and a
+/// cached check can't be forged, so it's moved rather than dropped.
+fn move_from_config_dir<R: Runtime>(app: &AppHandle<R>, path: &Path) {
+  let Ok(old) = app.path().app_config_dir().map(|dir| dir.join(LICENSE_FILE)) else {
+    return;
+  };
+  if !old.exists() || path.exists() {
+    return;
+  }
+  let moved = path
+    .parent()
+    .map_or(Ok(()), std::fs::create_dir_all)
+    .and_then(|()| std::fs::rename(&old, path));
+  if let Err(e) = moved {
+    log::warn!("Failed to move {} to {}: {}", old.display(), path.display(), e);
+  }
+}
+
+fn load(path: &Path) -> Option<StoredLicense> {
+  let bytes = std::fs::read(path).ok()?;
+  match serde_json::from_slice(&bytes) {
+    Ok(license) => Some(license),
+    Err(e) => {
+      log::warn!("Ignoring unreadable {}: {}", path.display(), e);
+      None
+    }
+  }
+}
+
+fn save(path: &Path, license: &StoredLicense) -> Result<(), String> {
+  let json = serde_json::to_vec_pretty(license).map_err(|e| e.to_string())?;
+  util::write_private(path, &json).map_err(|e| format!("Failed to save the license: {}", e))
+}
+
+fn remove(path: &Path) {
+  if let Err(e) = std::fs::remove_file(path) {
+    if e.kind() != std::io::ErrorKind::NotFound {
+      log::warn!("Failed to remove {}: {}", path.display(), e);
+    }
+  }
+}
+
+/// Move a key the frontend saved in `pro-settings.json` into the license
+/// file, and drop it from the store.
+fn migrate_legacy_key<R: Runtime>(app: &AppHandle<R>, path: &Path) {
+  let Ok(store) = app.store(LEGACY_STORE_FILE) else {
+    return;
+  };
+  let Some(mut settings) = store.get(LEGACY_STORE_KEY) else {
+    return;
+  };
+  let Some(key) = settings.as_object_mut().and_then(|s| s.remove("licenseKey")) else {
+    return;
+  };
+  if let Some(key) = key.as_str().filter(|k| !k.is_empty()) {
+    if load(path).is_none() {
+      let license = StoredLicense {
+        key: key.to_string(),
+        instance_id: None,
+        last_validation: None,
+      };
+      if let Err(e) = save(path, &license) {
+        log::warn!("Failed to move the license key out of {}: {}", LEGACY_STORE_FILE, e);
+        return;
+      }
+    }
+  }
+  store.set(LEGACY_STORE_KEY, settings);
+  if let Err(e) = store.save() {
+    log::warn!("Failed to save {}: {}", LEGACY_STORE_FILE, e);
+  }
+}
+
+pub fn status<R: Runtime>(app: &AppHandle<R>) -> LicenseStatus {
+  app
+    .try_state::<LicenseState>()
+    .and_then(|state| state.status.lock().ok().map(|s| s.clone()))
+    .unwrap_or_default()
+}
+
+fn emit<R: Runtime>(app: &AppHandle<R>, status: &LicenseStatus) {
+  if let Err(e) = app.emit(LICENSE_EVENT, status) {
+    log::warn!("Failed to emit {}: {}", LICENSE_EVENT, e);
+  }
+}
+
+/// Returns false if nothing changed, in which case nothing is emitted.
+fn set_status<R: Runtime>(app: &AppHandle<R>, status: LicenseStatus) -> bool {
+  let state = app.state::<LicenseState>();
+  if let Ok(mut current) = state.status.lock() {
+    if *current == status {
+      return false;
+    }
+    *current = status.clone();
+  }
+  emit(app, &status);
+  true
+}
+
+fn free_status() -> LicenseStatus {
+  LicenseStatus::default()
+}
+
+fn pro_status(key: &str) -> LicenseStatus {
+  LicenseStatus {
+    is_pro: true,
+    key_hint: Some(key_hint(key)),
+    ..free_status()
+  }
+}
+
+fn site_status(key: &str, site: SiteLicense) -> LicenseStatus {
+  LicenseStatus {
+    licensee: Some(site.licensee),
+    ..pro_status(key)
+  }
+}
+
+enum Checked {
+  Online,
+  Offline,
+  NoLicense,
+}
+
+/// Check the saved key. A key the server turns down is forgotten. While the
+/// server can't be reached the key is kept, and Pro holds until the grace
+/// period of the last successful check runs out. Site licenses are only
+/// checked for expiry.
+async fn revalidate<R: Runtime>(app: &AppHandle<R>) -> Checked {
+  let state = app.state::<LicenseState>();
+  let _busy = state.busy.lock().await;
+  let Ok(path) = license_path(app) else {
+    return Checked::NoLicense;
+  };
+  let Some(mut license) = load(&path) else {
+    set_status(app, free_status());
+    return Checked::NoLicense;
+  };
+  if token::is_token(&license.key) {
+    return match token::verify(&license.key, now_millis()) {
+      Ok(site) => {
+        set_status(app, site_status(&license.key, site));
+        Checked::Online
+      }
+      Err(e) => {
+        log::warn!("Saved site license is no longer valid: {}", e);
+        remove(&path);
+        set_status(app, free_status());
+        Checked::NoLicense
+      }
+    };
+  }
+
+  match state.client.validate(&license.key, license.instance_id.as_deref()).await {
+    Ok(response) if response.valid => {
+      license.mark_validated(now_millis());
+      if let Err(e) = save(&path, &license) {
+        log::warn!("{}", e);
+      }
+      set_status(app, pro_status(&license.key));
+      Checked::Online
+    }
+    Ok(response) => {
+      log::warn!("Saved license is no longer valid: {}", response.error_or("not valid"));
+      remove(&path);
+      set_status(app, free_status());
+      Checked::NoLicense
+    }
+    Err(e) => {
+      log::warn!("{}", e);
+      let status = match license.offline_until(now_millis(), grace_period(app)) {
+        Some(until) => LicenseStatus {
+          offline_until: Some(until),
+          ..pro_status(&license.key)
+        },
+        None => LicenseStatus {
+          key_hint: Some(key_hint(&license.key)),
+          ..free_status()
+        },
+      };
+      // The days left go down even when the status stays the same.
+      if !set_status(app, status.clone()) {
+        emit(app, &status);
+      }
+      Checked::Offline
+    }
+  }
+}
+
+/// Check the license at startup and every few hours after, more often
+/// while offline.
+async fn keep_validated<R: Runtime>(app: AppHandle<R>) {
+  loop {
+    let wait = match revalidate(&app).await {
+      Checked::Offline => OFFLINE_RETRY_INTERVAL,
+      Checked::Online | Checked::NoLicense => REVALIDATE_INTERVAL,
+    };
+    tokio::time::sleep(wait).await;
+  }
+}
+
+/// Activate the key the configuration preloads, for machines that were
+/// set up with one. Tried again each start until it works.
+fn preload<R: Runtime>(app: &AppHandle<R>) {
+  let Some(key) = config::get(app).license_key() else {
+    return;
+  };
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    if let Err(e) = activate_license(app, key.to_string()).await {
+      log::warn!("Failed to activate the preloaded license: {}", e);
+    }
+  });
+}
+
+/// Take over a key left by older versions and keep the saved one checked
+/// in the background.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+  let user_agent = format!("ZeroHz/{}", app.package_info().version);
+  app.manage(LicenseState {
+    status: Mutex::new(free_status()),
+    client: Client::new(&api_url(), &user_agent),
+    busy: tokio::sync::Mutex::new(()),
+  });
+
+  match license_path(app) {
+    Ok(path) => {
+      move_from_config_dir(app, &path);
+      migrate_legacy_key(app, &path);
+      let saved = load(&path);
+      if saved.is_none() {
+        preload(app);
+      }
+      if let Some(license) = saved.filter(|l| !token::is_token(&l.key)) {
+        // Pro from the start if it was checked recently; the server has
+        // the last word once it answers.
+        set_status(
+          app,
+          LicenseStatus {
+            is_pro: license.offline_until(now_millis(), grace_period(app)).is_some(),
+            key_hint: Some(key_hint(&license.key)),
+            is_validating: true,
+            ..free_status()
+          },
+        );
+      }
+    }
+    Err(e) => log::warn!("No place for the license file: {}", e),
+  }
+  tauri::async_runtime::spawn(keep_validated(app.clone()));
+}
+
+#[tauri::command]
+pub fn get_license_status<R: Runtime>(app: AppHandle<R>) -> LicenseStatus {
+  status(&app)
+}
+
+/// Release `license`'s activation on this device, if it has one. Fails
+/// only if the server can't be reached.
+async fn release(client: &Client, license: &StoredLicense) -> Result<(), String> {
+  // Site licenses have no activation to release.
+  let Some(instance_id) = &license.instance_id else {
+    return Ok(());
+  };
+  let response = client
+    .deactivate(&license.key, instance_id)
+    .await
+    .map_err(|e| e.to_string())?;
+  if !response.deactivated {
+    // Already released elsewhere, or the key is gone; nothing to keep.
+    log::warn!("License deactivation refused: {}", response.error_or("unknown error"));
+  }
+  Ok(())
+}
+
+/// Release the key that was saved before the one just activated. The new
+/// one stays either way; a seat left taken can be freed from the account.
+async fn release_replaced(client: &Client, replaced: Option<StoredLicense>) {
+  let Some(replaced) = replaced else {
+    return;
+  };
+  if let Err(e) = release(client, &replaced).await {
+    log::warn!("Failed to release the previous license on this device: {}", e);
+  }
+}
+
+/// Activate `key` on this device and save it, then release the activation
+/// of the key saved before so it doesn't keep a seat. Until the new key is
+/// saved the old one is left alone, so a failed activation costs nothing.
+/// Site license tokens are checked here and saved without asking
+/// LemonSqueezy.
+#[tauri::command]
+pub async fn activate_license<R: Runtime>(app: AppHandle<R>, key: String) -> Result<LicenseStatus, String> {
+  let key = key.trim().to_string();
+  let path = license_path(&app)?;
+  let state = app.state::<LicenseState>();
+  let _busy = state.busy.lock().await;
+  let replaced = load(&path);
+  // Activating it again would take a second seat, or fail at the limit.
+  if replaced.as_ref().is_some_and(|license| license.key == key) {
+    return Ok(status(&app));
+  }
+  if token::is_token(&key) {
+    let site = token::verify(&key, now_millis())?;
+    log::info!("Activating the site license for {} ({} seats)", site.licensee, site.seats);
+    let license = StoredLicense {
+      key: key.clone(),
+      instance_id: None,
+      last_validation: None,
+    };
+    save(&path, &license)?;
+    let status = site_status(&key, site);
+    set_status(&app, status.clone());
+    release_replaced(&state.client, replaced).await;
+    return Ok(status);
+  }
+
+  if !is_key_format(&key) {
+    return Err("Invalid license key".to_string());
+  }
+  let response: ApiResponse = state
+    .client
+    .activate(&key, &instance_name())
+    .await
+    .map_err(|e| e.to_string())?;
+  if !response.activated {
+    return Err(response.error_or("Invalid license key"));
+  }
+
+  let mut license = StoredLicense {
+    key: key.clone(),
+    instance_id: response.instance.map(|i| i.id),
+    last_validation: None,
+  };
+  license.mark_validated(now_millis());
+  if let Err(e) = save(&path, &license) {
+    // Don't hold a seat for a key that wasn't kept.
+    if let Err(release_error) = release(&state.client, &license).await {
+      log::warn!("Failed to release the unsaved activation: {}", release_error);
+    }
+    return Err(e);
+  }
+  let status = pro_status(&key);
+  set_status(&app, status.clone());
+  release_replaced(&state.client, replaced).await;
+  Ok(status)
+}
+
+/// Release this device's activation and forget the key. Fails, keeping
+/// the key, only if the server can't be reached.
+#[tauri::command]
+pub async fn deactivate_license<R: Runtime>(app: AppHandle<R>) -> Result<LicenseStatus, String> {
+  let path = license_path(&app)?;
+  let state = app.state::<LicenseState>();
+  let _busy = state.busy.lock().await;
+  if let Some(license) = load(&path) {
+    release(&state.client, &license).await?;
+    remove(&path);
+  }
+  set_status(&app, free_status());
+  Ok(free_status())
+}
+
+/// Seats the saved key uses, and this device's activation.
+#[tauri::command]
+pub async fn get_license_activations<R: Runtime>(app: AppHandle<R>) -> Result<LicenseActivations, String> {
+  let path = license_path(&app)?;
+  let state = app.state::<LicenseState>();
+  let _busy = state.busy.lock().await;
+  let license = load(&path).ok_or("No license is active")?;
+  if token::is_token(&license.key) {
+    return Err("Site licenses aren't activated per device".to_string());
+  }
+  let response = state
+    .client
+    .validate(&license.key, license.instance_id.as_deref())
+    .await
+    .map_err(|e| e.to_string())?;
+  if !response.valid {
+    return Err(response.error_or("The license is no longer valid"));
+  }
+  let usage = response.license_key.unwrap_or_default();
+  let this_device = response
+    .instance
+    .filter(|instance| license.instance_id.as_deref() == Some(instance.id.as_str()))
+    .map(|instance| LicenseDevice {
+      id: instance.id,
+      name: instance.name,
+      created_at: instance.created_at,
+    });
+  Ok(LicenseActivations {
+    activation_usage: usage.activation_usage,
+    activation_limit: usage.activation_limit,
+    this_device,
+  })
+}
+
+/// Release another device's activation of the saved key, to free its seat.
+/// This device's goes with [`deactivate_license`].
+#[tauri::command]
+pub async fn deactivate_license_device<R: Runtime>(app: AppHandle<R>, instance_id: String) -> Result<(), String> {
+  let instance_id = instance_id.trim();
+  let path = license_path(&app)?;
+  let state = app.state::<LicenseState>();
+  let _busy = state.busy.lock().await;
+  let license = load(&path).ok_or("No license is active")?;
+  if token::is_token(&license.key) {
+    return Err("Site licenses aren't activated per device".to_string());
+  }
+  if license.instance_id.as_deref() == Some(instance_id) {
+    return Err("That's this device; remove the license instead".to_string());
+  }
+  let response = state
+    .client
+    .deactivate(&license.key, instance_id)
+    .await
+    .map_err(|e| e.to_string())?;
+  if !response.deactivated {
+    return Err(response.error_or("Couldn't deactivate that device"));
+  }
+  Ok(())
+}
@@ -0,0 +1,191 @@
+//! Site licenses: signed tokens for teams whose machines can't each
+//! activate with LemonSqueezy. A token is checked here against an
+//! embedded public key, with no server involved.
+//!
+//! A token is `zerohz1.<payload>.<signature>`, both parts base64url
+//! without padding. The payload is JSON ([`SiteLicense`]); the signature is
+//! ed25519 over `zerohz1.<payload>`.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::Deserialize;
+
+pub const PREFIX: &str = "zerohz1.";
+/// What a token must list in `features` to unlock Pro.
+const PRO_FEATURE: &str = "pro";
+/// Hex. Builds can replace it with `ZEROHZ_LICENSE_PUBLIC_KEY`.
+const PUBLIC_KEY: &str = "a42344aab14bf88155c3a5abec0e27ceb09137ffd0a57ccf3ef26bec29eab0df";
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteLicense {
+  pub licensee: String,
+  /// Machines the licensee paid for. Not enforced: nothing is counted.
+  pub seats: u32,
+  /// Unix time in milliseconds; never expires if missing.
+  #[serde(default)]
+  pub expires_at: Option<u64>,
+  #[serde(default)]
+  pub features: Vec<String>,
+}
+
+pub fn is_token(key: &str) -> bool {
+  key.starts_with(PREFIX)
+}
+
+fn public_key() -> Result<VerifyingKey, String> {
+  // Debug builds can check tokens signed with a test key.
+  #[cfg(debug_assertions)]
+  if let Ok(hex) = std::env::var("ZEROHZ_LICENSE_PUBLIC_KEY") {
+    return parse_public_key(&hex);
+  }
+  parse_public_key(option_env!("ZEROHZ_LICENSE_PUBLIC_KEY").unwrap_or(PUBLIC_KEY))
+}
+
+fn parse_public_key(hex: &str) -> Result<VerifyingKey, String> {
+  let hex = hex.trim();
+  let mut bytes = [0u8; 32];
+  if hex.len() != bytes.len() * 2 {
+    return Err("The site license public key isn't 32 bytes of hex".to_string());
+  }
+  for (i, byte) in bytes.iter_mut().enumerate() {
+    *byte = hex
+      .get(i * 2..i * 2 + 2)
+      .and_then(|b| u8::from_str_radix(b, 16).ok())
+      .ok_or("The site license public key isn't hex")?;
+  }
+  VerifyingKey::from_bytes(&bytes).map_err(|e| format!("Bad site license public key: {}", e))
+}
+
+/// The license in `token`, if it's signed by us, unexpired and includes
+/// Pro. Errors are shown to the user.
+pub fn verify(token: &str, now: u64) -> Result<SiteLicense, String> {
+  verify_with(&public_key()?, token, now)
+}
+
+fn verify_with(key: &VerifyingKey, token: &str, now: u64) -> Result<SiteLicense, String> {
+  let invalid = || "Invalid license key".to_string();
+  let signed = token.rsplit_once('.').filter(|(signed, _)| is_token(signed));
+  let Some((signed, signature)) = signed else {
+    return Err(invalid());
+  };
+  let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| invalid())?;
+  let signature = Signature::from_slice(&signature).map_err(|_| invalid())?;
+  key
+    .verify_strict(signed.as_bytes(), &signature)
+    .map_err(|_| "This site license isn't signed for ZeroHz".to_string())?;
+
+  let payload = URL_SAFE_NO_PAD
+    .decode(&signed[PREFIX.len()..])
+    .map_err(|_| invalid())?;
+  let license: SiteLicense =
+    serde_json::from_slice(&payload).map_err(|e| format!("Unreadable site license: {}", e))?;
+  if license.expires_at.is_some_and(|at| now >= at) {
+    return Err(format!("The site license for {} has expired", license.licensee));
+  }
+  if !license.features.iter().any(|f| f == PRO_FEATURE) {
+    return Err(format!("The site license for {} doesn't include Pro", license.licensee));
+  }
+  Ok(license)
+}
+
+#[cfg(test)]
+mod tests {
+  use ed25519_dalek::{Signer, SigningKey};
+
+  use super::*;
+
+  const NOW: u64 = 1_770_000_000_000;
+
+  fn signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[7; 32])
+  }
+
+  fn sign(key: &SigningKey, payload: &str) -> String {
+    let signed = format!("{}{}", PREFIX, URL_SAFE_NO_PAD.encode(payload));
+    let signature = key.sign(signed.as_bytes());
+    format!("{}.{}", signed, URL_SAFE_NO_PAD.encode(signature.to_bytes()))
+  }
+
+  fn check(token: &str) -> Result<SiteLicense, String> {
+    verify_with(&signing_key().verifying_key(), token, NOW)
+  }
+
+  const PAYLOAD: &str = r#"{"licensee":"Acme","seats":20,"features":["pro"]}"#;
+
+  #[test]
+  fn embedded_key_parses() {
+    parse_public_key(PUBLIC_KEY).unwrap();
+    assert!(parse_public_key("abcd").is_err());
+    assert!(parse_public_key(&"zz".repeat(32)).is_err());
+  }
+
+  #[test]
+  fn tokens_are_told_apart_by_their_prefix() {
+    assert!(is_token(&sign(&signing_key(), PAYLOAD)));
+    assert!(!is_token("38b1460a-5104-4067-a91d-77b872934d51"));
+    assert!(!is_token("zerohz1"));
+  }
+
+  #[test]
+  fn a_signed_token_unlocks_pro() {
+    let license = check(&sign(&signing_key(), PAYLOAD)).unwrap();
+    assert_eq!(license.licensee, "Acme");
+    assert_eq!(license.seats, 20);
+    assert_eq!(license.expires_at, None);
+
+    let until_later = r#"{"licensee":"Acme","seats":1,"expiresAt":1770000000001,"features":["pro"]}"#;
+    assert!(check(&sign(&signing_key(), until_later)).is_ok());
+  }
+
+  #[test]
+  fn a_tampered_payload_fails_the_signature() {
+    let token = sign(&signing_key(), PAYLOAD);
+    let (_, signature) = token.rsplit_once('.').unwrap();
+    let forged = r#"{"licensee":"Acme","seats":2000,"features":["pro"]}"#;
+    let tampered = format!("{}{}.{}", PREFIX, URL_SAFE_NO_PAD.encode(forged), signature);
+    let error = check(&tampered).unwrap_err();
+    assert!(error.contains("isn't signed"), "{}", error);
+  }
+
+  #[test]
+  fn other_keys_and_bad_signatures_are_refused() {
+    let other = SigningKey::from_bytes(&[8; 32]);
+    assert!(check(&sign(&other, PAYLOAD)).unwrap_err().contains("isn't signed"));
+
+    let token = sign(&signing_key(), PAYLOAD);
+    let (signed, _) = token.rsplit_once('.').unwrap();
+    let zeros = format!("{}.{}", signed, URL_SAFE_NO_PAD.encode([0u8; 64]));
+    assert!(check(&zeros).is_err());
+    let short = format!("{}.{}", signed, URL_SAFE_NO_PAD.encode([1u8; 10]));
+    assert_eq!(check(&short).unwrap_err(), "Invalid license key");
+    assert_eq!(check(&format!("{}.***", signed)).unwrap_err(), "Invalid license key");
+  }
+
+  #[test]
+  fn expired_tokens_and_ones_without_pro_are_refused() {
+    let expired = r#"{"licensee":"Acme","seats":1,"expiresAt":1770000000000,"features":["pro"]}"#;
+    assert!(check(&sign(&signing_key(), expired)).unwrap_err().contains("expired"));
+    let no_pro = r#"{"licensee":"Acme","seats":1,"features":["beta"]}"#;
+    assert!(check(&sign(&signing_key(), no_pro)).unwrap_err().contains("doesn't include Pro"));
+  }
+
+  #[test]
+  fn malformed_tokens_are_invalid() {
+    let token = sign(&signing_key(), PAYLOAD);
+    let (signed, signature) = token.rsplit_once('.').unwrap();
+    let payload = &signed[PREFIX.len()..];
+    let malformed = [
+      String::new(),
+      "zerohz1".to_string(),
+      format!("zerohz1.{}", payload),
+      format!("zerohz2.{}.{}", payload, signature),
+      format!("{}.{}", payload, signature),
+      format!("{}.extra.{}", signed, signature),
+    ];
+    for token in malformed {
+      assert!(check(&token).is_err(), "{}", token);
+    }
+  }
+}
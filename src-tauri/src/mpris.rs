@@ -0,0 +1,432 @@
+//! MPRIS2 server, so desktop media keys and shell widgets (GNOME, KDE,
+//! playerctl, waybar) can see and control ZeroHz on Linux.
+//!
+//! The D-Bus side only knows about [`PlayerControl`] and [`PlaybackState`],
+//! so it can be served on any connection, including a private bus in tests.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager, Runtime};
+use zbus::connection::{self, Connection};
+use zbus::interface;
+use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+
+use crate::audio::playback::{self, PlaybackCommand, PlaybackState, PlaybackStatus};
+use crate::audio::AudioState;
+use crate::scenes;
+
+pub const BUS_NAME: &str = "org.mpris.MediaPlayer2.zerohz";
+pub const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+const NO_TRACK: &str = "/org/mpris/MediaPlayer2/TrackList/NoTrack";
+
+/// Where incoming MPRIS calls go.
+pub trait PlayerControl: Send + Sync + 'static {
+  fn command(&self, command: PlaybackCommand);
+  fn set_volume(&self, volume: f64);
+  fn raise(&self);
+  fn quit(&self);
+}
+
+/// What the player currently exposes: playback state plus the scene title
+/// (labels of the active sounds).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+  pub state: PlaybackState,
+  pub title: String,
+}
+
+impl Snapshot {
+  /// Object path identifying the scene, one segment per active sound.
+  fn track_id(&self) -> String {
+    if self.state.active_sounds.is_empty() {
+      return NO_TRACK.to_string();
+    }
+    let segments: Vec<String> = self
+      .state
+      .active_sounds
+      .iter()
+      .map(|id| id.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect())
+      .collect();
+    format!("/com/zerohz/scene/{}", segments.join("/"))
+  }
+}
+
+struct Root {
+  control: Arc<dyn PlayerControl>,
+}
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl Root {
+  fn raise(&self) {
+    self.control.raise();
+  }
+
+  fn quit(&self) {
+    self.control.quit();
+  }
+
+  #[zbus(property)]
+  fn can_quit(&self) -> bool {
+    true
+  }
+
+  #[zbus(property)]
+  fn can_raise(&self) -> bool {
+    true
+  }
+
+  #[zbus(property)]
+  fn has_track_list(&self) -> bool {
+    false
+  }
+
+  #[zbus(property)]
+  fn identity(&self) -> String {
+    "ZeroHz".to_string()
+  }
+
+  #[zbus(property)]
+  fn desktop_entry(&self) -> String {
+    "ZeroHz".to_string()
+  }
+
+  #[zbus(property)]
+  fn supported_uri_schemes(&self) -> Vec<String> {
+    Vec::new()
+  }
+
+  #[zbus(property)]
+  fn supported_mime_types(&self) -> Vec<String> {
+    Vec::new()
+  }
+}
+
+struct Player {
+  control: Arc<dyn PlayerControl>,
+  snapshot: Snapshot,
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl Player {
+  fn play(&self) {
+    self.control.command(PlaybackCommand::Play);
+  }
+
+  fn pause(&self) {
+    self.control.command(PlaybackCommand::Pause);
+  }
+
+  fn play_pause(&self) {
+    self.control.command(PlaybackCommand::PlayPause);
+  }
+
+  fn stop(&self) {
+    self.control.command(PlaybackCommand::Stop);
+  }
+
+  // Ambient loops have no tracks to skip or positions to seek to.
+  fn next(&self) {}
+
+  fn previous(&self) {}
+
+  fn seek(&self, _offset: i64) {}
+
+  fn set_position(&self, _track_id: ObjectPath<'_>, _position: i64) {}
+
+  fn open_uri(&self, _uri: &str) {}
+
+  #[zbus(property)]
+  fn playback_status(&self) -> String {
+    match self.snapshot.state.status() {
+      PlaybackStatus::Playing => "Playing",
+      PlaybackStatus::Paused => "Paused",
+      PlaybackStatus::Stopped => "Stopped",
+    }
+    .to_string()
+  }
+
+  #[zbus(property)]
+  fn metadata(&self) -> HashMap<String, OwnedValue> {
+    let mut metadata = HashMap::new();
+    let mut insert = |key: &str, value: Value<'_>| {
+      if let Ok(value) = OwnedValue::try_from(value) {
+        metadata.insert(key.to_string(), value);
+      }
+    };
+    if let Ok(path) = ObjectPath::try_from(self.snapshot.track_id()) {
+      insert("mpris:trackid", Value::from(path));
+    }
+    if !self.snapshot.state.active_sounds.is_empty() {
+      insert("xesam:title", Value::from(self.snapshot.title.clone()));
+      insert("xesam:artist", Value::from(vec!["ZeroHz".to_string()]));
+    }
+    metadata
+  }
+
+  #[zbus(property)]
+  fn volume(&self) -> f64 {
+    self.snapshot.state.volume as f64
+  }
+
+  #[zbus(property)]
+  fn set_volume(&mut self, volume: f64) {
+    self.control.set_volume(volume.clamp(0.0, 1.0));
+  }
+
+  #[zbus(property)]
+  fn position(&self) -> i64 {
+    0
+  }
+
+  #[zbus(property)]
+  fn rate(&self) -> f64 {
+    1.0
+  }
+
+  #[zbus(property)]
+  fn set_rate(&mut self, _rate: f64) {}
+
+  #[zbus(property)]
+  fn minimum_rate(&self) -> f64 {
+    1.0
+  }
+
+  #[zbus(property)]
+  fn maximum_rate(&self) -> f64 {
+    1.0
+  }
+
+  #[zbus(property)]
+  fn can_go_next(&self) -> bool {
+    false
+  }
+
+  #[zbus(property)]
+  fn can_go_previous(&self) -> bool {
+    false
+  }
+
+  /// Play resumes a paused scene; with nothing selected there's nothing to
+  /// start.
+  #[zbus(property)]
+  fn can_play(&self) -> bool {
+    !self.snapshot.state.active_sounds.is_empty()
+  }
+
+  #[zbus(property)]
+  fn can_pause(&self) -> bool {
+    !self.snapshot.state.active_sounds.is_empty()
+  }
+
+  #[zbus(property)]
+  fn can_seek(&self) -> bool {
+    false
+  }
+
+  #[zbus(property)]
+  fn can_control(&self) -> bool {
+    true
+  }
+}
+
+pub struct MprisServer {
+  connection: Connection,
+}
+
+impl MprisServer {
+  /// Register the player on the connection `builder` makes. The caller
+  /// asks for [`BUS_NAME`], which a peer-to-peer connection can't.
+  pub async fn serve(builder: connection::Builder<'_>, control: impl PlayerControl) -> zbus::Result<Self> {
+    let control: Arc<dyn PlayerControl> = Arc::new(control);
+    let connection = builder
+      .serve_at(OBJECT_PATH, Root { control: control.clone() })?
+      .serve_at(
+        OBJECT_PATH,
+        Player {
+          control,
+          snapshot: Snapshot::default(),
+        },
+      )?
+      .build()
+      .await?;
+    Ok(Self { connection })
+  }
+
+  /// Publish a new snapshot, emitting `PropertiesChanged` for what changed.
+  pub async fn update(&self, snapshot: Snapshot) -> zbus::Result<()> {
+    let player = self
+      .connection
+      .object_server()
+      .interface::<_, Player>(OBJECT_PATH)
+      .await?;
+    let mut iface = player.get_mut().await;
+    let previous = std::mem::replace(&mut iface.snapshot, snapshot);
+    let current = &iface.snapshot;
+    let emitter = player.signal_emitter();
+
+    if previous.state.status() != current.state.status() {
+      iface.playback_status_changed(emitter).await?;
+    }
+    if previous.state.active_sounds != current.state.active_sounds || previous.title != current.title {
+      iface.metadata_changed(emitter).await?;
+    }
+    if previous.state.active_sounds.is_empty() != current.state.active_sounds.is_empty() {
+      iface.can_play_changed(emitter).await?;
+      iface.can_pause_changed(emitter).await?;
+    }
+    if previous.state.volume != current.state.volume {
+      iface.volume_changed(emitter).await?;
+    }
+    Ok(())
+  }
+}
+
+struct AppControl<R: Runtime>(AppHandle<R>);
+
+impl<R: Runtime> PlayerControl for AppControl<R> {
+  fn command(&self, command: PlaybackCommand) {
+    playback::send_command(&self.0, command);
+  }
+
+  fn set_volume(&self, volume: f64) {
+    if let Err(e) = crate::audio::set_master_volume(&self.0, volume as f32) {
+      log::warn!("Failed to set volume from MPRIS: {}", e);
+    }
+  }
+
+  fn raise(&self) {
+    if let Some(window) = self.0.get_webview_window("main") {
+      let _ = window.show();
+      let _ = window.set_focus();
+    }
+  }
+
+  fn quit(&self) {
+    self.0.exit(0);
+  }
+}
+
+/// Serve MPRIS on the session bus and keep it in sync with playback.
+/// Failing to reach the bus (no desktop session) only logs.
+pub fn start<R: Runtime>(app: &AppHandle<R>) {
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    let server = match connection::Builder::session().and_then(|b| b.name(BUS_NAME)) {
+      Ok(builder) => MprisServer::serve(builder, AppControl(app.clone())).await,
+      Err(e) => Err(e),
+    };
+    let server = match server {
+      Ok(server) => server,
+      Err(e) => {
+        log::warn!("MPRIS unavailable: {}", e);
+        return;
+      }
+    };
+
+    let mut playback = app.state::<AudioState>().subscribe_playback();
+    loop {
+      let state = playback.borrow_and_update().clone();
+      let snapshot = Snapshot {
+        title: scenes::title(&app, &state.active_sounds),
+        state,
+      };
+      if let Err(e) = server.update(snapshot).await {
+        log::warn!("Failed to update MPRIS state: {}", e);
+      }
+      if playback.changed().await.is_err() {
+        return;
+      }
+    }
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Mutex;
+
+  use zbus::proxy::CacheProperties;
+
+  use super::*;
+
+  #[derive(Clone, Default)]
+  struct FakeControl {
+    commands: Arc<Mutex<Vec<PlaybackCommand>>>,
+  }
+
+  impl PlayerControl for FakeControl {
+    fn command(&self, command: PlaybackCommand) {
+      self.commands.lock().unwrap().push(command);
+    }
+
+    fn set_volume(&self, _volume: f64) {}
+
+    fn raise(&self) {}
+
+    fn quit(&self) {}
+  }
+
+  /// The server and a client talking over a private socket pair instead
+  /// of the session bus.
+  async fn connect(control: FakeControl) -> (MprisServer, Connection) {
+    let (server_end, client_end) = tokio::net::UnixStream::pair().unwrap();
+    let server = connection::Builder::unix_stream(server_end)
+      .server(zbus::Guid::generate())
+      .unwrap()
+      .p2p();
+    let client = connection::Builder::unix_stream(client_end).p2p().build();
+    let (server, client) = tokio::join!(MprisServer::serve(server, control), client);
+    (server.unwrap(), client.unwrap())
+  }
+
+  async fn player(client: &Connection) -> zbus::Proxy<'static> {
+    zbus::proxy::Builder::new(client)
+      .destination(BUS_NAME)
+      .unwrap()
+      .path(OBJECT_PATH)
+      .unwrap()
+      .interface("org.mpris.MediaPlayer2.Player")
+      .unwrap()
+      .cache_properties(CacheProperties::No)
+      .build()
+      .await
+      .unwrap()
+  }
+
+  #[tokio::test]
+  async fn playback_status_follows_the_snapshot() {
+    let (server, client) = connect(FakeControl::default()).await;
+    let player = player(&client).await;
+    let status = || async { player.get_property::<String>("PlaybackStatus").await.unwrap() };
+    assert_eq!(status().await, "Stopped");
+
+    let mut snapshot = Snapshot {
+      state: PlaybackState {
+        active_sounds: vec!["rain".to_string()],
+        ..PlaybackState::default()
+      },
+      title: "Rain".to_string(),
+    };
+    server.update(snapshot.clone()).await.unwrap();
+    assert_eq!(status().await, "Playing");
+    assert!(player.get_property::<bool>("CanPause").await.unwrap());
+
+    snapshot.state.muted = true;
+    server.update(snapshot).await.unwrap();
+    assert_eq!(status().await, "Paused");
+  }
+
+  #[tokio::test]
+  async fn play_and_pause_calls_reach_the_control() {
+    let control = FakeControl::default();
+    let (_server, client) = connect(control.clone()).await;
+    let player = player(&client).await;
+    for method in ["Play", "Pause", "PlayPause"] {
+      player.call_method(method, &()).await.unwrap();
+    }
+    assert_eq!(
+      *control.commands.lock().unwrap(),
+      vec![PlaybackCommand::Play, PlaybackCommand::Pause, PlaybackCommand::PlayPause]
+    );
+  }
+}
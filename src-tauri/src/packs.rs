@@ -0,0 +1,656 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tauri_plugin_store::StoreExt;
+
+use crate::audio::decoder;
+use crate::audio::mixer::LoopRegion;
+use crate::catalog::{self, Category};
+use crate::import::{SOUNDS_STORE_FILE, SUPPORTED_EXTENSIONS};
+use crate::util;
+
+const PACKS_STORE_KEY: &str = "packs";
+/// Installed packs are unpacked to `<app data>/packs/<pack id>/`.
+const PACKS_DIR: &str = "packs";
+pub const MANIFEST_FILE: &str = "manifest.json";
+const SCHEMA_VERSION: u32 = 1;
+
+const MAX_ID_LEN: usize = 32;
+const MAX_LABEL_LEN: usize = 40;
+const MAX_SOUNDS_PER_PACK: usize = 64;
+const MAX_ARCHIVE_ENTRIES: usize = 1024;
+const MAX_MANIFEST_SIZE: u64 = 1024 * 1024;
+/// Limit on what a pack may unpack to, so a zip bomb can't fill the disk.
+/// Tests get a smaller one.
+const MAX_UNPACKED_SIZE: u64 = if cfg!(test) { 64 * 1024 } else { 1024 * 1024 * 1024 };
+
+/// `manifest.json` at the root of a sound pack archive.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackManifest {
+  pub schema_version: u32,
+  pub id: String,
+  pub name: String,
+  pub version: String,
+  #[serde(default)]
+  pub author: Option<String>,
+  #[serde(default)]
+  pub description: Option<String>,
+  /// License of the pack as a whole (SPDX identifier or free text).
+  pub license: String,
+  pub sounds: Vec<PackSound>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackSound {
+  /// Unique within the pack; the app-wide id is `<pack id>.<sound id>`.
+  pub id: String,
+  pub label: String,
+  /// Path of the audio file inside the archive.
+  pub file: String,
+  /// Lucide icon name (kebab-case); unknown names fall back to a generic icon.
+  #[serde(default)]
+  pub icon: Option<String>,
+  #[serde(default)]
+  pub category: Option<String>,
+  #[serde(default, rename = "loop")]
+  pub loop_region: Option<LoopRegion>,
+  /// Overrides the pack license for this sound.
+  #[serde(default)]
+  pub license: Option<String>,
+  #[serde(default)]
+  pub attribution: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledPack {
+  pub manifest: PackManifest,
+  pub enabled: bool,
+  /// Unix time in milliseconds.
+  pub installed_at: u64,
+}
+
+/// Lowercase letters, digits and dashes, starting with a letter or digit.
+/// Dots are reserved as the pack/sound separator.
+fn is_valid_id(id: &str) -> bool {
+  !id.is_empty()
+    && id.len() <= MAX_ID_LEN
+    && id.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+    && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// A relative path that stays inside the archive root.
+fn is_safe_path(path: &str) -> bool {
+  !path.is_empty()
+    && !path.contains('\\')
+    && Path::new(path).components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+fn require_text(field: &str, value: &str, max_len: usize) -> Result<(), String> {
+  let len = value.trim().chars().count();
+  if len == 0 {
+    return Err(format!("\"{}\" must not be empty", field));
+  }
+  if len > max_len {
+    return Err(format!("\"{}\" is longer than {} characters", field, max_len));
+  }
+  Ok(())
+}
+
+impl PackManifest {
+  pub fn validate(&self) -> Result<(), String> {
+    if self.schema_version != SCHEMA_VERSION {
+      return Err(format!(
+        "Unsupported schema version {} (expected {})",
+        self.schema_version, SCHEMA_VERSION
+      ));
+    }
+    if !is_valid_id(&self.id) {
+      return Err(format!("Invalid pack id \"{}\"", self.id));
+    }
+    require_text("name", &self.name, 60)?;
+    require_text("version", &self.version, 32)?;
+    require_text("license", &self.license, 200)?;
+    if self.sounds.is_empty() {
+      return Err("Pack has no sounds".to_string());
+    }
+    if self.sounds.len() > MAX_SOUNDS_PER_PACK {
+      return Err(format!("Pack has more than {} sounds", MAX_SOUNDS_PER_PACK));
+    }
+
+    let mut ids = HashSet::new();
+    for sound in &self.sounds {
+      let context = |e: String| format!("Sound \"{}\": {}", sound.id, e);
+      if !is_valid_id(&sound.id) {
+        return Err(format!("Invalid sound id \"{}\"", sound.id));
+      }
+      if !ids.insert(sound.id.as_str()) {
+        return Err(format!("Duplicate sound id \"{}\"", sound.id));
+      }
+      require_text("label", &sound.label, MAX_LABEL_LEN).map_err(context)?;
+      if !is_safe_path(&sound.file) {
+        return Err(context(format!("invalid file path \"{}\"", sound.file)));
+      }
+      let ext = Path::new(&sound.file)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+      if !SUPPORTED_EXTENSIONS.contains(&ext.as_str()) {
+        return Err(context(format!("unsupported file type \"{}\"", sound.file)));
+      }
+      if let Some(icon) = &sound.icon {
+        if !is_valid_id(icon) {
+          return Err(context(format!("invalid icon \"{}\"", icon)));
+        }
+      }
+      if let Some(category) = &sound.category {
+        if Category::from_name(category).is_none() {
+          let names: Vec<&str> = Category::ALL.iter().map(|c| c.as_str()).collect();
+          return Err(context(format!(
+            "unknown category \"{}\" (expected one of: {})",
+            category,
+            names.join(", ")
+          )));
+        }
+      }
+      if let Some(region) = &sound.loop_region {
+        let end_ok = region.end.map(|end| end.is_finite() && end > region.start).unwrap_or(true);
+        if !region.start.is_finite() || region.start < 0.0 || !end_ok {
+          return Err(context("invalid loop points".to_string()));
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+/// App-wide id of a pack sound.
+pub fn sound_id(pack_id: &str, sound_id: &str) -> String {
+  format!("{}.{}", pack_id, sound_id)
+}
+
+/// The webview can write the store, so packs are checked again on the way
+/// out; ones that don't pass are left out.
+pub fn installed_packs<R: Runtime>(app: &AppHandle<R>) -> Vec<InstalledPack> {
+  let packs: Vec<InstalledPack> = app
+    .store(SOUNDS_STORE_FILE)
+    .ok()
+    .and_then(|s| s.get(PACKS_STORE_KEY))
+    .and_then(|v| serde_json::from_value(v).ok())
+    .unwrap_or_default();
+  packs
+    .into_iter()
+    .filter(|pack| match pack.manifest.validate() {
+      Ok(()) => true,
+      Err(e) => {
+        log::warn!("Ignoring installed sound pack {}: {}", pack.manifest.id, e);
+        false
+      }
+    })
+    .collect()
+}
+
+fn save_packs<R: Runtime>(app: &AppHandle<R>, packs: &[InstalledPack]) -> Result<(), String> {
+  let store = app.store(SOUNDS_STORE_FILE).map_err(|e| e.to_string())?;
+  store.set(PACKS_STORE_KEY, serde_json::to_value(packs).map_err(|e| e.to_string())?);
+  store.save().map_err(|e| e.to_string())
+}
+
+pub fn packs_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+  app
+    .path()
+    .app_data_dir()
+    .map(|dir| dir.join(PACKS_DIR))
+    .map_err(|e| e.to_string())
+}
+
+/// The folder `pack_id` is unpacked to, if it exists and resolves to a
+/// folder right inside `root`; a link to somewhere else doesn't count.
+fn installed_dir(root: &Path, pack_id: &str) -> Result<PathBuf, String> {
+  if !is_valid_id(pack_id) {
+    return Err(format!("Invalid pack id \"{}\"", pack_id));
+  }
+  let root = root.canonicalize().map_err(|e| e.to_string())?;
+  let dir = root.join(pack_id).canonicalize().map_err(|e| e.to_string())?;
+  if dir.parent() != Some(root.as_path()) {
+    return Err(format!("{} is outside {}", dir.display(), root.display()));
+  }
+  Ok(dir)
+}
+
+fn read_manifest<R: Read + std::io::Seek>(archive: &mut zip::ZipArchive<R>) -> Result<PackManifest, String> {
+  let mut entry = archive
+    .by_name(MANIFEST_FILE)
+    .map_err(|_| format!("Archive has no {}", MANIFEST_FILE))?;
+  if entry.size() > MAX_MANIFEST_SIZE {
+    return Err(format!("{} is too large", MANIFEST_FILE));
+  }
+  let mut json = String::new();
+  entry
+    .read_to_string(&mut json)
+    .map_err(|e| format!("Failed to read {}: {}", MANIFEST_FILE, e))?;
+  let manifest: PackManifest =
+    serde_json::from_str(&json).map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE, e))?;
+  manifest.validate()?;
+  Ok(manifest)
+}
+
+/// Unpack the sounds listed in the manifest into `dest` and check that each
+/// one decodes and that its loop points fit.
+fn unpack_sounds<R: Read + std::io::Seek>(
+  archive: &mut zip::ZipArchive<R>,
+  manifest: &PackManifest,
+  dest: &Path,
+) -> Result<(), String> {
+  let mut unpacked = 0u64;
+  let files: HashSet<&str> = manifest.sounds.iter().map(|s| s.file.as_str()).collect();
+  for file in files {
+    let mut entry = archive
+      .by_name(file)
+      .map_err(|_| format!("Archive is missing {}", file))?;
+    unpacked += entry.size();
+    if unpacked > MAX_UNPACKED_SIZE {
+      return Err(format!("Pack unpacks to more than {} MB", MAX_UNPACKED_SIZE / 1024 / 1024));
+    }
+
+    let path = dest.join(file);
+    if let Some(parent) = path.parent() {
+      std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    // Cap the copy as well: the size in the zip header is only a claim.
+    let mut out = File::create(&path).map_err(|e| e.to_string())?;
+    let written = std::io::copy(&mut (&mut entry).take(MAX_UNPACKED_SIZE + 1), &mut out)
+      .map_err(|e| format!("Failed to unpack {}: {}", file, e))?;
+    if written > MAX_UNPACKED_SIZE {
+      return Err(format!("{} is too large", file));
+    }
+  }
+
+  for sound in &manifest.sounds {
+    let path = dest.join(&sound.file);
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_string();
+    let bytes = std::fs::read(&path).map_err(|e| e.to_string())?;
+    let decoded =
+      decoder::decode(bytes, Some(&ext)).map_err(|e| format!("Sound \"{}\": {}", sound.id, e))?;
+    let duration = decoded.frames() as f64 / decoded.sample_rate as f64;
+    if let Some(region) = &sound.loop_region {
+      if region.start >= duration || region.end.map(|end| end > duration + 0.01).unwrap_or(false) {
+        return Err(format!(
+          "Sound \"{}\": loop points are outside the file ({:.2}s)",
+          sound.id, duration
+        ));
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Open a pack archive and read its manifest.
+fn open_archive(source: &Path) -> Result<(zip::ZipArchive<File>, PackManifest), String> {
+  let file = File::open(source).map_err(|e| format!("Failed to open {}: {}", source.display(), e))?;
+  let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Not a valid sound pack: {}", e))?;
+  if archive.len() > MAX_ARCHIVE_ENTRIES {
+    return Err(format!("Archive has more than {} entries", MAX_ARCHIVE_ENTRIES));
+  }
+  let manifest = read_manifest(&mut archive)?;
+  Ok((archive, manifest))
+}
+
+/// `replace` decides whether an already installed pack with the same id is
+/// upgraded; it's only consulted on a conflict.
+fn install_archive<R: Runtime>(
+  app: &AppHandle<R>,
+  source: &Path,
+  replace: impl FnOnce(&InstalledPack, &PackManifest) -> bool,
+) -> Result<InstalledPack, String> {
+  let (mut archive, manifest) = open_archive(source)?;
+
+  let mut packs = installed_packs(app);
+  let existing = packs.iter().position(|p| p.manifest.id == manifest.id);
+  if let Some(index) = existing.filter(|&i| !replace(&packs[i], &manifest)) {
+    return Err(format!(
+      "Sound pack \"{}\" is already installed (version {})",
+      manifest.id, packs[index].manifest.version
+    ));
+  }
+
+  // Unpack next to the final location and swap in only once everything
+  // checked out, so a bad archive never leaves a half-installed pack.
+  let root = packs_dir(app)?;
+  let staging = root.join(format!(".staging-{}", manifest.id));
+  let _ = std::fs::remove_dir_all(&staging);
+  std::fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+  let unpacked = unpack_sounds(&mut archive, &manifest, &staging).and_then(|_| {
+    let json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    std::fs::write(staging.join(MANIFEST_FILE), json).map_err(|e| e.to_string())
+  });
+  if let Err(e) = unpacked {
+    let _ = std::fs::remove_dir_all(&staging);
+    return Err(e);
+  }
+
+  let target = root.join(&manifest.id);
+  if target.exists() {
+    std::fs::remove_dir_all(&target).map_err(|e| format!("Failed to replace pack: {}", e))?;
+  }
+  std::fs::rename(&staging, &target).map_err(|e| format!("Failed to install pack: {}", e))?;
+
+  let pack = InstalledPack {
+    enabled: existing.map(|i| packs[i].enabled).unwrap_or(true),
+    installed_at: util::now_millis(),
+    manifest,
+  };
+  match existing {
+    Some(index) => packs[index] = pack.clone(),
+    None => packs.push(pack.clone()),
+  }
+  save_packs(app, &packs)?;
+  log::info!("Installed sound pack {} {}", pack.manifest.id, pack.manifest.version);
+  catalog::notify_changed(app);
+  Ok(pack)
+}
+
+/// Install a sound pack archive. Without `path` a file picker is shown (and
+/// failures are reported in a dialog too); cancelling it resolves to `null`.
+/// A pack whose id is already installed is rejected unless `replace` is set
+/// (or, from the picker, the user confirms), in which case it's upgraded in
+/// place and keeps its enabled state.
+#[tauri::command]
+pub async fn install_sound_pack<R: Runtime>(
+  app: AppHandle<R>,
+  path: Option<String>,
+  replace: Option<bool>,
+) -> Result<Option<InstalledPack>, String> {
+  tauri::async_runtime::spawn_blocking(move || {
+    let interactive = path.is_none();
+    let ko = crate::current_lang(&app) == "ko";
+    let source = match path {
+      Some(path) => PathBuf::from(path),
+      None => {
+        let picked = app
+          .dialog()
+          .file()
+          .add_filter(if ko { "사운드 팩" } else { "Sound Pack" }, &["zip"])
+          .blocking_pick_file();
+        match picked {
+          Some(file) => file.into_path().map_err(|e| e.to_string())?,
+          None => return Ok(None),
+        }
+      }
+    };
+    let replace = replace.unwrap_or(false);
+    let result = install_archive(&app, &source, |installed, manifest| {
+      replace
+        || (interactive
+          && app
+            .dialog()
+            .message(if ko {
+              format!(
+                "\"{}\" {} 버전이 이미 설치되어 있습니다. {} 버전으로 바꿀까요?",
+                installed.manifest.name, installed.manifest.version, manifest.version
+              )
+            } else {
+              format!(
+                "\"{}\" {} is already installed. Replace it with version {}?",
+                installed.manifest.name, installed.manifest.version, manifest.version
+              )
+            })
+            .title(if ko { "사운드 팩을 바꿀까요?" } else { "Replace sound pack?" })
+            .buttons(MessageDialogButtons::OkCancelCustom(
+              (if ko { "바꾸기" } else { "Replace" }).to_string(),
+              (if ko { "취소" } else { "Cancel" }).to_string(),
+            ))
+            .blocking_show())
+    });
+    if let (true, Err(e)) = (interactive, &result) {
+      app
+        .dialog()
+        .message(e)
+        .title(if ko { "사운드 팩을 설치하지 못했습니다" } else { "Couldn't install sound pack" })
+        .kind(MessageDialogKind::Error)
+        .show(|_| {});
+    }
+    result.map(Some)
+  })
+  .await
+  .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn list_sound_packs<R: Runtime>(app: AppHandle<R>) -> Vec<InstalledPack> {
+  installed_packs(&app)
+}
+
+#[tauri::command]
+pub fn set_sound_pack_enabled<R: Runtime>(app: AppHandle<R>, pack_id: String, enabled: bool) -> Result<(), String> {
+  let mut packs = installed_packs(&app);
+  let pack = packs
+    .iter_mut()
+    .find(|p| p.manifest.id == pack_id)
+    .ok_or_else(|| format!("Sound pack \"{}\" is not installed", pack_id))?;
+  pack.enabled = enabled;
+  save_packs(&app, &packs)?;
+  catalog::notify_changed(&app);
+  Ok(())
+}
+
+#[tauri::command]
+pub fn uninstall_sound_pack<R: Runtime>(app: AppHandle<R>, pack_id: String) -> Result<(), String> {
+  let mut packs = installed_packs(&app);
+  let Some(index) = packs.iter().position(|p| p.manifest.id == pack_id) else {
+    return Ok(());
+  };
+  packs.remove(index);
+  save_packs(&app, &packs)?;
+
+  match packs_dir(&app).and_then(|root| installed_dir(&root, &pack_id)) {
+    Ok(dir) => {
+      if let Err(e) = std::fs::remove_dir_all(dir) {
+        log::warn!("Failed to delete sound pack {}: {}", pack_id, e);
+      }
+    }
+    Err(e) => log::warn!("Not deleting sound pack {}: {}", pack_id, e),
+  }
+  log::info!("Uninstalled sound pack {}", pack_id);
+  catalog::notify_changed(&app);
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Write;
+
+  use zip::write::SimpleFileOptions;
+
+  use super::*;
+  use crate::test_support::TempDir;
+
+  fn sound(id: &str, file: &str) -> PackSound {
+    PackSound {
+      id: id.to_string(),
+      label: "Light rain".to_string(),
+      file: file.to_string(),
+      icon: None,
+      category: None,
+      loop_region: None,
+      license: None,
+      attribution: None,
+    }
+  }
+
+  fn manifest(sounds: Vec<PackSound>) -> PackManifest {
+    PackManifest {
+      schema_version: SCHEMA_VERSION,
+      id: "rain".to_string(),
+      name: "Rain".to_string(),
+      version: "1.0.0".to_string(),
+      author: None,
+      description: None,
+      license: "CC0-1.0".to_string(),
+      sounds,
+    }
+  }
+
+  /// A second of 16-bit mono silence.
+  fn wav() -> Vec<u8> {
+    let rate: u32 = 8000;
+    let data_len = rate * 2;
+    let mut bytes = b"RIFF".to_vec();
+    bytes.extend((36 + data_len).to_le_bytes());
+    bytes.extend(b"WAVEfmt ");
+    bytes.extend(16u32.to_le_bytes());
+    bytes.extend(1u16.to_le_bytes());
+    bytes.extend(1u16.to_le_bytes());
+    bytes.extend(rate.to_le_bytes());
+    bytes.extend((rate * 2).to_le_bytes());
+    bytes.extend(2u16.to_le_bytes());
+    bytes.extend(16u16.to_le_bytes());
+    bytes.extend(b"data");
+    bytes.extend(data_len.to_le_bytes());
+    bytes.resize(bytes.len() + data_len as usize, 0);
+    bytes
+  }
+
+  fn archive(dir: &TempDir, entries: &[(&str, &[u8])]) -> PathBuf {
+    let path = dir.join("pack.zip");
+    let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+    for (name, bytes) in entries {
+      zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+      zip.write_all(bytes).unwrap();
+    }
+    zip.finish().unwrap();
+    path
+  }
+
+  fn manifest_json(manifest: &PackManifest) -> Vec<u8> {
+    serde_json::to_vec(manifest).unwrap()
+  }
+
+  #[test]
+  fn paths_must_stay_inside_the_archive() {
+    assert!(is_safe_path("light.wav"));
+    assert!(is_safe_path("sounds/light.wav"));
+    for path in ["", "../light.wav", "sounds/../../light.wav", "/etc/passwd", "./light.wav", "sounds\\light.wav"] {
+      assert!(!is_safe_path(path), "{}", path);
+    }
+  }
+
+  #[test]
+  fn ids_are_lowercase_words() {
+    assert!(is_valid_id("rain"));
+    assert!(is_valid_id("2-rain"));
+    for id in ["", "-rain", "Rain", "rain.light", "../rain", &"a".repeat(MAX_ID_LEN + 1)] {
+      assert!(!is_valid_id(id), "{}", id);
+    }
+  }
+
+  #[test]
+  fn manifest_is_validated() {
+    assert!(manifest(vec![sound("light", "sounds/light.wav")]).validate().is_ok());
+
+    let invalid = [
+      PackManifest {
+        schema_version: 2,
+        ..manifest(vec![sound("light", "light.wav")])
+      },
+      PackManifest {
+        id: "../rain".to_string(),
+        ..manifest(vec![sound("light", "light.wav")])
+      },
+      PackManifest {
+        name: " ".to_string(),
+        ..manifest(vec![sound("light", "light.wav")])
+      },
+      manifest(Vec::new()),
+      manifest(vec![sound("light", "light.wav"), sound("light", "heavy.wav")]),
+      manifest(vec![sound("Light", "light.wav")]),
+      manifest(vec![sound("light", "../light.wav")]),
+      manifest(vec![sound("light", "/tmp/light.wav")]),
+      manifest(vec![sound("light", "light.exe")]),
+      manifest(vec![PackSound {
+        category: Some("nowhere".to_string()),
+        ..sound("light", "light.wav")
+      }]),
+      manifest(vec![PackSound {
+        loop_region: Some(LoopRegion { start: 2.0, end: Some(1.0) }),
+        ..sound("light", "light.wav")
+      }]),
+    ];
+    for manifest in invalid {
+      assert!(manifest.validate().is_err(), "{:?}", manifest);
+    }
+  }
+
+  #[test]
+  fn a_valid_archive_unpacks() {
+    let dir = TempDir::new();
+    let manifest = manifest(vec![sound("light", "sounds/light.wav")]);
+    let path = archive(&dir, &[(MANIFEST_FILE, &manifest_json(&manifest)), ("sounds/light.wav", &wav())]);
+    let (mut archive, read) = open_archive(&path).unwrap();
+    assert_eq!(read.id, "rain");
+    let dest = dir.join("unpacked");
+    unpack_sounds(&mut archive, &read, &dest).unwrap();
+    assert_eq!(std::fs::read(dest.join("sounds/light.wav")).unwrap(), wav());
+  }
+
+  #[test]
+  fn archives_with_too_many_entries_or_a_huge_manifest_are_refused() {
+    let dir = TempDir::new();
+    let json = manifest_json(&manifest(vec![sound("light", "light.wav")]));
+    let names: Vec<String> = (0..MAX_ARCHIVE_ENTRIES).map(|n| format!("{}.txt", n)).collect();
+    let mut entries: Vec<(&str, &[u8])> = names.iter().map(|n| (n.as_str(), &[][..])).collect();
+    entries.push((MANIFEST_FILE, &json));
+    let error = open_archive(&archive(&dir, &entries)).err().unwrap();
+    assert!(error.contains("entries"), "{}", error);
+
+    let huge = vec![b' '; MAX_MANIFEST_SIZE as usize + 1];
+    let error = open_archive(&archive(&dir, &[(MANIFEST_FILE, &huge)])).err().unwrap();
+    assert!(error.contains("too large"), "{}", error);
+  }
+
+  #[test]
+  fn zip_bombs_are_stopped_at_the_unpacked_limit() {
+    let dir = TempDir::new();
+    // Compresses to next to nothing.
+    let zeros = vec![0; MAX_UNPACKED_SIZE as usize / 2 + 1];
+    let manifest = manifest(vec![sound("light", "light.wav"), sound("heavy", "heavy.wav")]);
+    let path = archive(
+      &dir,
+      &[(MANIFEST_FILE, &manifest_json(&manifest)), ("light.wav", &zeros), ("heavy.wav", &zeros)],
+    );
+    let (mut archive, manifest) = open_archive(&path).unwrap();
+    let error = unpack_sounds(&mut archive, &manifest, &dir.join("unpacked")).unwrap_err();
+    assert!(error.contains("unpacks to more than"), "{}", error);
+  }
+
+  #[test]
+  fn only_folders_right_inside_the_root_are_installed_dirs() {
+    let root = TempDir::new();
+    std::fs::create_dir(root.join("rain")).unwrap();
+    let installed = installed_dir(root.path(), "rain").unwrap();
+    assert_eq!(installed, root.path().canonicalize().unwrap().join("rain"));
+
+    assert!(installed_dir(root.path(), "snow").is_err());
+    for id in ["..", "../rain", "/tmp", "rain/.."] {
+      assert!(installed_dir(root.path(), id).is_err(), "{}", id);
+    }
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn links_out_of_the_root_are_not_installed_dirs() {
+    let root = TempDir::new();
+    let elsewhere = TempDir::new();
+    std::os::unix::fs::symlink(elsewhere.path(), root.join("rain")).unwrap();
+    assert!(installed_dir(root.path(), "rain").is_err());
+    assert!(elsewhere.path().exists());
+  }
+}
@@ -0,0 +1,88 @@
+//! Files page scripts must not be able to write, such as the switches for
+//! the local API and shell hooks.
+//!
+//! The webview reaches the disk only through the store plugin, whose `load`
+//! would open any path, absolute or with `..`. So the webview isn't granted
+//! `load` (see `capabilities/default.json`): it can only get the stores in
+//! [`WEBVIEW_STORES`], which [`plugin`] opens before any window exists, and
+//! the ones opened here in Rust. None of those is ever in [`DIR`].
+
+use std::path::PathBuf;
+
+use tauri::plugin::{Builder, TauriPlugin};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+/// The stores the frontend keeps its own settings in.
+pub const WEBVIEW_STORES: &[&str] = &["app-settings.json", "timer-sessions.json"];
+/// Under the app config folder.
+const DIR: &str = "protected";
+
+/// `<app config>/protected/<name>`.
+pub fn path<R: Runtime>(app: &AppHandle<R>, name: &str) -> Result<PathBuf, String> {
+  app
+    .path()
+    .app_config_dir()
+    .map(|dir| dir.join(DIR).join(name))
+    .map_err(|e| e.to_string())
+}
+
+/// Opens [`WEBVIEW_STORES`] for the webview to get with `getStore`. A
+/// plugin, since plugins are set up before the windows are created.
+pub fn plugin<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("webview-stores")
+    .setup(|app, _api| {
+      for name in WEBVIEW_STORES {
+        if let Err(e) = app.store(*name) {
+          log::warn!("Failed to open {}: {}", name, e);
+        }
+      }
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn capabilities() -> Vec<(PathBuf, serde_json::Value)> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    ["capabilities", "optional-capabilities"]
+      .iter()
+      .flat_map(|dir| std::fs::read_dir(root.join(dir)).unwrap())
+      .map(|entry| entry.unwrap().path())
+      .filter(|path| path.extension().is_some_and(|e| e == "json"))
+      .map(|path| {
+        let json = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        (path, json)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn the_webview_cannot_open_stores_by_path() {
+    let capabilities = capabilities();
+    assert!(!capabilities.is_empty());
+    for (path, capability) in capabilities {
+      for permission in capability["permissions"].as_array().unwrap() {
+        let id = permission.as_str().or(permission["identifier"].as_str()).unwrap();
+        assert!(
+          !matches!(id, "store:default" | "store:allow-load"),
+          "{} grants {}",
+          path.display(),
+          id
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn webview_stores_are_plain_file_names() {
+    for name in WEBVIEW_STORES {
+      let path = std::path::Path::new(name);
+      assert_eq!(path.components().count(), 1, "{}", name);
+      assert_ne!(path, std::path::Path::new(DIR));
+    }
+  }
+}
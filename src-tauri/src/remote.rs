@@ -0,0 +1,314 @@
+//! Requests from outside the window, such as a second `zerohz` invocation,
+//! a `zerohz://` link or the local API, and the status reported back to them.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::audio::playback::{self, PlaybackCommand, PlaybackState, PlaybackStatus};
+use crate::audio::{self, AudioState};
+use crate::{catalog, scenes};
+use crate::timer::{self, TimerCommand, TimerState, TimerStatus};
+
+/// How long a request waits for the frontend to sync back the state it
+/// changed, so the reported status already reflects it.
+const SYNC_TIMEOUT: Duration = Duration::from_millis(750);
+/// Extra time after the first sync for the rest of a combined request
+/// (`--start` plus `--scene`) to land.
+const SYNC_SETTLE: Duration = Duration::from_millis(50);
+const SYNC_POLL: Duration = Duration::from_millis(10);
+
+/// Windows of the UI that can be opened from outside it, such as the tray.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Panel {
+  SessionHistory,
+  Usage,
+  AudioOutput,
+  SoundPacks,
+  Webhooks,
+  License,
+}
+
+impl Panel {
+  pub const ALL: [Panel; 6] = [
+    Panel::SessionHistory,
+    Panel::Usage,
+    Panel::AudioOutput,
+    Panel::SoundPacks,
+    Panel::Webhooks,
+    Panel::License,
+  ];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      Panel::SessionHistory => "session-history",
+      Panel::Usage => "usage",
+      Panel::AudioOutput => "audio-output",
+      Panel::SoundPacks => "sound-packs",
+      Panel::Webhooks => "webhooks",
+      Panel::License => "license",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL.into_iter().find(|p| p.name() == name)
+  }
+
+  /// Frontend event that opens the panel.
+  fn event(self) -> &'static str {
+    match self {
+      Panel::SessionHistory => "open-session-history",
+      Panel::Usage => "open-usage",
+      Panel::AudioOutput => "open-audio-output",
+      Panel::SoundPacks => "open-sound-packs",
+      Panel::Webhooks => "open-webhooks",
+      Panel::License => "open-license-input",
+    }
+  }
+}
+
+/// Show the window and open `panel` in it.
+pub fn open_panel<R: Runtime>(app: &AppHandle<R>, panel: Panel) {
+  if let Some(window) = app.get_webview_window("main") {
+    let _ = window.show();
+    let _ = window.set_focus();
+    let _ = window.emit(panel.event(), ());
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum Action {
+  /// Show and focus the window.
+  Show,
+  Open { panel: Panel },
+  /// Play a scene (see [`scenes::resolve`]).
+  Scene { scene: String },
+  /// Start a countdown from a timer preset.
+  Start { preset: String, task: Option<String> },
+  /// Stop the timer and every sound.
+  Stop,
+  PauseTimer,
+  ResumeTimer,
+  StopTimer,
+  /// Turn every sound off, leaving the timer running.
+  StopSounds,
+  /// Turn one sound on or off and/or set its volume (0-1).
+  Sound {
+    sound: String,
+    on: Option<bool>,
+    volume: Option<f32>,
+  },
+  Mute { muted: bool },
+  /// Set the master volume (0-1).
+  Volume { volume: f32 },
+}
+
+impl Action {
+  /// Whether the action changes the timer or the sounds.
+  pub fn changes_state(&self) -> bool {
+    !matches!(self, Action::Show | Action::Open { .. })
+  }
+}
+
+fn validate_volume(volume: f32) -> Result<(), String> {
+  if (0.0..=1.0).contains(&volume) {
+    Ok(())
+  } else {
+    Err(format!("Volume must be between 0 and 1, got {}", volume))
+  }
+}
+
+enum FrontendCommand {
+  Playback(PlaybackCommand),
+  Timer(TimerCommand),
+  Open(Panel),
+}
+
+impl FrontendCommand {
+  fn send<R: Runtime>(self, app: &AppHandle<R>) {
+    match self {
+      FrontendCommand::Playback(command) => playback::send_command(app, command),
+      FrontendCommand::Timer(command) => timer::send_command(app, command),
+      FrontendCommand::Open(panel) => open_panel(app, panel),
+    }
+  }
+}
+
+/// Commands are held back until the frontend has registered its listeners,
+/// so a request made at launch isn't emitted into the void.
+#[derive(Default)]
+pub struct RemoteState {
+  inner: Mutex<Pending>,
+}
+
+#[derive(Default)]
+struct Pending {
+  ready: bool,
+  queued: Vec<FrontendCommand>,
+}
+
+fn show_window<R: Runtime>(app: &AppHandle<R>) {
+  if let Some(window) = app.get_webview_window("main") {
+    let _ = window.show();
+    let _ = window.set_focus();
+  }
+}
+
+/// Validate every action, then carry them out. Nothing is applied if any
+/// action is invalid.
+pub fn apply<R: Runtime>(app: &AppHandle<R>, actions: &[Action]) -> Result<(), String> {
+  let mut commands = Vec::new();
+  let mut show = false;
+  let mut master_volume = None;
+  for action in actions {
+    match action {
+      Action::Show => show = true,
+      Action::Open { panel } => commands.push(FrontendCommand::Open(*panel)),
+      Action::Scene { scene } => commands.push(FrontendCommand::Playback(PlaybackCommand::Scene {
+        sounds: scenes::resolve(app, scene)?,
+      })),
+      Action::Start { preset, task } => {
+        timer::validate_preset(preset)?;
+        commands.push(FrontendCommand::Timer(TimerCommand::Start {
+          preset: preset.clone(),
+          task: task.as_deref().map(str::trim).filter(|t| !t.is_empty()).map(str::to_string),
+        }));
+      }
+      Action::Stop => {
+        commands.push(FrontendCommand::Timer(TimerCommand::Stop));
+        commands.push(FrontendCommand::Playback(PlaybackCommand::Stop));
+      }
+      Action::PauseTimer => commands.push(FrontendCommand::Timer(TimerCommand::Pause)),
+      Action::ResumeTimer => commands.push(FrontendCommand::Timer(TimerCommand::Resume)),
+      Action::StopTimer => commands.push(FrontendCommand::Timer(TimerCommand::Stop)),
+      Action::StopSounds => commands.push(FrontendCommand::Playback(PlaybackCommand::Stop)),
+      Action::Sound { sound, on, volume } => {
+        if catalog::find(app, sound).is_none() {
+          return Err(format!("Unknown sound '{}'", sound));
+        }
+        if let Some(volume) = volume {
+          validate_volume(*volume)?;
+        }
+        commands.push(FrontendCommand::Playback(PlaybackCommand::Sound {
+          sound: sound.clone(),
+          on: *on,
+          volume: *volume,
+        }));
+      }
+      Action::Mute { muted: true } => commands.push(FrontendCommand::Playback(PlaybackCommand::Pause)),
+      Action::Mute { muted: false } => commands.push(FrontendCommand::Playback(PlaybackCommand::Play)),
+      Action::Volume { volume } => {
+        validate_volume(*volume)?;
+        master_volume = Some(*volume);
+      }
+    }
+  }
+
+  if show {
+    show_window(app);
+  }
+  // The master volume lives in the mixer, not the frontend.
+  if let Some(volume) = master_volume {
+    audio::set_master_volume(app, volume)?;
+  }
+  let state = app.state::<RemoteState>();
+  let mut pending = state.inner.lock().map_err(|e| e.to_string())?;
+  if pending.ready {
+    drop(pending);
+    for command in commands {
+      command.send(app);
+    }
+  } else {
+    pending.queued.extend(commands);
+  }
+  Ok(())
+}
+
+/// Called by the frontend once it listens for playback, timer and panel
+/// events.
+#[tauri::command]
+pub fn remote_ready<R: Runtime>(app: AppHandle<R>, state: tauri::State<RemoteState>) -> Result<(), String> {
+  let queued = {
+    let mut pending = state.inner.lock().map_err(|e| e.to_string())?;
+    pending.ready = true;
+    std::mem::take(&mut pending.queued)
+  };
+  for command in queued {
+    command.send(&app);
+  }
+  Ok(())
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Status {
+  pub version: String,
+  pub timer: TimerStatus,
+  pub playback: PlaybackStatus,
+  pub audio: PlaybackState,
+  /// Named scene matching the active sounds exactly.
+  pub scene: Option<String>,
+  /// "Rain + Fire"
+  pub title: String,
+}
+
+pub fn status<R: Runtime>(app: &AppHandle<R>) -> Status {
+  let audio = app.state::<AudioState>().subscribe_playback().borrow().clone();
+  Status {
+    version: app.package_info().version.to_string(),
+    timer: app.state::<TimerState>().status(),
+    playback: audio.status(),
+    scene: scenes::matching(&audio.active_sounds).map(|s| s.id.to_string()),
+    title: scenes::title(app, &audio.active_sounds),
+    audio,
+  }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Response {
+  pub ok: bool,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub status: Option<Status>,
+}
+
+impl Response {
+  pub fn failed(error: impl Into<String>) -> Self {
+    Self {
+      ok: false,
+      error: Some(error.into()),
+      status: None,
+    }
+  }
+}
+
+/// Apply `actions` and report the resulting status.
+pub fn handle<R: Runtime>(app: &AppHandle<R>, actions: &[Action]) -> Response {
+  let timer = app.state::<TimerState>().subscribe();
+  let playback = app.state::<AudioState>().subscribe_playback();
+  if let Err(e) = apply(app, actions) {
+    return Response::failed(e);
+  }
+
+  if actions.iter().any(Action::changes_state) {
+    let deadline = Instant::now() + SYNC_TIMEOUT;
+    while Instant::now() < deadline {
+      if timer.has_changed().unwrap_or(true) || playback.has_changed().unwrap_or(true) {
+        std::thread::sleep(SYNC_SETTLE);
+        break;
+      }
+      std::thread::sleep(SYNC_POLL);
+    }
+  }
+
+  Response {
+    ok: true,
+    error: None,
+    status: Some(status(app)),
+  }
+}
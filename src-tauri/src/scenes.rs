@@ -0,0 +1,71 @@
+use tauri::{AppHandle, Runtime};
+
+use crate::catalog;
+
+/// A named mix of built-in sounds, played at their current volumes.
+pub struct Scene {
+  pub id: &'static str,
+  pub sounds: &'static [&'static str],
+}
+
+pub const SCENES: &[Scene] = &[
+  Scene { id: "rain-fire", sounds: &["rain", "fire"] },
+  Scene { id: "deep-work", sounds: &["rain", "keyboard"] },
+  Scene { id: "storm", sounds: &["rain", "thunder", "wind"] },
+  Scene { id: "seaside", sounds: &["waves", "wind"] },
+  Scene { id: "forest-stream", sounds: &["forest", "stream"] },
+  Scene { id: "night-train", sounds: &["train", "night"] },
+];
+
+/// Sound ids for a scene name, or for an ad-hoc `rain+waves` mix of any
+/// sounds in the catalog. Spaces separate sounds too, since an unescaped
+/// `+` in a link's query decodes to one.
+pub fn resolve<R: Runtime>(app: &AppHandle<R>, name: &str) -> Result<Vec<String>, String> {
+  let name = name.trim();
+  if let Some(scene) = SCENES.iter().find(|s| s.id == name) {
+    return Ok(scene.sounds.iter().map(|id| id.to_string()).collect());
+  }
+  if name.is_empty() {
+    return Err("No scene given".to_string());
+  }
+
+  let known = catalog::sounds(app);
+  let mut ids: Vec<String> = Vec::new();
+  for id in name.split(['+', ' ']).filter(|id| !id.is_empty()) {
+    if !known.iter().any(|s| s.id == id) {
+      let scenes: Vec<_> = SCENES.iter().map(|s| s.id).collect();
+      return Err(format!(
+        "Unknown scene or sound '{}' (scenes: {})",
+        id,
+        scenes.join(", ")
+      ));
+    }
+    if !ids.iter().any(|i| i == id) {
+      ids.push(id.to_string());
+    }
+  }
+  Ok(ids)
+}
+
+/// The named scene playing exactly `sound_ids`, if any.
+pub fn matching(sound_ids: &[String]) -> Option<&'static Scene> {
+  SCENES.iter().find(|scene| {
+    scene.sounds.len() == sound_ids.len() && scene.sounds.iter().all(|id| sound_ids.iter().any(|s| s == id))
+  })
+}
+
+/// "Rain + Fire" for the given sounds.
+pub fn title<R: Runtime>(app: &AppHandle<R>, sound_ids: &[String]) -> String {
+  let sounds = catalog::sounds(app);
+  sound_ids
+    .iter()
+    .map(|id| {
+      sounds
+        .iter()
+        .find(|s| &s.id == id)
+        .map(|s| s.label.clone())
+        .unwrap_or_else(|| id.clone())
+    })
+    .collect::<Vec<_>>()
+    .join(" + ")
+}
@@ -0,0 +1,252 @@
+//! Status file for bars that can't show the tray (waybar, polybar, tmux):
+//! the timer, active sounds and today's focus time as one line of JSON in
+//! the runtime dir, rewritten whenever they change and removed on exit
+//! (see `docs/08-status-bar.md`).
+//!
+//! The top-level `text`, `alt`, `tooltip`, `class` and `percentage` keys
+//! are what a waybar custom module with `"return-type": "json"` expects.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::audio::AudioState;
+use crate::scenes;
+use crate::timer::{TimerMode, TimerSession, TimerState, TimerStatus, BREAK_PRESETS};
+use crate::util::{self, now_millis};
+
+/// Written by `useTimerSessions` (`src/hooks/useTimerSessions.ts`).
+const SESSIONS_STORE_FILE: &str = "timer-sessions.json";
+const SESSIONS_STORE_KEY: &str = "sessions";
+/// Picks up newly recorded sessions and the date changing while nothing
+/// else happens.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Phase {
+  Idle,
+  Focus,
+  Break,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusLine {
+  /// Time left on a countdown, or elapsed on a stopwatch; empty when idle
+  /// so waybar hides the module.
+  text: String,
+  /// `phase`, or `paused`; for waybar's `format-icons`.
+  alt: &'static str,
+  tooltip: String,
+  class: Vec<&'static str>,
+  /// Countdown progress, 0-100.
+  percentage: u32,
+  phase: Phase,
+  paused: bool,
+  mode: TimerMode,
+  remaining_seconds: Option<u32>,
+  elapsed_seconds: u32,
+  preset: Option<String>,
+  task: Option<String>,
+  sounds: Vec<String>,
+  today_focus_seconds: u32,
+}
+
+/// Debug builds get their own file, like the instance socket.
+fn status_path() -> PathBuf {
+  let base = if cfg!(debug_assertions) { "zerohz-dev" } else { "zerohz" };
+  match std::env::var_os("XDG_RUNTIME_DIR") {
+    Some(dir) => PathBuf::from(dir).join(format!("{}-status.json", base)),
+    None => {
+      let user = std::env::var("USER").unwrap_or_default();
+      std::env::temp_dir().join(format!("{}-{}-status.json", base, user))
+    }
+  }
+}
+
+/// Unix time in milliseconds of the last local midnight, the same "today"
+/// the session history uses.
+fn local_midnight(now_millis: u64) -> u64 {
+  let now = (now_millis / 1000) as libc::time_t;
+  // SAFETY: localtime_r only writes to the tm we pass it.
+  let since_midnight = unsafe {
+    let mut tm: libc::tm = std::mem::zeroed();
+    if libc::localtime_r(&now, &mut tm).is_null() {
+      return now_millis - now_millis % 86_400_000;
+    }
+    tm.tm_hour as u64 * 3600 + tm.tm_min as u64 * 60 + tm.tm_sec as u64
+  };
+  (now_millis - now_millis % 1000).saturating_sub(since_midnight * 1000)
+}
+
+fn is_break(preset: Option<&str>) -> bool {
+  preset.is_some_and(|p| BREAK_PRESETS.contains(&p))
+}
+
+/// Focus time of the sessions recorded since `since`; breaks don't count.
+fn recorded_focus_seconds<R: Runtime>(app: &AppHandle<R>, since: u64) -> u32 {
+  let sessions: Vec<TimerSession> = app
+    .store(SESSIONS_STORE_FILE)
+    .ok()
+    .and_then(|s| s.get(SESSIONS_STORE_KEY))
+    .and_then(|v| serde_json::from_value(v).ok())
+    .unwrap_or_default();
+  sessions
+    .iter()
+    .filter(|s| s.started_at >= since && !is_break(s.preset.as_deref()))
+    .map(|s| s.actual_seconds)
+    .sum()
+}
+
+/// "4:05", or "1:02:03" past an hour.
+fn clock(seconds: u32) -> String {
+  let (h, m, s) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+  if h > 0 {
+    format!("{}:{:02}:{:02}", h, m, s)
+  } else {
+    format!("{}:{:02}", m, s)
+  }
+}
+
+/// "1h 25m", or "25m".
+fn duration(seconds: u32) -> String {
+  let (h, m) = (seconds / 3600, seconds / 60 % 60);
+  if h > 0 {
+    format!("{}h {}m", h, m)
+  } else {
+    format!("{}m", m)
+  }
+}
+
+/// `recorded_focus` is today's total before the session that's running.
+fn render(timer: &TimerStatus, sounds: Vec<String>, sounds_title: &str, recorded_focus: u32) -> StatusLine {
+  let phase = match (timer.is_running, is_break(timer.preset.as_deref())) {
+    (false, _) => Phase::Idle,
+    (true, false) => Phase::Focus,
+    (true, true) => Phase::Break,
+  };
+  let paused = timer.is_running && timer.is_paused;
+  let (remaining, elapsed) = match timer.mode {
+    TimerMode::Countdown => (
+      Some(timer.current_seconds),
+      timer.target_seconds.saturating_sub(timer.current_seconds),
+    ),
+    TimerMode::Stopwatch => (None, timer.current_seconds),
+  };
+  let today_focus = if phase == Phase::Focus {
+    recorded_focus + elapsed
+  } else {
+    recorded_focus
+  };
+  let percentage = match timer.mode {
+    TimerMode::Countdown if timer.target_seconds > 0 => elapsed * 100 / timer.target_seconds,
+    _ => 0,
+  };
+  let alt = match (phase, paused) {
+    (_, true) => "paused",
+    (Phase::Idle, _) => "idle",
+    (Phase::Focus, _) => "focus",
+    (Phase::Break, _) => "break",
+  };
+
+  let mut class = vec![alt];
+  if paused {
+    class.insert(0, if phase == Phase::Break { "break" } else { "focus" });
+  }
+  if !sounds.is_empty() {
+    class.push("playing");
+  }
+
+  let mut tooltip = Vec::new();
+  match phase {
+    Phase::Idle => tooltip.push("Timer stopped".to_string()),
+    _ => {
+      let what = if phase == Phase::Break { "Break" } else { "Focus" };
+      let state = if paused { " (paused)" } else { "" };
+      let time = match remaining {
+        Some(left) => format!("{} left", clock(left)),
+        None => clock(elapsed),
+      };
+      tooltip.push(format!("{}{}: {}", what, state, time));
+    }
+  }
+  if let Some(task) = timer.task.as_deref().filter(|t| !t.is_empty()) {
+    tooltip.push(format!("Task: {}", task));
+  }
+  if !sounds_title.is_empty() {
+    tooltip.push(format!("Sounds: {}", sounds_title));
+  }
+  tooltip.push(format!("Today: {}", duration(today_focus)));
+
+  StatusLine {
+    text: if phase == Phase::Idle {
+      String::new()
+    } else {
+      clock(remaining.unwrap_or(elapsed))
+    },
+    alt,
+    tooltip: tooltip.join("\n"),
+    class,
+    percentage: percentage.min(100),
+    phase,
+    paused,
+    mode: timer.mode,
+    remaining_seconds: remaining,
+    elapsed_seconds: elapsed,
+    preset: timer.preset.clone(),
+    task: timer.task.clone(),
+    sounds,
+    today_focus_seconds: today_focus,
+  }
+}
+
+/// Keep the status file up to date until the app exits.
+pub fn start<R: Runtime>(app: &AppHandle<R>) {
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    let path = status_path();
+    let mut timer = app.state::<TimerState>().subscribe();
+    let mut playback = app.state::<AudioState>().subscribe_playback();
+    let mut refresh = tokio::time::interval(REFRESH_INTERVAL);
+    let mut last = String::new();
+    loop {
+      let status = timer.borrow_and_update().clone();
+      let sounds = playback.borrow_and_update().active_sounds.clone();
+      let title = scenes::title(&app, &sounds);
+      let recorded = recorded_focus_seconds(&app, local_midnight(now_millis()));
+      let line = render(&status, sounds, &title, recorded);
+      match serde_json::to_string(&line) {
+        Ok(mut json) => {
+          json.push('\n');
+          if json != last {
+            match util::write_atomic(&path, json.as_bytes()) {
+              Ok(()) => last = json,
+              Err(e) => log::warn!("Failed to write {}: {}", path.display(), e),
+            }
+          }
+        }
+        Err(e) => log::warn!("Failed to serialize the status line: {}", e),
+      }
+
+      tokio::select! {
+        changed = timer.changed() => if changed.is_err() { return },
+        changed = playback.changed() => if changed.is_err() { return },
+        _ = refresh.tick() => {}
+      }
+    }
+  });
+}
+
+/// Remove the status file so bars don't show a stale timer.
+pub fn remove() {
+  let path = status_path();
+  if let Err(e) = std::fs::remove_file(&path) {
+    if e.kind() != std::io::ErrorKind::NotFound {
+      log::warn!("Failed to remove {}: {}", path.display(), e);
+    }
+  }
+}
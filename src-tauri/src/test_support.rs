@@ -0,0 +1,112 @@
+//! Helpers shared by the unit tests.
+
+use std::path::{Path, PathBuf};
+
+use crate::util;
+
+/// A new folder under the system temp folder, deleted when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+  pub fn new() -> Self {
+    let dir = std::env::temp_dir().join(format!("zerohz-test-{}", util::random_hex(8).unwrap()));
+    std::fs::create_dir_all(&dir).unwrap();
+    Self(dir)
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.0
+  }
+
+  pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+    self.0.join(path)
+  }
+}
+
+impl Drop for TempDir {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_dir_all(&self.0);
+  }
+}
+
+/// A stand-in HTTP server for the clients that talk to other services.
+pub mod mock_http {
+  use std::net::SocketAddr;
+  use std::sync::{Arc, Mutex};
+
+  use axum::extract::State;
+  use axum::http::{HeaderMap, Method, StatusCode, Uri};
+  use axum::Router;
+
+  /// One request the server got.
+  #[derive(Clone, Debug)]
+  pub struct Request {
+    pub method: Method,
+    pub path: String,
+    pub headers: HeaderMap,
+    pub body: String,
+  }
+
+  /// Records every request, on any path, and answers with what was last
+  /// set with [`Mock::reply`]: `200` and `{}` until then.
+  #[derive(Clone)]
+  pub struct Mock {
+    pub address: SocketAddr,
+    requests: Arc<Mutex<Vec<Request>>>,
+    reply: Arc<Mutex<(u16, String)>>,
+  }
+
+  impl Mock {
+    pub async fn start() -> Self {
+      let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+      let mock = Self {
+        address: listener.local_addr().unwrap(),
+        requests: Arc::default(),
+        reply: Arc::new(Mutex::new((200, "{}".to_string()))),
+      };
+      let app = Router::new().fallback(answer).with_state(mock.clone());
+      tokio::spawn(async move { axum::serve(listener, app).await });
+      mock
+    }
+
+    /// `path` starts with `/`.
+    pub fn url(&self, path: &str) -> String {
+      format!("http://{}{}", self.address, path)
+    }
+
+    pub fn reply(&self, status: u16, body: &str) {
+      *self.reply.lock().unwrap() = (status, body.to_string());
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+      self.requests.lock().unwrap().clone()
+    }
+
+    pub fn last_request(&self) -> Request {
+      self.requests().pop().expect("no request was made")
+    }
+  }
+
+  async fn answer(
+    State(mock): State<Mock>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: String,
+  ) -> (StatusCode, String) {
+    mock.requests.lock().unwrap().push(Request {
+      method,
+      path: uri.path().to_string(),
+      headers,
+      body,
+    });
+    let (status, body) = mock.reply.lock().unwrap().clone();
+    (StatusCode::from_u16(status).unwrap(), body)
+  }
+
+  /// An address nothing listens on.
+  pub fn closed_address() -> SocketAddr {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap()
+  }
+}
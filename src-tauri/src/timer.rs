@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime};
+use tokio::sync::watch;
+
+use crate::{hooks, usage, webhooks};
+
+/// Frontend event carrying a [`TimerCommand`]. Like playback, the timer runs
+/// in the frontend, which syncs the result back through `sync_timer_state`.
+pub const TIMER_COMMAND_EVENT: &str = "timer-command";
+
+/// Ids of the countdown presets in `TIMER_PRESETS` (`src/constants/timer.ts`).
+pub const PRESETS: &[&str] = &["pomodoro", "short-break", "long-break", "focus", "hour"];
+
+/// Presets that count down a break rather than focus time.
+pub const BREAK_PRESETS: &[&str] = &["short-break", "long-break"];
+
+pub fn validate_preset(id: &str) -> Result<(), String> {
+  if PRESETS.contains(&id) {
+    Ok(())
+  } else {
+    Err(format!("Unknown timer preset '{}' (expected one of: {})", id, PRESETS.join(", ")))
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum TimerCommand {
+  /// Start a countdown from a preset, abandoning any running session.
+  Start { preset: String, task: Option<String> },
+  Pause,
+  Resume,
+  /// Stop and reset the timer.
+  Stop,
+}
+
+pub fn send_command<R: Runtime>(app: &AppHandle<R>, command: TimerCommand) {
+  if let Err(e) = app.emit(TIMER_COMMAND_EVENT, command) {
+    log::warn!("Failed to emit {}: {}", TIMER_COMMAND_EVENT, e);
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerMode {
+  #[default]
+  Stopwatch,
+  Countdown,
+}
+
+/// The frontend timer, as last synced.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerStatus {
+  pub mode: TimerMode,
+  pub target_seconds: u32,
+  /// Counts up in stopwatch mode and down in countdown mode.
+  pub current_seconds: u32,
+  pub is_running: bool,
+  pub is_paused: bool,
+  /// Preset of the current countdown, if it was started from one.
+  pub preset: Option<String>,
+  pub task: Option<String>,
+}
+
+#[derive(Default)]
+pub struct TimerState {
+  status: watch::Sender<TimerStatus>,
+}
+
+impl TimerState {
+  pub fn status(&self) -> TimerStatus {
+    self.status.borrow().clone()
+  }
+
+  /// Notified whenever the timer starts, stops or ticks.
+  pub fn subscribe(&self) -> watch::Receiver<TimerStatus> {
+    self.status.subscribe()
+  }
+}
+
+/// Mirror the frontend timer so it can be reported outside the window.
+#[tauri::command]
+pub fn sync_timer_state(state: tauri::State<TimerState>, status: TimerStatus) {
+  state.status.send_if_modified(|current| {
+    let changed = *current != status;
+    *current = status;
+    changed
+  });
+}
+
+/// A timer session, as `TimerSession` in `src/types/timer.ts`. A session
+/// that has just started has no id, end or length yet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerSession {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub id: Option<String>,
+  pub mode: TimerMode,
+  pub target_seconds: u32,
+  #[serde(default)]
+  pub actual_seconds: u32,
+  #[serde(default)]
+  pub completed: bool,
+  /// Unix time in milliseconds.
+  pub started_at: u64,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub ended_at: Option<u64>,
+  #[serde(default)]
+  pub active_sounds: Vec<String>,
+  #[serde(default)]
+  pub preset: Option<String>,
+  #[serde(default)]
+  pub task_description: Option<String>,
+}
+
+/// Reported by the frontend as a session moves along. `Completed` and
+/// `Abandoned` come with the session as recorded in the history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionEvent {
+  Started,
+  Paused,
+  Resumed,
+  Completed,
+  Abandoned,
+}
+
+/// Pass a session event on to webhooks and shell hooks, and count
+/// completed sessions against the free timer trials.
+#[tauri::command]
+pub fn report_session_event<R: Runtime>(app: AppHandle<R>, event: SessionEvent, session: TimerSession) -> Result<(), String> {
+  if event == SessionEvent::Completed {
+    usage::timer_completed(&app);
+  }
+  hooks::session_event(&app, event, &session);
+  webhooks::session_event(&app, event, &session)
+}
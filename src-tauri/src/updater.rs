@@ -0,0 +1,273 @@
+//! Self-updating through `tauri-plugin-updater`, shown in the tray as the
+//! "Check for Updates" item and the status line above the version. Only
+//! built with the `updater` feature.
+
+use tauri::{Manager, Runtime, Wry};
+use tauri_plugin_updater::{Updater, UpdaterExt};
+
+use crate::{config, TrayMenuState};
+
+#[derive(Clone)]
+pub(crate) enum UpdateState {
+  Checking,
+  Available(String),
+  Latest(String),
+  Failed,
+}
+
+pub(crate) struct UpdateLocale<'a> {
+  title_available: &'a str,
+  message_available: &'a str,
+  button_install: &'a str,
+  button_later: &'a str,
+  title_ready: &'a str,
+  message_ready: &'a str,
+  title_error_download: &'a str,
+  message_error_download: &'a str,
+  title_error_check: &'a str,
+  message_error_check: &'a str,
+  title_no_update: &'a str,
+  message_no_update: &'a str,
+  title_init_error: &'a str,
+  message_init_error: &'a str,
+  status_checking: &'a str,
+  status_available: &'a str,
+  status_latest: &'a str,
+  status_failed: &'a str,
+  install_menu_label: &'a str,
+  check_menu_label: &'a str,
+  retry_menu_label: &'a str,
+}
+
+pub(crate) fn get_update_locale(lang: &str) -> UpdateLocale<'static> {
+  match lang {
+    "ko" => UpdateLocale {
+      title_available: "업데이트 가능",
+      message_available: "새 버전 {version}이 준비되었습니다. 지금 다운로드하고 설치할까요?",
+      button_install: "설치",
+      button_later: "나중에",
+      title_ready: "업데이트 완료",
+      message_ready: "업데이트가 설치되었습니다. ZeroHz를 다시 시작합니다.",
+      title_error_download: "업데이트 오류",
+      message_error_download: "업데이트 다운로드/설치에 실패했습니다. 다시 시도해 주세요.",
+      title_error_check: "업데이트 확인 실패",
+      message_error_check: "업데이트 확인에 실패했습니다. 인터넷 연결을 확인한 뒤 다시 시도해 주세요.",
+      title_no_update: "최신 버전입니다",
+      message_no_update: "이미 최신 버전(v{version})을 사용 중입니다.",
+      title_init_error: "업데이트 초기화 실패",
+      message_init_error: "업데이트 모듈을 초기화하지 못했습니다. 잠시 후 다시 시도해 주세요.",
+      status_checking: "… 업데이트 확인 중",
+      status_available: "⬆ 업데이트 가능: v{version}",
+      status_latest: "✓ 최신 버전",
+      status_failed: "⚠ 업데이트 확인 실패",
+      install_menu_label: "업데이트 설치 (v{version})",
+      check_menu_label: "업데이트 확인",
+      retry_menu_label: "업데이트 다시 확인",
+    },
+    _ => UpdateLocale {
+      title_available: "Update Available",
+      message_available: "New version {version} is available. Download and install now?",
+      button_install: "Install",
+      button_later: "Later",
+      title_ready: "Update Ready",
+      message_ready: "Update installed. ZeroHz will restart to apply it.",
+      title_error_download: "Update Error",
+      message_error_download: "Failed to download or install the update. Please try again.",
+      title_error_check: "Update Check Failed",
+      message_error_check: "Failed to check for updates. Please check your connection and try again.",
+      title_no_update: "No Updates Available",
+      message_no_update: "You are already running the latest version (v{version}).",
+      title_init_error: "Updater Error",
+      message_init_error: "Failed to initialize the updater. Please try again later.",
+      status_checking: "… Checking for updates",
+      status_available: "⬆ Update available: v{version}",
+      status_latest: "✓ Up to date",
+      status_failed: "⚠ Update check failed",
+      install_menu_label: "Install update (v{version})",
+      check_menu_label: "Check for Updates",
+      retry_menu_label: "Retry update check",
+    },
+  }
+}
+
+pub(crate) fn apply_update_state<R: Runtime>(state: &TrayMenuState<R>, locale: &UpdateLocale<'_>, new_state: UpdateState) {
+  if let Ok(mut st) = state.update_state.lock() {
+    *st = new_state.clone();
+  }
+
+  match new_state {
+    UpdateState::Checking => {
+      let _ = state.update_status.set_text(locale.status_checking);
+      let _ = state.check_update.set_text(locale.check_menu_label);
+    }
+    UpdateState::Available(v) => {
+      let _ = state.update_status.set_text(locale.status_available.replace("{version}", &v));
+      let _ = state.check_update.set_text(locale.install_menu_label.replace("{version}", &v));
+    }
+    UpdateState::Latest(v) => {
+      let _ = state.update_status.set_text(locale.status_latest.replace("{version}", &v));
+      let _ = state.check_update.set_text(locale.check_menu_label);
+    }
+    UpdateState::Failed => {
+      let _ = state.update_status.set_text(locale.status_failed);
+      let _ = state.check_update.set_text(locale.retry_menu_label);
+    }
+  }
+}
+
+/// The updater, looking where the configuration says if it pins a
+/// channel or endpoint.
+fn updater(app_handle: &tauri::AppHandle<Wry>) -> tauri_plugin_updater::Result<Updater> {
+  let builder = app_handle.updater_builder();
+  match config::get(app_handle).update_endpoint() {
+    Some(endpoint) => builder.endpoints(vec![endpoint])?.build(),
+    None => builder.build(),
+  }
+}
+
+/// Check from the tray, asking before installing what's found.
+pub(crate) fn check_now(app_handle: tauri::AppHandle<Wry>) {
+  println!("Checking for updates...");
+  tauri::async_runtime::spawn(async move {
+    let lang = app_handle
+      .try_state::<TrayMenuState<Wry>>()
+      .and_then(|state| state.current_lang.lock().ok().map(|l| l.clone()))
+      .unwrap_or_else(|| "en".to_string());
+    let locale = get_update_locale(&lang);
+    if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
+      apply_update_state(&state, &locale, UpdateState::Checking);
+    }
+
+    match updater(&app_handle) {
+      Ok(updater) => {
+        match updater.check().await {
+          Ok(update_response) => {
+            if let Some(update) = update_response {
+              println!("Update available: version {}", update.version);
+              println!("Download URL: {}", update.download_url);
+              if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
+                apply_update_state(&state, &locale, UpdateState::Available(update.version.clone()));
+              }
+
+              // Show dialog to user about available update
+              use tauri_plugin_dialog::{DialogExt, MessageDialogKind, MessageDialogButtons};
+              let confirmed = app_handle.dialog()
+                .message(locale.message_available.replace("{version}", &update.version))
+                .title(locale.title_available)
+                .buttons(MessageDialogButtons::OkCancelCustom(
+                  locale.button_install.to_string(),
+                  locale.button_later.to_string()
+                ))
+                .blocking_show();
+
+              if confirmed {
+                // User clicked Install
+                match update.download_and_install(|chunk_length, content_length| {
+                  if let Some(total) = content_length {
+                    let progress = (chunk_length as f64 / total as f64) * 100.0;
+                    println!("Download progress: {:.1}%", progress);
+                  }
+                }, || {
+                  println!("Download complete, preparing to install...");
+                }).await {
+                  Ok(_) => {
+                    println!("Update installed successfully. Restarting app to apply.");
+                    app_handle.dialog()
+                      .message(locale.message_ready)
+                      .title(locale.title_ready)
+                      .kind(MessageDialogKind::Info)
+                      .blocking_show();
+                    app_handle.request_restart();
+                  }
+                  Err(e) => {
+                    println!("Failed to download/install update: {:?}", e);
+                    app_handle.dialog()
+                      .message(locale.message_error_download)
+                      .title(locale.title_error_download)
+                      .kind(MessageDialogKind::Error)
+                      .blocking_show();
+                  }
+                }
+              }
+            } else {
+              println!("No updates available");
+              if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
+                apply_update_state(&state, &locale, UpdateState::Latest(env!("CARGO_PKG_VERSION").to_string()));
+              }
+              // Notify user that app is up to date
+              use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+              app_handle.dialog()
+                .message(locale.message_no_update.replace("{version}", env!("CARGO_PKG_VERSION")))
+                .title(locale.title_no_update)
+                .kind(MessageDialogKind::Info)
+                .blocking_show();
+            }
+          }
+          Err(e) => {
+            println!("Failed to check for updates: {:?}", e);
+            if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
+              apply_update_state(&state, &locale, UpdateState::Failed);
+            }
+            use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+            app_handle.dialog()
+              .message(locale.message_error_check)
+              .title(locale.title_error_check)
+              .kind(MessageDialogKind::Error)
+              .blocking_show();
+          }
+        }
+      }
+      Err(e) => {
+        println!("Failed to initialize updater: {:?}", e);
+        if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
+          apply_update_state(&state, &locale, UpdateState::Failed);
+        }
+        use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+        app_handle.dialog()
+          .message(locale.message_init_error)
+          .title(locale.title_init_error)
+          .kind(MessageDialogKind::Error)
+          .blocking_show();
+      }
+    }
+  });
+}
+
+/// Check on startup, to show the status in the tray without asking.
+pub(crate) fn check_in_background(app_handle: tauri::AppHandle<Wry>) {
+  tauri::async_runtime::spawn(async move {
+    let lang = app_handle
+      .try_state::<TrayMenuState<Wry>>()
+      .and_then(|state| state.current_lang.lock().ok().map(|l| l.clone()))
+      .unwrap_or_else(|| "en".to_string());
+    let locale = get_update_locale(&lang);
+
+    if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
+      apply_update_state(&state, &locale, UpdateState::Checking);
+    }
+
+    match updater(&app_handle) {
+      Ok(updater) => match updater.check().await {
+        Ok(update_response) => {
+          if let Some(update) = update_response {
+            if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
+              apply_update_state(&state, &locale, UpdateState::Available(update.version.clone()));
+            }
+          } else if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
+            apply_update_state(&state, &locale, UpdateState::Latest(env!("CARGO_PKG_VERSION").to_string()));
+          }
+        }
+        Err(_) => {
+          if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
+            apply_update_state(&state, &locale, UpdateState::Failed);
+          }
+        }
+      },
+      Err(_) => {
+        if let Some(state) = app_handle.try_state::<TrayMenuState<Wry>>() {
+          apply_update_state(&state, &locale, UpdateState::Failed);
+        }
+      }
+    }
+  });
+}
@@ -0,0 +1,488 @@
+//! Free-tier usage: today's playtime and completed timer trials, counted
+//! here rather than in the webview (see `docs/01-pricing-and-licensing.md`).
+//!
+//! Playtime adds up monotonic time while sounds play, so changing the clock
+//! neither adds nor removes any. A counted day later than today, left by a
+//! clock that was set ahead, is stale: today counts as used up.
+//!
+//! The saved counters are sealed with [`integrity`], and the latest day
+//! counted is kept, sealed too, in a second file elsewhere. After the first
+//! run, counters that are missing, don't match their seal or are older
+//! than that day count as a used-up day, as do days after today.
+
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::audio::playback::PlaybackStatus;
+use crate::audio::AudioState;
+use crate::{integrity, license, util};
+
+/// Emitted with the new [`Usage`] whenever it changes.
+pub const USAGE_EVENT: &str = "usage-changed";
+const USAGE_FILE: &str = "usage.json";
+/// In the local data folder, which isn't the config folder on Linux or
+/// Windows, so deleting one folder doesn't reset the counters.
+const MARKER_FILE: &str = "usage-seen.json";
+const INTEGRITY_PURPOSE: &str = "usage";
+/// Where the frontend used to keep the counters (`ProProvider`).
+const LEGACY_STORE_FILE: &str = "pro-settings.json";
+const LEGACY_STORE_KEY: &str = "pro";
+const TICK: Duration = Duration::from_secs(1);
+/// How often playtime is saved while it's being counted.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Debug builds get a shorter day, for testing the limit.
+pub const DAILY_PLAYTIME_LIMIT: u32 = if cfg!(debug_assertions) { 60 * 60 } else { 2 * 60 * 60 };
+pub const TIMER_TRIAL_LIMIT: u32 = if cfg!(debug_assertions) { 5 } else { 3 };
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Counters {
+  /// Local date, `YYYY-MM-DD`.
+  day: String,
+  playtime_millis: u64,
+  timer_trials: u32,
+}
+
+impl Counters {
+  fn fresh(day: String) -> Self {
+    Self {
+      day,
+      ..Self::default()
+    }
+  }
+
+  fn used_up(day: String) -> Self {
+    Self {
+      day,
+      playtime_millis: DAILY_PLAYTIME_LIMIT as u64 * 1000,
+      timer_trials: TIMER_TRIAL_LIMIT,
+    }
+  }
+
+  fn sealed_data(&self) -> Vec<u8> {
+    format!("{}\n{}\n{}", self.day, self.playtime_millis, self.timer_trials).into_bytes()
+  }
+
+  /// Start over if `today` is after the counted day, or use today up if
+  /// it's before, since the clock was set ahead. Returns whether either
+  /// happened.
+  fn roll_over(&mut self, today: &str) -> bool {
+    match today.cmp(self.day.as_str()) {
+      Ordering::Greater => *self = Self::fresh(today.to_string()),
+      Ordering::Less => {
+        log::warn!("Usage was counted for a later day; today's free usage is used up");
+        *self = Self::used_up(today.to_string());
+      }
+      Ordering::Equal => return false,
+    }
+    true
+  }
+
+  fn usage(&self) -> Usage {
+    Usage {
+      playtime_seconds: ((self.playtime_millis / 1000) as u32).min(DAILY_PLAYTIME_LIMIT),
+      playtime_limit_seconds: DAILY_PLAYTIME_LIMIT,
+      timer_trials: self.timer_trials.min(TIMER_TRIAL_LIMIT),
+      timer_trial_limit: TIMER_TRIAL_LIMIT,
+    }
+  }
+}
+
+/// What's saved in [`USAGE_FILE`].
+#[derive(Serialize, Deserialize)]
+struct SavedCounters {
+  #[serde(flatten)]
+  counters: Counters,
+  checksum: String,
+}
+
+/// What's saved in [`MARKER_FILE`]: the latest day counted.
+#[derive(Serialize, Deserialize)]
+struct SavedMarker {
+  day: String,
+  checksum: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Saved<T> {
+  Missing,
+  /// Unreadable or not matching its seal.
+  Invalid,
+  Valid(T),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Usage {
+  pub playtime_seconds: u32,
+  pub playtime_limit_seconds: u32,
+  pub timer_trials: u32,
+  pub timer_trial_limit: u32,
+}
+
+pub struct UsageState {
+  counters: Mutex<Counters>,
+  path: Option<PathBuf>,
+  marker_path: Option<PathBuf>,
+}
+
+fn today() -> String {
+  chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+fn marker_data(day: &str) -> Vec<u8> {
+  format!("marker\n{}", day).into_bytes()
+}
+
+fn read(path: &Path) -> Option<Vec<u8>> {
+  match std::fs::read(path) {
+    Ok(bytes) => Some(bytes),
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+    Err(e) => {
+      log::warn!("Failed to read {}: {}", path.display(), e);
+      Some(Vec::new())
+    }
+  }
+}
+
+fn load(path: &Path) -> Saved<Counters> {
+  let Some(bytes) = read(path) else {
+    return Saved::Missing;
+  };
+  match serde_json::from_slice::<SavedCounters>(&bytes) {
+    Ok(saved) if integrity::verify(INTEGRITY_PURPOSE, &saved.counters.sealed_data(), &saved.checksum) => {
+      Saved::Valid(saved.counters)
+    }
+    _ => Saved::Invalid,
+  }
+}
+
+fn load_marker(path: &Path) -> Saved<String> {
+  let Some(bytes) = read(path) else {
+    return Saved::Missing;
+  };
+  match serde_json::from_slice::<SavedMarker>(&bytes) {
+    Ok(saved) if integrity::verify(INTEGRITY_PURPOSE, &marker_data(&saved.day), &saved.checksum) => {
+      Saved::Valid(saved.day)
+    }
+    _ => Saved::Invalid,
+  }
+}
+
+/// The counters to start from. Only a first run, with neither file, starts
+/// fresh (or from `legacy`); any other gap counts as a used-up day, as does
+/// a day after `today`.
+fn restore(counters: Saved<Counters>, marker: Saved<String>, legacy: Option<Counters>, today: &str) -> Counters {
+  let used_up = || Counters::used_up(today.to_string());
+  let ahead = |day: &str| day > today;
+  match (counters, marker) {
+    (Saved::Valid(counters), marker)
+      if ahead(&counters.day) || matches!(&marker, Saved::Valid(day) if ahead(day)) =>
+    {
+      log::warn!("Saved usage is from a later day; today's free usage is used up");
+      used_up()
+    }
+    (Saved::Valid(counters), Saved::Valid(day)) if counters.day < day => {
+      log::warn!("Saved usage is older than the last day counted; today's free usage is used up");
+      used_up()
+    }
+    (Saved::Valid(counters), _) => counters,
+    (Saved::Missing, Saved::Missing) => legacy.unwrap_or_else(|| Counters::fresh(today.to_string())),
+    (Saved::Missing, _) => {
+      log::warn!("Saved usage is missing; today's free usage is used up");
+      used_up()
+    }
+    (Saved::Invalid, _) => {
+      log::warn!("Saved usage doesn't match its checksum; today's free usage is used up");
+      used_up()
+    }
+  }
+}
+
+fn save(path: &Path, counters: &Counters) -> Result<(), String> {
+  let saved = SavedCounters {
+    checksum: integrity::seal(INTEGRITY_PURPOSE, &counters.sealed_data()),
+    counters: counters.clone(),
+  };
+  let json = serde_json::to_vec_pretty(&saved).map_err(|e| e.to_string())?;
+  util::write_atomic(path, &json).map_err(|e| format!("Failed to save usage: {}", e))
+}
+
+fn save_marker(path: &Path, day: &str) -> Result<(), String> {
+  let saved = SavedMarker {
+    day: day.to_string(),
+    checksum: integrity::seal(INTEGRITY_PURPOSE, &marker_data(day)),
+  };
+  let json = serde_json::to_vec_pretty(&saved).map_err(|e| e.to_string())?;
+  util::write_atomic(path, &json).map_err(|e| format!("Failed to save usage: {}", e))
+}
+
+/// Drop the counters the frontend kept in `pro-settings.json`, returning
+/// them if they're from today.
+fn take_legacy_counters<R: Runtime>(app: &AppHandle<R>) -> Option<Counters> {
+  let store = app.store(LEGACY_STORE_FILE).ok()?;
+  let mut settings = store.get(LEGACY_STORE_KEY)?;
+  let settings_map = settings.as_object_mut()?;
+  let playtime = settings_map.remove("dailyPlaytimeUsed");
+  let day = settings_map.remove("lastPlayDate");
+  let trials = settings_map.remove("timerTrialUsed");
+  if playtime.is_none() && day.is_none() && trials.is_none() {
+    return None;
+  }
+  store.set(LEGACY_STORE_KEY, settings);
+  if let Err(e) = store.save() {
+    log::warn!("Failed to save {}: {}", LEGACY_STORE_FILE, e);
+  }
+
+  // The frontend dated them in UTC.
+  let today = today();
+  let utc_today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+  let day = day.as_ref().and_then(|d| d.as_str())?;
+  if day != today && day != utc_today {
+    return None;
+  }
+  Some(Counters {
+    day: today,
+    playtime_millis: playtime.and_then(|p| p.as_u64()).unwrap_or_default() * 1000,
+    timer_trials: trials.and_then(|t| t.as_u64()).unwrap_or_default() as u32,
+  })
+}
+
+fn persist<R: Runtime>(app: &AppHandle<R>) {
+  let state = app.state::<UsageState>();
+  let counters = match state.counters.lock() {
+    Ok(counters) => counters.clone(),
+    Err(_) => return,
+  };
+  let saved = [
+    state.path.as_deref().map(|path| save(path, &counters)),
+    state.marker_path.as_deref().map(|path| save_marker(path, &counters.day)),
+  ];
+  for e in saved.into_iter().flatten().filter_map(Result::err) {
+    log::warn!("{}", e);
+  }
+}
+
+fn emit<R: Runtime>(app: &AppHandle<R>, usage: &Usage) {
+  if let Err(e) = app.emit(USAGE_EVENT, usage) {
+    log::warn!("Failed to emit {}: {}", USAGE_EVENT, e);
+  }
+}
+
+/// Today's usage.
+pub fn usage<R: Runtime>(app: &AppHandle<R>) -> Usage {
+  let state = app.state::<UsageState>();
+  let Ok(mut counters) = state.counters.lock() else {
+    return Counters::used_up(today()).usage();
+  };
+  counters.roll_over(&today());
+  counters.usage()
+}
+
+/// Count playtime while sounds play, for free users.
+async fn count_playtime<R: Runtime>(app: AppHandle<R>) {
+  let playback = app.state::<AudioState>().subscribe_playback();
+  let mut tick = tokio::time::interval(TICK);
+  tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+  let mut last = Instant::now();
+  let mut last_save = Instant::now();
+  let mut unsaved = false;
+  loop {
+    tick.tick().await;
+    let now = Instant::now();
+    // A stalled or suspended app doesn't count more than a moment.
+    let elapsed = now.duration_since(last).min(TICK * 2);
+    last = now;
+    let counting =
+      playback.borrow().status() == PlaybackStatus::Playing && !license::status(&app).is_pro;
+
+    let state = app.state::<UsageState>();
+    let (before, after, rolled_over) = {
+      let Ok(mut counters) = state.counters.lock() else {
+        continue;
+      };
+      let before = counters.usage();
+      let rolled_over = counters.roll_over(&today());
+      if counting {
+        counters.playtime_millis = (counters.playtime_millis + elapsed.as_millis() as u64)
+          .min(DAILY_PLAYTIME_LIMIT as u64 * 1000);
+        unsaved = true;
+      }
+      (before, counters.usage(), rolled_over)
+    };
+
+    if rolled_over || (unsaved && (!counting || last_save.elapsed() >= SAVE_INTERVAL)) {
+      persist(&app);
+      unsaved = false;
+      last_save = now;
+    }
+    if after != before {
+      emit(&app, &after);
+    }
+  }
+}
+
+/// Count a completed timer session against the free trials.
+pub fn timer_completed<R: Runtime>(app: &AppHandle<R>) {
+  if license::status(app).is_pro {
+    return;
+  }
+  let state = app.state::<UsageState>();
+  let usage = {
+    let Ok(mut counters) = state.counters.lock() else {
+      return;
+    };
+    counters.roll_over(&today());
+    counters.timer_trials = (counters.timer_trials + 1).min(TIMER_TRIAL_LIMIT);
+    counters.usage()
+  };
+  persist(app);
+  emit(app, &usage);
+}
+
+/// Save playtime counted since the last save, before the app exits.
+pub fn flush<R: Runtime>(app: &AppHandle<R>) {
+  if app.try_state::<UsageState>().is_some() {
+    persist(app);
+  }
+}
+
+/// Load today's counters and start counting playtime.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+  let path = match app.path().app_config_dir() {
+    Ok(dir) => Some(dir.join(USAGE_FILE)),
+    Err(e) => {
+      log::warn!("No place for the usage file: {}", e);
+      None
+    }
+  };
+  let marker_path = match app.path().app_local_data_dir() {
+    Ok(dir) => Some(dir.join(MARKER_FILE)),
+    Err(e) => {
+      log::warn!("No place for the usage marker: {}", e);
+      None
+    }
+  };
+  let legacy = take_legacy_counters(app);
+  let counters = restore(
+    path.as_deref().map_or(Saved::Missing, load),
+    marker_path.as_deref().map_or(Saved::Missing, load_marker),
+    legacy,
+    &today(),
+  );
+  app.manage(UsageState {
+    counters: Mutex::new(counters),
+    path,
+    marker_path,
+  });
+  persist(app);
+  tauri::async_runtime::spawn(count_playtime(app.clone()));
+}
+
+#[tauri::command]
+pub fn get_usage<R: Runtime>(app: AppHandle<R>) -> Usage {
+  usage(&app)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::TempDir;
+
+  fn counters(day: &str, playtime_millis: u64, timer_trials: u32) -> Counters {
+    Counters {
+      day: day.to_string(),
+      playtime_millis,
+      timer_trials,
+    }
+  }
+
+  #[test]
+  fn saved_files_must_match_their_seal() {
+    let dir = TempDir::new();
+    let path = dir.join(USAGE_FILE);
+    let marker_path = dir.join(MARKER_FILE);
+    assert_eq!(load(&path), Saved::Missing);
+    assert_eq!(load_marker(&marker_path), Saved::Missing);
+
+    let saved = counters("2026-03-01", 5_000, 1);
+    save(&path, &saved).unwrap();
+    save_marker(&marker_path, "2026-03-01").unwrap();
+    assert_eq!(load(&path), Saved::Valid(saved));
+    assert_eq!(load_marker(&marker_path), Saved::Valid("2026-03-01".to_string()));
+
+    let edited = std::fs::read_to_string(&path).unwrap().replace("5000", "0");
+    std::fs::write(&path, edited).unwrap();
+    assert_eq!(load(&path), Saved::Invalid);
+    let edited = std::fs::read_to_string(&marker_path).unwrap().replace("03-01", "02-01");
+    std::fs::write(&marker_path, edited).unwrap();
+    assert_eq!(load_marker(&marker_path), Saved::Invalid);
+    std::fs::write(&path, "{").unwrap();
+    assert_eq!(load(&path), Saved::Invalid);
+  }
+
+  #[test]
+  fn only_a_first_run_starts_fresh() {
+    let today = "2026-03-02";
+    let used_up = Counters::used_up(today.to_string());
+    let marker = || Saved::Valid("2026-03-01".to_string());
+
+    assert_eq!(restore(Saved::Missing, Saved::Missing, None, today), Counters::fresh(today.to_string()));
+    let legacy = counters(today, 1_000, 1);
+    assert_eq!(restore(Saved::Missing, Saved::Missing, Some(legacy.clone()), today), legacy);
+    // Counters from before the marker existed.
+    let saved = counters("2026-03-01", 1_000, 0);
+    assert_eq!(restore(Saved::Valid(saved.clone()), Saved::Missing, None, today), saved);
+    assert_eq!(restore(Saved::Valid(saved.clone()), marker(), None, today), saved);
+
+    assert_eq!(restore(Saved::Missing, marker(), None, today), used_up);
+    assert_eq!(restore(Saved::Missing, Saved::Invalid, None, today), used_up);
+    assert_eq!(restore(Saved::Invalid, Saved::Missing, None, today), used_up);
+    assert_eq!(restore(Saved::Invalid, marker(), Some(legacy), today), used_up);
+    // A copy kept from an earlier day.
+    let stale = Saved::Valid(counters("2026-02-27", 0, 0));
+    assert_eq!(restore(stale, marker(), None, today), used_up);
+  }
+
+  #[test]
+  fn days_after_today_are_stale() {
+    let today = "2026-03-02";
+    let used_up = Counters::used_up(today.to_string());
+    let ahead = || Saved::Valid("2026-03-05".to_string());
+    // Left by a run with the clock set ahead, then set back.
+    let borrowed = || Saved::Valid(counters("2026-03-05", 0, 0));
+    assert_eq!(restore(borrowed(), ahead(), None, today), used_up);
+    assert_eq!(restore(borrowed(), Saved::Missing, None, today), used_up);
+    assert_eq!(restore(Saved::Valid(counters(today, 0, 0)), ahead(), None, today), used_up);
+    assert_eq!(restore(Saved::Missing, ahead(), None, today), used_up);
+  }
+
+  #[test]
+  fn roll_over_starts_a_later_day_and_uses_up_an_earlier_one() {
+    let mut counted = counters("2026-03-02", 5_000, 2);
+    assert!(!counted.roll_over("2026-03-02"));
+    assert_eq!(counted, counters("2026-03-02", 5_000, 2));
+    assert!(counted.roll_over("2026-03-03"));
+    assert_eq!(counted, Counters::fresh("2026-03-03".to_string()));
+    assert!(counted.roll_over("2026-03-01"));
+    assert_eq!(counted, Counters::used_up("2026-03-01".to_string()));
+  }
+
+  #[test]
+  fn usage_is_clamped_to_the_limits() {
+    let usage = counters("2026-03-02", 1_999, 1).usage();
+    assert_eq!((usage.playtime_seconds, usage.timer_trials), (1, 1));
+
+    let over = counters("2026-03-02", (DAILY_PLAYTIME_LIMIT as u64 + 10) * 1000, TIMER_TRIAL_LIMIT + 4);
+    assert_eq!(over.usage(), Counters::used_up("2026-03-02".to_string()).usage());
+    assert_eq!(over.usage().playtime_seconds, DAILY_PLAYTIME_LIMIT);
+    assert_eq!(over.usage().timer_trials, TIMER_TRIAL_LIMIT);
+  }
+}
@@ -0,0 +1,117 @@
+//! Small helpers shared by the modules that keep files and ids.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Unix time in milliseconds; 0 if the clock is before 1970.
+pub fn now_millis() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis() as u64)
+    .unwrap_or_default()
+}
+
+/// Lowercase hex, two digits per byte.
+pub fn hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// `len` random bytes as hex.
+pub fn random_hex(len: usize) -> Result<String, String> {
+  let mut bytes = vec![0u8; len];
+  getrandom::fill(&mut bytes).map_err(|e| e.to_string())?;
+  Ok(hex(&bytes))
+}
+
+fn temporary_path(path: &Path) -> PathBuf {
+  let mut name = path.file_name().unwrap_or_default().to_os_string();
+  name.push(".tmp");
+  path.with_file_name(name)
+}
+
+fn write_through_temporary(path: &Path, bytes: &[u8], private: bool) -> std::io::Result<()> {
+  if let Some(dir) = path.parent() {
+    std::fs::create_dir_all(dir)?;
+  }
+  let tmp = temporary_path(path);
+  let mut options = std::fs::OpenOptions::new();
+  options.write(true).create(true).truncate(true);
+  #[cfg(unix)]
+  if private {
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+  }
+  #[cfg(not(unix))]
+  let _ = private;
+  options.open(&tmp)?.write_all(bytes)?;
+  std::fs::rename(&tmp, path)
+}
+
+/// Replace `path` with `bytes`, creating its folder. Readers see the old
+/// file or the new one, never part of it, even if the app dies mid-write.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+  write_through_temporary(path, bytes, false)
+}
+
+/// [`write_atomic`] for secrets: on Unix only the user can read the file.
+pub fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+  write_through_temporary(path, bytes, true)
+}
+
+#[derive(Serialize, Deserialize)]
+struct Switch {
+  enabled: bool,
+}
+
+/// An on/off setting kept in a file of its own rather than the plugin
+/// store, which the webview can write. `None` if it was never saved.
+pub fn read_switch(path: &Path) -> Option<bool> {
+  let bytes = std::fs::read(path).ok()?;
+  match serde_json::from_slice::<Switch>(&bytes) {
+    Ok(switch) => Some(switch.enabled),
+    Err(e) => {
+      log::warn!("Ignoring unreadable {}: {}", path.display(), e);
+      None
+    }
+  }
+}
+
+pub fn write_switch(path: &Path, enabled: bool) -> std::io::Result<()> {
+  let json = serde_json::to_vec_pretty(&Switch { enabled }).map_err(std::io::Error::other)?;
+  write_atomic(path, &json)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::TempDir;
+
+  #[test]
+  fn hex_pads_each_byte() {
+    assert_eq!(hex(&[0x00, 0x0f, 0xab]), "000fab");
+    assert_eq!(random_hex(8).unwrap().len(), 16);
+  }
+
+  #[test]
+  fn write_atomic_replaces_the_file_and_leaves_no_temporary() {
+    let dir = TempDir::new();
+    let path = dir.join("nested").join("file.json");
+    write_atomic(&path, b"first").unwrap();
+    write_atomic(&path, b"second").unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"second");
+    assert!(!temporary_path(&path).exists());
+  }
+
+  #[test]
+  fn switch_reads_back_what_was_written() {
+    let dir = TempDir::new();
+    let path = dir.join("switch.json");
+    assert_eq!(read_switch(&path), None);
+    write_switch(&path, true).unwrap();
+    assert_eq!(read_switch(&path), Some(true));
+    std::fs::write(&path, "{").unwrap();
+    assert_eq!(read_switch(&path), None);
+  }
+}
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use reqwest::StatusCode;
+use sha2::Sha256;
+
+use super::outbox::Delivery;
+use crate::util;
+
+pub const SIGNATURE_HEADER: &str = "X-ZeroHz-Signature";
+pub const EVENT_HEADER: &str = "X-ZeroHz-Event";
+pub const DELIVERY_HEADER: &str = "X-ZeroHz-Delivery";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+pub enum Outcome {
+  Delivered,
+  /// The endpoint refused the payload; retrying won't help.
+  Rejected(StatusCode),
+  /// Offline, timed out or a server error; worth retrying.
+  Failed(String),
+}
+
+/// `t=<unix seconds>,v1=<hex HMAC-SHA256 of "<t>.<body>">`. Receivers
+/// recompute it with the webhook's secret and should reject stale `t`s.
+pub fn signature(secret: &str, timestamp: u64, body: &str) -> String {
+  let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+  mac.update(timestamp.to_string().as_bytes());
+  mac.update(b".");
+  mac.update(body.as_bytes());
+  let digest = util::hex(&mac.finalize().into_bytes());
+  format!("t={},v1={}", timestamp, digest)
+}
+
+pub fn client(user_agent: &str) -> reqwest::Client {
+  reqwest::Client::builder()
+    .timeout(TIMEOUT)
+    .user_agent(user_agent)
+    // A redirect would turn the POST into a GET; treat it as a rejection.
+    .redirect(reqwest::redirect::Policy::none())
+    .build()
+    .unwrap_or_default()
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+  status.is_server_error() || status == StatusCode::REQUEST_TIMEOUT || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// POST `delivery` to `url`, signed with `secret` at `now` (Unix seconds).
+pub async fn send(client: &reqwest::Client, url: &str, secret: &str, delivery: &Delivery, now: u64) -> Outcome {
+  let result = client
+    .post(url)
+    .header(reqwest::header::CONTENT_TYPE, "application/json")
+    .header(EVENT_HEADER, &delivery.event)
+    .header(DELIVERY_HEADER, &delivery.id)
+    .header(SIGNATURE_HEADER, signature(secret, now, &delivery.body))
+    .body(delivery.body.clone())
+    .send()
+    .await;
+  match result {
+    Ok(response) if response.status().is_success() => Outcome::Delivered,
+    Ok(response) if is_retryable(response.status()) => Outcome::Failed(response.status().to_string()),
+    Ok(response) => Outcome::Rejected(response.status()),
+    Err(e) => Outcome::Failed(e.to_string()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::mock_http::{self, Mock};
+
+  const SECRET: &str = "whsec";
+  const BODY: &str = r#"{"event":"session.completed"}"#;
+
+  fn delivery() -> Delivery {
+    Delivery {
+      id: "d1".to_string(),
+      webhook_id: "w1".to_string(),
+      event: "session.completed".to_string(),
+      body: BODY.to_string(),
+      created_at: 0,
+      attempts: 0,
+      next_attempt_at: 0,
+    }
+  }
+
+  /// Send to `mock` once it answers with `status`.
+  async fn send_to(mock: &Mock, status: u16) -> Outcome {
+    mock.reply(status, "");
+    send(&client("test"), &mock.url("/hook"), SECRET, &delivery(), 1_700_000_000).await
+  }
+
+  #[test]
+  fn signature_is_timestamp_and_hmac_of_the_signed_payload() {
+    assert_eq!(
+      signature(SECRET, 1_700_000_000, BODY),
+      "t=1700000000,v1=7b2143704a43ef4bde82c17b94078ad7399e7657ea2fd5a278d04da0b283e130"
+    );
+  }
+
+  #[tokio::test]
+  async fn sends_the_body_with_signed_headers() {
+    let mock = Mock::start().await;
+    assert!(matches!(send_to(&mock, 204).await, Outcome::Delivered));
+
+    let mock_http::Request { method, headers, body, .. } = mock.last_request();
+    assert_eq!(method, "POST");
+    assert_eq!(body, BODY);
+    assert_eq!(headers[EVENT_HEADER], "session.completed");
+    assert_eq!(headers[DELIVERY_HEADER], "d1");
+    assert_eq!(headers[SIGNATURE_HEADER], signature(SECRET, 1_700_000_000, BODY).as_str());
+  }
+
+  #[tokio::test]
+  async fn server_errors_and_rate_limits_are_retried() {
+    let mock = Mock::start().await;
+    for status in [500, 503, 429, 408] {
+      assert!(matches!(send_to(&mock, status).await, Outcome::Failed(_)), "{}", status);
+    }
+  }
+
+  #[tokio::test]
+  async fn other_client_errors_and_redirects_are_rejected() {
+    let mock = Mock::start().await;
+    for status in [400, 401, 404, 410, 302] {
+      match send_to(&mock, status).await {
+        Outcome::Rejected(code) => assert_eq!(code.as_u16(), status),
+        _ => panic!("{} should be rejected", status),
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn unreachable_endpoints_are_retried() {
+    let url = format!("http://{}/hook", mock_http::closed_address());
+    let outcome = send(&client("test"), &url, SECRET, &delivery(), 1_700_000_000).await;
+    assert!(matches!(outcome, Outcome::Failed(_)));
+  }
+}
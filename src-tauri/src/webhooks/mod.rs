@@ -0,0 +1,285 @@
+//! Outgoing webhooks: session events POSTed as signed JSON to URLs the user
+//! adds in the Webhooks panel (see `docs/06-webhooks.md`).
+//!
+//! Events go into a persistent [`Outbox`] first, and a background task
+//! delivers them, retrying with backoff while the endpoint is unreachable.
+
+mod delivery;
+mod outbox;
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime, Url};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::Notify;
+
+use crate::timer::{SessionEvent, TimerSession};
+use crate::util::{now_millis, random_hex};
+use delivery::Outcome;
+use outbox::{Delivery, Outbox};
+
+const WEBHOOKS_STORE_FILE: &str = "webhooks.json";
+const WEBHOOKS_STORE_KEY: &str = "webhooks";
+/// Pending deliveries live in `<app data>/webhook-outbox.json`.
+const OUTBOX_FILE: &str = "webhook-outbox.json";
+const MAX_WEBHOOKS: usize = 10;
+const MAX_URL_LEN: usize = 2048;
+
+/// An endpoint events are sent to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+  pub id: String,
+  pub url: String,
+  /// HMAC key for the `X-ZeroHz-Signature` header.
+  pub secret: String,
+  pub enabled: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookInfo {
+  #[serde(flatten)]
+  pub webhook: Webhook,
+  /// Deliveries waiting to be retried.
+  pub pending: usize,
+}
+
+fn event_name(event: SessionEvent) -> &'static str {
+  match event {
+    SessionEvent::Started => "session.started",
+    SessionEvent::Paused => "session.paused",
+    SessionEvent::Resumed => "session.resumed",
+    SessionEvent::Completed => "session.completed",
+    SessionEvent::Abandoned => "session.abandoned",
+  }
+}
+
+/// Sent as the request body.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload<'a> {
+  /// Same as `X-ZeroHz-Delivery`, and unchanged across retries.
+  id: &'a str,
+  event: &'a str,
+  /// Unix time in milliseconds.
+  created_at: u64,
+  app_version: &'a str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  session: Option<&'a TimerSession>,
+}
+
+pub struct WebhookState {
+  outbox: Mutex<Outbox>,
+  /// Wakes the dispatcher when something is queued.
+  wake: Notify,
+}
+
+fn webhooks<R: Runtime>(app: &AppHandle<R>) -> Vec<Webhook> {
+  app
+    .store(WEBHOOKS_STORE_FILE)
+    .ok()
+    .and_then(|s| s.get(WEBHOOKS_STORE_KEY))
+    .and_then(|v| serde_json::from_value(v).ok())
+    .unwrap_or_default()
+}
+
+fn save_webhooks<R: Runtime>(app: &AppHandle<R>, webhooks: &[Webhook]) -> Result<(), String> {
+  let store = app.store(WEBHOOKS_STORE_FILE).map_err(|e| e.to_string())?;
+  store.set(WEBHOOKS_STORE_KEY, serde_json::to_value(webhooks).map_err(|e| e.to_string())?);
+  store.save().map_err(|e| e.to_string())
+}
+
+fn validate_url(url: &str) -> Result<(), String> {
+  if url.len() > MAX_URL_LEN {
+    return Err("URL is too long".to_string());
+  }
+  let parsed = Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+  if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+    return Err("Webhook URLs must start with http:// or https://".to_string());
+  }
+  Ok(())
+}
+
+/// Queue `event` for `targets` and wake the dispatcher.
+fn enqueue<R: Runtime>(
+  app: &AppHandle<R>,
+  targets: &[Webhook],
+  event: &str,
+  session: Option<&TimerSession>,
+) -> Result<(), String> {
+  let state = app.state::<WebhookState>();
+  let now = now_millis();
+  let version = app.package_info().version.to_string();
+  {
+    let mut outbox = state.outbox.lock().map_err(|e| e.to_string())?;
+    for webhook in targets {
+      let id = random_hex(16)?;
+      let body = serde_json::to_string(&Payload {
+        id: &id,
+        event,
+        created_at: now,
+        app_version: &version,
+        session,
+      })
+      .map_err(|e| e.to_string())?;
+      outbox.push(Delivery {
+        id,
+        webhook_id: webhook.id.clone(),
+        event: event.to_string(),
+        body,
+        created_at: now,
+        attempts: 0,
+        next_attempt_at: now,
+      });
+    }
+  }
+  state.wake.notify_one();
+  Ok(())
+}
+
+/// Deliver everything that's due, then sleep until the next retry or until
+/// something new is queued.
+async fn dispatch<R: Runtime>(app: AppHandle<R>) {
+  let user_agent = format!("ZeroHz/{}", app.package_info().version);
+  let client = delivery::client(&user_agent);
+  let state = app.state::<WebhookState>();
+  loop {
+    let due = match state.outbox.lock() {
+      Ok(outbox) => outbox.due(now_millis()),
+      Err(_) => return,
+    };
+    let webhooks = webhooks(&app);
+    for item in due {
+      // Removed or switched off since the event was queued.
+      let Some(webhook) = webhooks.iter().find(|w| w.id == item.webhook_id && w.enabled) else {
+        if let Ok(mut outbox) = state.outbox.lock() {
+          outbox.remove(&item.id);
+        }
+        continue;
+      };
+
+      let outcome = delivery::send(&client, &webhook.url, &webhook.secret, &item, now_millis() / 1000).await;
+      let Ok(mut outbox) = state.outbox.lock() else {
+        return;
+      };
+      match outcome {
+        Outcome::Delivered => outbox.remove(&item.id),
+        Outcome::Rejected(status) => {
+          log::warn!("Webhook {} rejected {} ({}); dropping it", webhook.url, item.event, status);
+          outbox.remove(&item.id);
+        }
+        Outcome::Failed(e) => {
+          if !outbox.retry_later(&item.id, now_millis()) {
+            log::warn!("Giving up on {} to {}: {}", item.event, webhook.url, e);
+          }
+        }
+      }
+    }
+
+    let next = state.outbox.lock().ok().and_then(|o| o.next_attempt_at());
+    match next {
+      Some(at) => {
+        let wait = Duration::from_millis(at.saturating_sub(now_millis()));
+        tokio::select! {
+          _ = tokio::time::sleep(wait) => {}
+          _ = state.wake.notified() => {}
+        }
+      }
+      None => state.wake.notified().await,
+    }
+  }
+}
+
+/// Load the outbox and start delivering, including whatever was left
+/// undelivered last time.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+  let path = app
+    .path()
+    .app_data_dir()
+    .map(|dir| dir.join(OUTBOX_FILE))
+    .unwrap_or_else(|_| std::env::temp_dir().join(OUTBOX_FILE));
+  app.manage(WebhookState {
+    outbox: Mutex::new(Outbox::load(path)),
+    wake: Notify::new(),
+  });
+  tauri::async_runtime::spawn(dispatch(app.clone()));
+}
+
+#[tauri::command]
+pub fn list_webhooks<R: Runtime>(app: AppHandle<R>, state: tauri::State<WebhookState>) -> Result<Vec<WebhookInfo>, String> {
+  let outbox = state.outbox.lock().map_err(|e| e.to_string())?;
+  Ok(
+    webhooks(&app)
+      .into_iter()
+      .map(|webhook| WebhookInfo {
+        pending: outbox.pending(&webhook.id),
+        webhook,
+      })
+      .collect(),
+  )
+}
+
+/// Add an endpoint with a new signing secret.
+#[tauri::command]
+pub fn add_webhook<R: Runtime>(app: AppHandle<R>, url: String) -> Result<Webhook, String> {
+  let url = url.trim().to_string();
+  validate_url(&url)?;
+  let mut list = webhooks(&app);
+  if list.len() >= MAX_WEBHOOKS {
+    return Err(format!("At most {} webhooks can be added", MAX_WEBHOOKS));
+  }
+  if list.iter().any(|w| w.url == url) {
+    return Err("This URL is already added".to_string());
+  }
+  let webhook = Webhook {
+    id: random_hex(8)?,
+    url,
+    secret: random_hex(32)?,
+    enabled: true,
+  };
+  list.push(webhook.clone());
+  save_webhooks(&app, &list)?;
+  Ok(webhook)
+}
+
+#[tauri::command]
+pub fn remove_webhook<R: Runtime>(app: AppHandle<R>, state: tauri::State<WebhookState>, id: String) -> Result<(), String> {
+  let mut list = webhooks(&app);
+  list.retain(|w| w.id != id);
+  save_webhooks(&app, &list)?;
+  state.outbox.lock().map_err(|e| e.to_string())?.remove_webhook(&id);
+  Ok(())
+}
+
+#[tauri::command]
+pub fn set_webhook_enabled<R: Runtime>(app: AppHandle<R>, id: String, enabled: bool) -> Result<(), String> {
+  let mut list = webhooks(&app);
+  let webhook = list
+    .iter_mut()
+    .find(|w| w.id == id)
+    .ok_or_else(|| format!("Unknown webhook '{}'", id))?;
+  webhook.enabled = enabled;
+  save_webhooks(&app, &list)
+}
+
+/// Queue a `ping` event so the receiving end can be checked.
+#[tauri::command]
+pub fn send_test_webhook<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+  let webhook = webhooks(&app)
+    .into_iter()
+    .find(|w| w.id == id)
+    .ok_or_else(|| format!("Unknown webhook '{}'", id))?;
+  enqueue(&app, &[webhook], "ping", None)
+}
+
+/// Queue `event` for every enabled webhook.
+pub fn session_event<R: Runtime>(app: &AppHandle<R>, event: SessionEvent, session: &TimerSession) -> Result<(), String> {
+  let targets: Vec<Webhook> = webhooks(app).into_iter().filter(|w| w.enabled).collect();
+  if targets.is_empty() {
+    return Ok(());
+  }
+  enqueue(app, &targets, event_name(event), Some(session))
+}
@@ -0,0 +1,204 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::util;
+
+/// Deliveries older than this are dropped instead of retried.
+const MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// Oldest deliveries are dropped beyond this, so a dead endpoint can't grow
+/// the file forever.
+const MAX_PENDING: usize = 1000;
+const FIRST_RETRY: Duration = Duration::from_secs(15);
+const MAX_RETRY: Duration = Duration::from_secs(60 * 60);
+
+/// One event waiting to be delivered to one webhook. The body is kept as
+/// sent, so retries carry the same payload and delivery id.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Delivery {
+  pub id: String,
+  pub webhook_id: String,
+  /// `session.completed`
+  pub event: String,
+  pub body: String,
+  /// Unix time in milliseconds.
+  pub created_at: u64,
+  pub attempts: u32,
+  pub next_attempt_at: u64,
+}
+
+/// Wait before retrying after `attempts` failed attempts: 15s, 30s, 1m, ...
+/// up to an hour.
+pub fn backoff(attempts: u32) -> Duration {
+  FIRST_RETRY
+    .checked_mul(1 << attempts.saturating_sub(1).min(16))
+    .unwrap_or(MAX_RETRY)
+    .min(MAX_RETRY)
+}
+
+/// Deliveries not yet accepted by their endpoint, saved to disk on every
+/// change so they survive a restart while offline.
+pub struct Outbox {
+  path: PathBuf,
+  deliveries: Vec<Delivery>,
+}
+
+impl Outbox {
+  /// Load the outbox at `path`; a missing or unreadable file starts empty.
+  pub fn load(path: PathBuf) -> Self {
+    let deliveries = std::fs::read(&path)
+      .ok()
+      .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+      .unwrap_or_default();
+    Self { path, deliveries }
+  }
+
+  fn save(&self) {
+    if let Err(e) = write(&self.path, &self.deliveries) {
+      log::warn!("Failed to save the webhook outbox: {}", e);
+    }
+  }
+
+  pub fn push(&mut self, delivery: Delivery) {
+    self.deliveries.push(delivery);
+    if self.deliveries.len() > MAX_PENDING {
+      let excess = self.deliveries.len() - MAX_PENDING;
+      log::warn!("Webhook outbox is full; dropping {} oldest deliveries", excess);
+      self.deliveries.drain(..excess);
+    }
+    self.save();
+  }
+
+  /// Deliveries whose next attempt is at or before `now`.
+  pub fn due(&self, now: u64) -> Vec<Delivery> {
+    self
+      .deliveries
+      .iter()
+      .filter(|d| d.next_attempt_at <= now)
+      .cloned()
+      .collect()
+  }
+
+  /// When the next delivery is due, if any are pending.
+  pub fn next_attempt_at(&self) -> Option<u64> {
+    self.deliveries.iter().map(|d| d.next_attempt_at).min()
+  }
+
+  pub fn pending(&self, webhook_id: &str) -> usize {
+    self.deliveries.iter().filter(|d| d.webhook_id == webhook_id).count()
+  }
+
+  pub fn remove(&mut self, delivery_id: &str) {
+    self.deliveries.retain(|d| d.id != delivery_id);
+    self.save();
+  }
+
+  pub fn remove_webhook(&mut self, webhook_id: &str) {
+    self.deliveries.retain(|d| d.webhook_id != webhook_id);
+    self.save();
+  }
+
+  /// Schedule another attempt after a failure. Returns false if the
+  /// delivery is too old to retry and was dropped.
+  pub fn retry_later(&mut self, delivery_id: &str, now: u64) -> bool {
+    let Some(index) = self.deliveries.iter().position(|d| d.id == delivery_id) else {
+      return false;
+    };
+    let delivery = &mut self.deliveries[index];
+    delivery.attempts += 1;
+    let kept = now.saturating_sub(delivery.created_at) < MAX_AGE.as_millis() as u64;
+    if kept {
+      delivery.next_attempt_at = now + backoff(delivery.attempts).as_millis() as u64;
+    } else {
+      self.deliveries.remove(index);
+    }
+    self.save();
+    kept
+  }
+}
+
+fn write(path: &Path, deliveries: &[Delivery]) -> Result<(), String> {
+  let bytes = serde_json::to_vec(deliveries).map_err(|e| e.to_string())?;
+  util::write_atomic(path, &bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::TempDir;
+
+  const HOUR_MILLIS: u64 = 60 * 60 * 1000;
+
+  fn delivery(id: &str, webhook_id: &str, created_at: u64) -> Delivery {
+    Delivery {
+      id: id.to_string(),
+      webhook_id: webhook_id.to_string(),
+      event: "session.completed".to_string(),
+      body: "{}".to_string(),
+      created_at,
+      attempts: 0,
+      next_attempt_at: created_at,
+    }
+  }
+
+  #[test]
+  fn backoff_doubles_up_to_an_hour() {
+    assert_eq!(backoff(1), Duration::from_secs(15));
+    assert_eq!(backoff(2), Duration::from_secs(30));
+    assert_eq!(backoff(3), Duration::from_secs(60));
+    assert_eq!(backoff(20), MAX_RETRY);
+    assert_eq!(backoff(u32::MAX), MAX_RETRY);
+  }
+
+  #[test]
+  fn survives_a_reload() {
+    let dir = TempDir::new();
+    let path = dir.join("outbox.json");
+    let mut outbox = Outbox::load(path.clone());
+    outbox.push(delivery("d1", "w1", 0));
+    outbox.push(delivery("d2", "w2", 0));
+    assert!(outbox.retry_later("d1", 1000));
+
+    let reloaded = Outbox::load(path.clone());
+    assert_eq!(reloaded.deliveries, outbox.deliveries);
+    assert_eq!(reloaded.deliveries[0].attempts, 1);
+    assert_eq!(reloaded.deliveries[0].next_attempt_at, 1000 + 15_000);
+  }
+
+  #[test]
+  fn unreadable_file_starts_empty() {
+    let dir = TempDir::new();
+    let path = dir.join("outbox.json");
+    util::write_atomic(&path, b"not json").unwrap();
+    assert!(Outbox::load(path.clone()).deliveries.is_empty());
+  }
+
+  #[test]
+  fn only_due_deliveries_are_returned() {
+    let dir = TempDir::new();
+    let path = dir.join("outbox.json");
+    let mut outbox = Outbox::load(path.clone());
+    outbox.push(delivery("d1", "w1", 0));
+    outbox.push(delivery("d2", "w1", 0));
+    outbox.retry_later("d2", 0);
+    assert_eq!(outbox.due(0).len(), 1);
+    assert_eq!(outbox.next_attempt_at(), Some(0));
+    outbox.remove("d1");
+    assert_eq!(outbox.next_attempt_at(), Some(15_000));
+    assert_eq!(outbox.due(15_000).len(), 1);
+    assert_eq!(outbox.pending("w1"), 1);
+  }
+
+  #[test]
+  fn old_deliveries_are_dropped_instead_of_retried() {
+    let dir = TempDir::new();
+    let path = dir.join("outbox.json");
+    let mut outbox = Outbox::load(path.clone());
+    outbox.push(delivery("d1", "w1", 0));
+    assert!(outbox.retry_later("d1", HOUR_MILLIS));
+    assert!(!outbox.retry_later("d1", MAX_AGE.as_millis() as u64));
+    assert_eq!(outbox.pending("w1"), 0);
+  }
+}
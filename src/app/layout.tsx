@@ -4,7 +4,6 @@ import { CSPostHogProvider } from "../components/providers";
 import I18nProvider from "@/components/providers/I18nProvider";
 import { ProProvider } from "@/components/providers/ProProvider";
 import TrayLanguageSync from "@/components/providers/TrayLanguageSync";
-import TrayProSync from "@/components/providers/TrayProSync";
 import "./globals.css";
 
 const geistSans = Geist({
@@ -36,7 +35,6 @@ export default function RootLayout({
           <I18nProvider>
             <ProProvider>
               <TrayLanguageSync />
-              <TrayProSync />
               {children}
             </ProProvider>
           </I18nProvider>
@@ -0,0 +1,189 @@
+"use client";
+
+import { useEffect, useRef } from "react";
+import { useTranslation } from "react-i18next";
+import { X, Check, Speaker } from "lucide-react";
+import { cn } from "@/lib/utils";
+import { useAudioDevices } from "@/hooks";
+
+interface AudioOutputModalProps {
+  onClose: () => void;
+}
+
+const MODAL_WINDOW_SIZE = { width: 400, height: 360 };
+
+export function AudioOutputModal({ onClose }: AudioOutputModalProps) {
+  const { t } = useTranslation();
+  const { devices, status, selectDevice } = useAudioDevices();
+  const originalSizeRef = useRef<{ width: number; height: number } | null>(
+    null
+  );
+  const originalPositionRef = useRef<{ x: number; y: number } | null>(null);
+
+  // 모달 열릴 때 윈도우 크기 확장 및 중앙 배치
+  useEffect(() => {
+    const expandWindow = async () => {
+      try {
+        const {
+          getCurrentWindow,
+          LogicalSize,
+          PhysicalPosition,
+          currentMonitor,
+        } = await import("@tauri-apps/api/window");
+        const appWindow = getCurrentWindow();
+
+        // 현재 크기 및 위치 저장
+        const currentSize = await appWindow.outerSize();
+        const currentPos = await appWindow.outerPosition();
+
+        originalSizeRef.current = {
+          width: currentSize.width,
+          height: currentSize.height,
+        };
+        originalPositionRef.current = {
+          x: currentPos.x,
+          y: currentPos.y,
+        };
+
+        // 모달 크기로 확장
+        await appWindow.setSize(
+          new LogicalSize(MODAL_WINDOW_SIZE.width, MODAL_WINDOW_SIZE.height)
+        );
+
+        // 화면 상단 중앙으로 이동
+        const monitor = await currentMonitor();
+        if (monitor) {
+          const scaleFactor = monitor.scaleFactor;
+          const monitorSize = monitor.size;
+          const monitorPosition = monitor.position;
+
+          const physicalWidth = MODAL_WINDOW_SIZE.width * scaleFactor;
+
+          const x =
+            monitorPosition.x +
+            Math.round((monitorSize.width - physicalWidth) / 2);
+          const y = monitorPosition.y + 50;
+
+          await appWindow.setPosition(new PhysicalPosition(x, y));
+        }
+      } catch {
+        console.log("Not in Tauri environment");
+      }
+    };
+
+    expandWindow();
+
+    return () => {
+      const restoreWindow = async () => {
+        if (originalSizeRef.current && originalPositionRef.current) {
+          try {
+            const { getCurrentWindow, PhysicalSize, PhysicalPosition } =
+              await import("@tauri-apps/api/window");
+            const appWindow = getCurrentWindow();
+
+            await appWindow.setSize(
+              new PhysicalSize(
+                originalSizeRef.current.width,
+                originalSizeRef.current.height
+              )
+            );
+
+            await appWindow.setPosition(
+              new PhysicalPosition(
+                originalPositionRef.current.x,
+                originalPositionRef.current.y
+              )
+            );
+          } catch {
+            console.log("Failed to restore window size/position");
+          }
+        }
+      };
+      restoreWindow();
+    };
+  }, []);
+
+  // ESC 키로 닫기
+  useEffect(() => {
+    function handleEscape(event: KeyboardEvent) {
+      if (event.key === "Escape") {
+        onClose();
+      }
+    }
+
+    document.addEventListener("keydown", handleEscape);
+    return () => document.removeEventListener("keydown", handleEscape);
+  }, [onClose]);
+
+  const selected = status?.selected ?? null;
+  // 선택한 장치가 분리되어 기본 장치로 대체 출력 중인 경우
+  const isFallback =
+    selected !== null && !!status?.active && status.active !== selected;
+
+  const options: { name: string | null; label: string }[] = [
+    {
+      name: null,
+      label: status?.defaultDevice
+        ? `${t("audioOutput.systemDefault")} (${status.defaultDevice})`
+        : t("audioOutput.systemDefault"),
+    },
+    ...devices.map((device) => ({ name: device.name, label: device.name })),
+  ];
+  // 분리된 장치도 선택 상태를 보여줄 수 있도록 목록에 유지
+  if (selected !== null && !devices.some((d) => d.name === selected)) {
+    options.push({ name: selected, label: selected });
+  }
+
+  return (
+    <div className="fixed inset-0 z-50 flex flex-col bg-[#1A1A1A] overflow-hidden rounded-2xl border border-white/10">
+      {/* 헤더 */}
+      <div className="flex items-center justify-between p-4 border-b border-white/10">
+        <h3 className="text-white text-sm font-semibold">
+          {t("audioOutput.title")}
+        </h3>
+        <button
+          onClick={onClose}
+          className="text-white/50 hover:text-white p-1 rounded-full hover:bg-white/10 transition-all"
+        >
+          <X size={16} />
+        </button>
+      </div>
+
+      <div className="flex-1 p-4 overflow-auto space-y-1">
+        {devices.length === 0 && (
+          <p className="text-white/50 text-sm text-center py-4">
+            {t("audioOutput.noDevices")}
+          </p>
+        )}
+        {options.map((option) => {
+          const isSelected = option.name === selected;
+          return (
+            <button
+              key={option.name ?? "__default__"}
+              onClick={() => selectDevice(option.name)}
+              className={cn(
+                "w-full flex items-center gap-3 px-3 py-2 rounded-lg text-left text-sm transition-all",
+                isSelected
+                  ? "bg-white/10 text-white"
+                  : "text-white/70 hover:bg-white/5 hover:text-white"
+              )}
+            >
+              <Speaker size={16} className="shrink-0" />
+              <span className="flex-1 truncate">{option.label}</span>
+              {isSelected && <Check size={16} className="shrink-0" />}
+            </button>
+          );
+        })}
+      </div>
+
+      {/* 현재 출력 장치 */}
+      {status?.active && (
+        <div className="px-4 py-3 border-t border-white/10 text-xs text-white/50 truncate">
+          {isFallback
+            ? t("audioOutput.unavailable", { device: status.active })
+            : t("audioOutput.current", { device: status.active })}
+        </div>
+      )}
+    </div>
+  );
+}
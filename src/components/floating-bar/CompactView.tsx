@@ -4,8 +4,7 @@ import { useState } from "react";
 import { useTranslation } from "react-i18next";
 import { Play, Pause, Maximize2, Timer, Clock } from "lucide-react";
 import { cn } from "@/lib/utils";
-import { SOUNDS } from "@/constants/sounds";
-import type { SoundType } from "@/types/audio";
+import type { SoundControl, SoundType } from "@/types/audio";
 import type {
   TimerMode,
   TimerPreset,
@@ -18,6 +17,7 @@ import { TimerSettingsModal } from "./TimerSettingsModal";
  * CompactView - 플로팅 바의 축소된 뷰 컴포넌트
  */
 interface CompactViewProps {
+  sounds: SoundControl[];
   activeSounds: Set<SoundType>;
   isPlaying: boolean;
   formattedTime: string;
@@ -36,6 +36,7 @@ interface CompactViewProps {
 }
 
 export function CompactView({
+  sounds,
   activeSounds,
   isPlaying,
   formattedTime,
@@ -137,7 +138,7 @@ export function CompactView({
         <>
           <div className="w-px h-3 bg-white/10" />
           <div className="flex items-center gap-1.5">
-            {SOUNDS.filter((s) => activeSounds.has(s.id)).map((sound) => {
+            {sounds.filter((s) => activeSounds.has(s.id)).map((sound) => {
               const Icon = sound.icon;
               return (
                 <Icon key={sound.id} size={12} className="text-white/70" />
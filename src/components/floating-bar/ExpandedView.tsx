@@ -8,12 +8,25 @@ import {
   ChevronLeft,
   ChevronRight,
   Minimize2,
+  Plus,
 } from "lucide-react";
+import { useTranslation } from "react-i18next";
 import { cn } from "@/lib/utils";
 import { SoundButton } from "./SoundButton";
 import { TimerControl } from "./TimerControl";
-import { SOUNDS, ITEMS_PER_SLIDE } from "@/constants/sounds";
-import type { SoundType, SoundVolumes } from "@/types/audio";
+import {
+  ITEMS_PER_SLIDE,
+  DEFAULT_VOLUME,
+  DEFAULT_EFFECT,
+} from "@/constants/sounds";
+import type {
+  CustomSoundId,
+  SoundControl,
+  SoundEffect,
+  SoundEffects,
+  SoundType,
+  SoundVolumes,
+} from "@/types/audio";
 import type {
   TimerMode,
   TimerPreset,
@@ -25,13 +38,18 @@ import type {
  * ExpandedView - 플로팅 바의 확장된 뷰 컴포넌트
  */
 interface ExpandedViewProps {
+  sounds: SoundControl[];
   activeSounds: Set<SoundType>;
   volumes: SoundVolumes;
+  effects: SoundEffects;
   isMuted: boolean;
   isPlaying: boolean;
   currentSlide: number;
   onToggleSound: (id: SoundType) => void;
   onVolumeChange: (id: SoundType, value: number) => void;
+  onEffectChange: (id: SoundType, effect: Partial<SoundEffect>) => void;
+  onImportSound: () => void;
+  onRemoveSound: (id: CustomSoundId) => void;
   onToggleMute: () => void;
   onTogglePlayPause: () => void;
   onPrevSlide: () => void;
@@ -57,13 +75,18 @@ interface ExpandedViewProps {
 }
 
 export function ExpandedView({
+  sounds,
   activeSounds,
   volumes,
+  effects,
   isMuted,
   isPlaying,
   currentSlide,
   onToggleSound,
   onVolumeChange,
+  onEffectChange,
+  onImportSound,
+  onRemoveSound,
   onToggleMute,
   onTogglePlayPause,
   onPrevSlide,
@@ -87,7 +110,8 @@ export function ExpandedView({
   timerStats,
   onClearSessions,
 }: ExpandedViewProps) {
-  const visibleSounds = SOUNDS.slice(
+  const { t } = useTranslation();
+  const visibleSounds = sounds.slice(
     currentSlide * ITEMS_PER_SLIDE,
     (currentSlide + 1) * ITEMS_PER_SLIDE
   );
@@ -110,13 +134,30 @@ export function ExpandedView({
             key={sound.id}
             sound={sound}
             isActive={activeSounds.has(sound.id)}
-            volume={volumes[sound.id]}
+            volume={volumes[sound.id] ?? DEFAULT_VOLUME}
+            effect={effects[sound.id] ?? DEFAULT_EFFECT}
             onToggle={onToggleSound}
             onVolumeChange={onVolumeChange}
+            onEffectChange={onEffectChange}
+            onRemove={
+              sound.isCustom
+                ? () => onRemoveSound(sound.id as CustomSoundId)
+                : undefined
+            }
           />
         ))}
       </div>
 
+      {/* 사운드 가져오기 */}
+      <button
+        onClick={onImportSound}
+        className="p-1.5 rounded-full text-white/50 hover:text-white hover:bg-white/10 transition-all"
+        title={t("sound.import")}
+        data-tauri-drag-region="false"
+      >
+        <Plus size={16} />
+      </button>
+
       {/* 사운드 네비게이션 - 다음 */}
       <button
         onClick={onNextSlide}
@@ -11,25 +11,40 @@ import {
   createSessionData,
   useAppSettings,
   usePro,
+  useCustomSounds,
+  useSoundPacks,
+  useSoundCatalog,
+  useRemoteCommands,
+  useTimerSync,
 } from "@/hooks";
-import { SOUNDS, ITEMS_PER_SLIDE } from "@/constants/sounds";
+import { ITEMS_PER_SLIDE } from "@/constants/sounds";
+import { TIMER_PRESETS } from "@/constants/timer";
 import { CompactView } from "./CompactView";
 import { ExpandedView } from "./ExpandedView";
 import { SessionHistoryModal } from "./SessionHistoryModal";
 import { ProUpgradeModal } from "./ProUpgradeModal";
 import { LicenseInputModal } from "./LicenseInputModal";
+import { LicenseManageModal } from "./LicenseManageModal";
 import { UsageModal } from "./UsageModal";
+import { AudioOutputModal } from "./AudioOutputModal";
+import { SoundPacksModal } from "./SoundPacksModal";
+import { WebhooksModal } from "./WebhooksModal";
 import { trackEvent } from "@/lib/analytics";
+import { reportSessionEvent } from "@/lib/webhooks";
 import {
   playTimerCompleteSound,
   playTimerWarningSound,
 } from "@/lib/notification-sound";
 import type { TimerPreset } from "@/types/timer";
-import type { SoundType } from "@/types/audio";
+import type { CustomSoundId, SoundType } from "@/types/audio";
 
 function FloatingBarContent() {
   const [currentSlide, setCurrentSlide] = useState(0);
-  const totalSlides = Math.ceil(SOUNDS.length / ITEMS_PER_SLIDE);
+  const customSounds = useCustomSounds();
+  const soundPacks = useSoundPacks();
+  const catalog = useSoundCatalog();
+  const sounds = catalog.controls;
+  const totalSlides = Math.ceil(sounds.length / ITEMS_PER_SLIDE);
   const [currentPresetId, setCurrentPresetId] = useState<string | undefined>();
   const [currentTaskDescription, setCurrentTaskDescription] = useState<
     string | undefined
@@ -37,7 +52,11 @@ function FloatingBarContent() {
   const [showSessionHistory, setShowSessionHistory] = useState(false);
   const [showUpgradeModal, setShowUpgradeModal] = useState(false);
   const [showLicenseModal, setShowLicenseModal] = useState(false);
+  const [showLicenseManageModal, setShowLicenseManageModal] = useState(false);
   const [showUsageModal, setShowUsageModal] = useState(false);
+  const [showAudioOutputModal, setShowAudioOutputModal] = useState(false);
+  const [showSoundPacksModal, setShowSoundPacksModal] = useState(false);
+  const [showWebhooksModal, setShowWebhooksModal] = useState(false);
 
   // Pro 상태 관리
   const pro = usePro();
@@ -48,12 +67,18 @@ function FloatingBarContent() {
   const {
     activeSounds,
     volumes,
+    effects,
     isMuted,
     toggleSound: audioToggleSound,
     handleVolumeChange: audioHandleVolumeChange,
+    handleEffectChange,
+    selectSounds,
+    stopAll,
     toggleMute: audioToggleMute,
   } = useAudioPlayer({
+    sounds,
     initialVolumes: settings.isLoaded ? settings.volumes : undefined,
+    initialEffects: settings.isLoaded ? settings.effects : undefined,
     initialMuted: settings.isLoaded ? settings.isMuted : undefined,
     initialActiveSounds: settings.isLoaded ? settings.activeSounds : undefined,
   });
@@ -76,6 +101,12 @@ function FloatingBarContent() {
     }
   }, [volumes, settings]);
 
+  useEffect(() => {
+    if (settings.isLoaded) {
+      settings.setEffects(effects);
+    }
+  }, [effects, settings]);
+
   useEffect(() => {
     if (settings.isLoaded) {
       settings.setIsMuted(isMuted);
@@ -93,6 +124,82 @@ function FloatingBarContent() {
     audioToggleMute();
   }, [audioToggleMute]);
 
+  // 사운드 가져오기: 가져온 사운드가 들어갈 슬라이드(팩 사운드 앞)로 이동 후 바로 재생
+  const { importSound, removeSound } = customSounds;
+  const handleImportSound = useCallback(async () => {
+    try {
+      const sound = await importSound();
+      if (!sound) return;
+      const index = catalog.sounds.filter(
+        (s) => s.source.type !== "pack"
+      ).length;
+      setCurrentSlide(Math.floor(index / ITEMS_PER_SLIDE));
+      audioToggleSound(sound.id);
+      trackEvent("custom_sound_imported", { duration: sound.duration });
+    } catch (error) {
+      // 사용자에게는 Rust 쪽에서 오류 대화상자를 띄운다
+      console.error("Failed to import sound:", error);
+    }
+  }, [importSound, catalog.sounds, audioToggleSound]);
+
+  const handleRemoveSound = useCallback(
+    async (id: CustomSoundId) => {
+      if (activeSounds.has(id)) {
+        audioToggleSound(id);
+      }
+      await removeSound(id);
+      setCurrentSlide((prev) =>
+        Math.min(prev, Math.ceil((sounds.length - 1) / ITEMS_PER_SLIDE) - 1)
+      );
+    },
+    [activeSounds, audioToggleSound, removeSound, sounds.length]
+  );
+
+  // 사운드 팩: 비활성화/삭제되는 팩의 재생 중인 사운드는 먼저 정지
+  const { installPack, setPackEnabled, uninstallPack } = soundPacks;
+  const stopPackSounds = useCallback(
+    (packId: string) => {
+      activeSounds.forEach((id) => {
+        if (id.startsWith(`${packId}.`)) {
+          audioToggleSound(id);
+        }
+      });
+      setCurrentSlide(0);
+    },
+    [activeSounds, audioToggleSound]
+  );
+
+  const handleInstallPack = useCallback(async () => {
+    try {
+      const pack = await installPack();
+      if (!pack) return;
+      trackEvent("sound_pack_installed", {
+        sound_count: pack.manifest.sounds.length,
+      });
+    } catch (error) {
+      // 사용자에게는 Rust 쪽에서 오류 대화상자를 띄운다
+      console.error("Failed to install sound pack:", error);
+    }
+  }, [installPack]);
+
+  const handleSetPackEnabled = useCallback(
+    async (packId: string, enabled: boolean) => {
+      if (!enabled) {
+        stopPackSounds(packId);
+      }
+      await setPackEnabled(packId, enabled);
+    },
+    [setPackEnabled, stopPackSounds]
+  );
+
+  const handleUninstallPack = useCallback(
+    async (packId: string) => {
+      stopPackSounds(packId);
+      await uninstallPack(packId);
+    },
+    [uninstallPack, stopPackSounds]
+  );
+
   // 타이머 완료 콜백
   const handleTimerComplete = useCallback(() => {
     playTimerCompleteSound(0.7);
@@ -134,59 +241,59 @@ function FloatingBarContent() {
     currentTaskDescriptionRef.current = currentTaskDescription;
   }, [currentTaskDescription]);
 
-  // Tauri 이벤트 리스너 (트레이 메뉴에서 세션 기록/라이센스/이용현황 열기)
-  useEffect(() => {
-    let unlistenSessionHistory: (() => void) | undefined;
-    let unlistenLicenseInput: (() => void) | undefined;
-    let unlistenUsage: (() => void) | undefined;
-    let isMounted = true;
-
-    const setupListeners = async () => {
-      try {
-        const { listen } = await import("@tauri-apps/api/event");
+  // 타이머 시작 시 웹훅으로 시작 이벤트 전송 (startedAt은 일시정지/재개 후에도 유지됨)
+  const startedSessionRef = useRef<number | null>(null);
 
-        const sessionHistoryFn = await listen("open-session-history", () => {
-          setShowSessionHistory(true);
-        });
-        if (isMounted) {
-          unlistenSessionHistory = sessionHistoryFn;
-        } else {
-          sessionHistoryFn();
-          return;
-        }
-
-        const licenseInputFn = await listen("open-license-input", () => {
-          setShowLicenseModal(true);
-        });
-        if (isMounted) {
-          unlistenLicenseInput = licenseInputFn;
-        } else {
-          licenseInputFn();
-          return;
-        }
-
-        const usageFn = await listen("open-usage", () => {
-          setShowUsageModal(true);
-        });
-        if (isMounted) {
-          unlistenUsage = usageFn;
-        } else {
-          usageFn();
-        }
-      } catch {
-        // 웹 환경에서는 무시
-      }
-    };
+  useEffect(() => {
+    if (!timer.startedAt || startedSessionRef.current === timer.startedAt) {
+      return;
+    }
+    startedSessionRef.current = timer.startedAt;
+    reportSessionEvent("started", {
+      mode: timer.mode,
+      targetSeconds: timer.targetSeconds,
+      actualSeconds: 0,
+      completed: false,
+      startedAt: timer.startedAt,
+      activeSounds: Array.from(activeSoundsRef.current),
+      preset:
+        timer.mode === "countdown" ? currentPresetIdRef.current : undefined,
+      taskDescription: currentTaskDescriptionRef.current,
+    });
+  }, [timer.startedAt, timer.mode, timer.targetSeconds]);
+
+  // 일시정지/재개 시 이벤트 전송 (셸 훅, 웹훅)
+  const currentSecondsRef = useRef(timer.currentSeconds);
+  const pausedSessionRef = useRef(false);
 
-    setupListeners();
+  useEffect(() => {
+    currentSecondsRef.current = timer.currentSeconds;
+  }, [timer.currentSeconds]);
 
-    return () => {
-      isMounted = false;
-      unlistenSessionHistory?.();
-      unlistenLicenseInput?.();
-      unlistenUsage?.();
-    };
-  }, []);
+  useEffect(() => {
+    if (!timer.startedAt) {
+      pausedSessionRef.current = false;
+      return;
+    }
+    if (pausedSessionRef.current === timer.isPaused) {
+      return;
+    }
+    pausedSessionRef.current = timer.isPaused;
+    reportSessionEvent(timer.isPaused ? "paused" : "resumed", {
+      mode: timer.mode,
+      targetSeconds: timer.targetSeconds,
+      actualSeconds:
+        timer.mode === "countdown"
+          ? timer.targetSeconds - currentSecondsRef.current
+          : currentSecondsRef.current,
+      completed: false,
+      startedAt: timer.startedAt,
+      activeSounds: Array.from(activeSoundsRef.current),
+      preset:
+        timer.mode === "countdown" ? currentPresetIdRef.current : undefined,
+      taskDescription: currentTaskDescriptionRef.current,
+    });
+  }, [timer.isPaused, timer.startedAt, timer.mode, timer.targetSeconds]);
 
   // 타이머 완료 시 세션 기록 (카운트다운만 완료 가능)
   // useRef로 중복 실행 방지
@@ -222,10 +329,7 @@ function FloatingBarContent() {
         active_sounds_count: activeSoundsRef.current.size,
       });
 
-      // Pro: 타이머 완료 시 트라이얼 차감 (Free 사용자만)
-      if (!pro.isPro) {
-        pro.useTimerTrial();
-      }
+      // Pro: 타이머 트라이얼은 완료 세션이 기록될 때 Rust가 차감한다
     }
   }, [
     timer.isCompleted,
@@ -233,45 +337,28 @@ function FloatingBarContent() {
     timer.mode,
     timer.targetSeconds,
     addSession,
-    pro,
   ]);
 
   // North Star Metric: Track playback time
   // 소리가 활성화되어 있으면 추적 (타이머 유무와 관계없이)
   usePlaybackTracking(activeSounds.size > 0, activeSounds);
 
-  // Pro: 플레이타임 추적 (1초마다)
-  const playtimeIntervalRef = useRef<NodeJS.Timeout | null>(null);
+  // Pro: 플레이타임 제한 (재생 시간은 Rust가 집계)
   const [upgradeReason, setUpgradeReason] = useState<
     "playtime" | "timer" | null
   >(null);
 
   useEffect(() => {
-    // Pro 사용자가 아니고, 소리가 재생 중일 때만 추적
-    if (!pro.isPro && activeSounds.size > 0) {
-      playtimeIntervalRef.current = setInterval(() => {
-        pro.trackPlaytime(1);
-
-        // 제한 도달 시 모든 사운드 비활성화 및 모달 표시
-        if (!pro.canPlay()) {
-          // 모든 활성화된 사운드 비활성화
-          activeSounds.forEach((soundId) => {
-            audioToggleSound(soundId);
-          });
-
-          setUpgradeReason("playtime");
-          setShowUpgradeModal(true);
-        }
-      }, 1000);
-    }
+    // 제한 도달 시 모든 사운드 비활성화 및 모달 표시
+    if (!pro.isPro && activeSounds.size > 0 && !pro.canPlay()) {
+      activeSounds.forEach((soundId) => {
+        audioToggleSound(soundId);
+      });
 
-    return () => {
-      if (playtimeIntervalRef.current) {
-        clearInterval(playtimeIntervalRef.current);
-        playtimeIntervalRef.current = null;
-      }
-    };
-  }, [pro.isPro, activeSounds.size, pro, activeSounds, audioToggleSound]);
+      setUpgradeReason("playtime");
+      setShowUpgradeModal(true);
+    }
+  }, [pro, activeSounds, audioToggleSound]);
 
   // 미완료 세션 기록 (리셋, 모드 전환 시 호출)
   const recordIncompleteSession = useCallback(() => {
@@ -353,22 +440,131 @@ function FloatingBarContent() {
     [timer]
   );
 
-  // 타이머 시작/정지 핸들러 (트라이얼 체크 포함)
+  // 타이머 시작 (트라이얼 체크 포함)
+  const startTimer = useCallback(() => {
+    // 타이머 시작 전 트라이얼 체크 (Free 사용자만, 카운트다운 모드일 때만)
+    if (timer.mode === "countdown" && !pro.isPro && !pro.canUseTimer()) {
+      setUpgradeReason("timer");
+      setShowUpgradeModal(true);
+      return;
+    }
+    timer.start();
+  }, [timer, pro]);
+
+  // 타이머 시작/정지 핸들러
   const handleTimerTogglePlayPause = useCallback(() => {
     if (timer.isRunning && !timer.isPaused) {
       timer.pause();
     } else if (timer.isPaused) {
       timer.resume();
     } else {
-      // 타이머 시작 전 트라이얼 체크 (Free 사용자만, 카운트다운 모드일 때만)
-      if (timer.mode === "countdown" && !pro.isPro && !pro.canUseTimer()) {
-        setUpgradeReason("timer");
-        setShowUpgradeModal(true);
-        return;
-      }
-      timer.start();
+      startTimer();
     }
-  }, [timer, pro]);
+  }, [timer, startTimer]);
+
+  // 창 밖(트레이 메뉴, OS 미디어 키, MPRIS, 명령줄, zerohz:// 링크)에서 온 명령
+  // 재생: 일시정지는 음소거, 정지는 모든 사운드 끄기
+  // 타이머: 프리셋 적용이 렌더링된 뒤에 시작해야 하므로 pendingTimerStart로 미룬다
+  const [pendingTimerStart, setPendingTimerStart] = useState(false);
+
+  useRemoteCommands({
+    onOpen: (panel) => {
+      switch (panel) {
+        case "session-history":
+          setShowSessionHistory(true);
+          break;
+        case "license-input":
+          // Pro면 라이센스 관리
+          if (pro.isPro) {
+            setShowLicenseManageModal(true);
+          } else {
+            setShowLicenseModal(true);
+          }
+          break;
+        case "usage":
+          setShowUsageModal(true);
+          break;
+        case "audio-output":
+          setShowAudioOutputModal(true);
+          break;
+        case "sound-packs":
+          setShowSoundPacksModal(true);
+          break;
+        case "webhooks":
+          setShowWebhooksModal(true);
+          break;
+      }
+    },
+    onPlayback: (command) => {
+      switch (command.type) {
+        case "play":
+          if (isMuted) audioToggleMute();
+          break;
+        case "pause":
+          if (!isMuted) audioToggleMute();
+          break;
+        case "play-pause":
+          if (activeSounds.size > 0) audioToggleMute();
+          break;
+        case "stop":
+          stopAll();
+          break;
+        case "scene":
+          selectSounds(command.sounds);
+          if (isMuted) audioToggleMute();
+          break;
+        case "sound":
+          if (
+            command.on !== null &&
+            command.on !== activeSounds.has(command.sound)
+          ) {
+            toggleSound(command.sound);
+          }
+          if (command.volume !== null) {
+            handleVolumeChange(command.sound, command.volume);
+          }
+          break;
+      }
+    },
+    onTimer: (command) => {
+      switch (command.type) {
+        case "start": {
+          const preset = TIMER_PRESETS.find((p) => p.id === command.preset);
+          if (!preset) return;
+          recordIncompleteSession();
+          handleSetPreset(preset, command.task ?? undefined);
+          setPendingTimerStart(true);
+          break;
+        }
+        case "pause":
+          if (timer.isRunning && !timer.isPaused) timer.pause();
+          break;
+        case "resume":
+          if (timer.isPaused) timer.resume();
+          break;
+        case "stop":
+          handleTimerReset();
+          break;
+      }
+    },
+  });
+
+  useEffect(() => {
+    if (!pendingTimerStart) return;
+    setPendingTimerStart(false);
+    startTimer();
+  }, [pendingTimerStart, startTimer]);
+
+  // 타이머 상태를 Rust에 동기화 (명령줄 --status 등)
+  useTimerSync({
+    mode: timer.mode,
+    targetSeconds: timer.targetSeconds,
+    currentSeconds: timer.currentSeconds,
+    isRunning: timer.isRunning,
+    isPaused: timer.isPaused,
+    preset: timer.mode === "countdown" ? currentPresetId : undefined,
+    task: currentTaskDescription,
+  });
 
   const nextSlide = () => {
     setCurrentSlide((prev) => (prev + 1) % totalSlides);
@@ -395,6 +591,7 @@ function FloatingBarContent() {
       >
         {isCompact ? (
           <CompactView
+            sounds={sounds}
             activeSounds={activeSounds}
             isPlaying={timer.isRunning && !timer.isPaused}
             formattedTime={timer.formattedTime}
@@ -413,13 +610,18 @@ function FloatingBarContent() {
           />
         ) : (
           <ExpandedView
+            sounds={sounds}
             activeSounds={activeSounds}
             volumes={volumes}
+            effects={effects}
             isMuted={isMuted}
             isPlaying={timer.isRunning && !timer.isPaused}
             currentSlide={currentSlide}
             onToggleSound={toggleSound}
             onVolumeChange={handleVolumeChange}
+            onEffectChange={handleEffectChange}
+            onImportSound={handleImportSound}
+            onRemoveSound={handleRemoveSound}
             onToggleMute={toggleMute}
             onTogglePlayPause={handleTimerTogglePlayPause}
             onPrevSlide={prevSlide}
@@ -454,6 +656,7 @@ function FloatingBarContent() {
           onClose={() => setShowSessionHistory(false)}
           onClear={clearSessions}
           isPro={pro.isPro}
+          sounds={sounds}
         />
       )}
 
@@ -484,9 +687,38 @@ function FloatingBarContent() {
         />
       )}
 
+      {/* 라이센스 관리 모달 */}
+      {showLicenseManageModal && (
+        <LicenseManageModal onClose={() => setShowLicenseManageModal(false)} />
+      )}
+
       {/* 이용 현황 모달 */}
       {showUsageModal && (
-        <UsageModal onClose={() => setShowUsageModal(false)} />
+        <UsageModal
+          sounds={sounds}
+          onClose={() => setShowUsageModal(false)}
+        />
+      )}
+
+      {/* 오디오 출력 장치 모달 */}
+      {showAudioOutputModal && (
+        <AudioOutputModal onClose={() => setShowAudioOutputModal(false)} />
+      )}
+
+      {/* 사운드 팩 모달 */}
+      {showSoundPacksModal && (
+        <SoundPacksModal
+          packs={soundPacks.packs}
+          onInstall={handleInstallPack}
+          onSetEnabled={handleSetPackEnabled}
+          onUninstall={handleUninstallPack}
+          onClose={() => setShowSoundPacksModal(false)}
+        />
+      )}
+
+      {/* 웹훅 모달 */}
+      {showWebhooksModal && (
+        <WebhooksModal onClose={() => setShowWebhooksModal(false)} />
       )}
     </div>
   );
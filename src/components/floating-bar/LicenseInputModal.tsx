@@ -161,7 +161,11 @@ export function LicenseInputModal({
               type="text"
               value={licenseKey}
               onChange={(e) => {
-                setLicenseKey(e.target.value.toUpperCase());
+                // 사이트 라이센스 토큰은 대소문자를 구분한다
+                const value = e.target.value;
+                setLicenseKey(
+                  value.trim().startsWith("zerohz1.") ? value : value.toUpperCase()
+                );
                 setError(null);
               }}
               placeholder={t("pro.enterLicenseKey")}
@@ -0,0 +1,360 @@
+"use client";
+
+import { useCallback, useEffect, useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { X, Key, Loader2, AlertCircle, CheckCircle, Laptop } from "lucide-react";
+import { usePro } from "@/hooks";
+import { deactivateLicenseDevice, getLicenseActivations } from "@/lib/license";
+import type { LicenseActivations } from "@/types/pro";
+
+interface LicenseManageModalProps {
+  onClose: () => void;
+}
+
+const MODAL_WINDOW_SIZE = { width: 420, height: 520 };
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+export function LicenseManageModal({ onClose }: LicenseManageModalProps) {
+  const { t } = useTranslation();
+  const pro = usePro();
+  const [activations, setActivations] = useState<LicenseActivations | null>(
+    null
+  );
+  const [isLoading, setIsLoading] = useState(false);
+  const [loadError, setLoadError] = useState<string | null>(null);
+  const [otherInstanceId, setOtherInstanceId] = useState("");
+  const [isReleasing, setIsReleasing] = useState(false);
+  const [releaseError, setReleaseError] = useState<string | null>(null);
+  const [released, setReleased] = useState(false);
+  const [isRemoving, setIsRemoving] = useState(false);
+  const [removeError, setRemoveError] = useState<string | null>(null);
+  const originalSizeRef = useRef<{ width: number; height: number } | null>(
+    null
+  );
+  const originalPositionRef = useRef<{ x: number; y: number } | null>(null);
+
+  const isSiteLicense = pro.licensee !== null;
+
+  // 모달 열릴 때 윈도우 크기 확장 및 중앙 배치
+  useEffect(() => {
+    const expandWindow = async () => {
+      try {
+        const {
+          getCurrentWindow,
+          LogicalSize,
+          PhysicalPosition,
+          currentMonitor,
+        } = await import("@tauri-apps/api/window");
+        const appWindow = getCurrentWindow();
+
+        // 현재 크기 및 위치 저장
+        const currentSize = await appWindow.outerSize();
+        const currentPos = await appWindow.outerPosition();
+
+        originalSizeRef.current = {
+          width: currentSize.width,
+          height: currentSize.height,
+        };
+        originalPositionRef.current = {
+          x: currentPos.x,
+          y: currentPos.y,
+        };
+
+        // 모달 크기로 확장
+        await appWindow.setSize(
+          new LogicalSize(MODAL_WINDOW_SIZE.width, MODAL_WINDOW_SIZE.height)
+        );
+
+        // 화면 상단 중앙으로 이동
+        const monitor = await currentMonitor();
+        if (monitor) {
+          const scaleFactor = monitor.scaleFactor;
+          const monitorSize = monitor.size;
+          const monitorPosition = monitor.position;
+
+          const physicalWidth = MODAL_WINDOW_SIZE.width * scaleFactor;
+
+          const x =
+            monitorPosition.x +
+            Math.round((monitorSize.width - physicalWidth) / 2);
+          const y = monitorPosition.y + 50;
+
+          await appWindow.setPosition(new PhysicalPosition(x, y));
+        }
+      } catch {
+        console.log("Not in Tauri environment");
+      }
+    };
+
+    expandWindow();
+
+    return () => {
+      const restoreWindow = async () => {
+        if (originalSizeRef.current && originalPositionRef.current) {
+          try {
+            const { getCurrentWindow, PhysicalSize, PhysicalPosition } =
+              await import("@tauri-apps/api/window");
+            const appWindow = getCurrentWindow();
+
+            await appWindow.setSize(
+              new PhysicalSize(
+                originalSizeRef.current.width,
+                originalSizeRef.current.height
+              )
+            );
+
+            await appWindow.setPosition(
+              new PhysicalPosition(
+                originalPositionRef.current.x,
+                originalPositionRef.current.y
+              )
+            );
+          } catch {
+            console.log("Failed to restore window size/position");
+          }
+        }
+      };
+      restoreWindow();
+    };
+  }, []);
+
+  // ESC 키로 닫기
+  useEffect(() => {
+    function handleEscape(event: KeyboardEvent) {
+      if (event.key === "Escape") {
+        onClose();
+      }
+    }
+
+    document.addEventListener("keydown", handleEscape);
+    return () => document.removeEventListener("keydown", handleEscape);
+  }, [onClose]);
+
+  // 활성화 현황 불러오기 (사이트 라이센스는 기기별 활성화가 없음)
+  const loadActivations = useCallback(async () => {
+    setIsLoading(true);
+    setLoadError(null);
+    try {
+      setActivations(await getLicenseActivations());
+    } catch (error) {
+      setLoadError(String(error));
+    } finally {
+      setIsLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    if (!isSiteLicense) loadActivations();
+  }, [isSiteLicense, loadActivations]);
+
+  // 다른 기기 활성화 해제
+  const handleReleaseOther = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setReleaseError(null);
+    setReleased(false);
+    setIsReleasing(true);
+    try {
+      await deactivateLicenseDevice(otherInstanceId.trim());
+      setReleased(true);
+      setOtherInstanceId("");
+      loadActivations();
+    } catch (error) {
+      setReleaseError(String(error));
+    } finally {
+      setIsReleasing(false);
+    }
+  };
+
+  // 이 기기에서 라이센스 제거
+  const handleRemove = async () => {
+    setRemoveError(null);
+    setIsRemoving(true);
+    const result = await pro.removeLicense();
+    setIsRemoving(false);
+    if (result.success) {
+      onClose();
+    } else {
+      setRemoveError(result.error ?? null);
+    }
+  };
+
+  const offlineDaysLeft =
+    pro.offlineUntil !== null
+      ? Math.max(0, Math.ceil((pro.offlineUntil - Date.now()) / DAY_MS))
+      : null;
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      {/* Backdrop */}
+      <div
+        className="absolute inset-0 bg-black/60 backdrop-blur-sm"
+        onClick={onClose}
+      />
+
+      {/* Modal */}
+      <div className="relative bg-[#1A1A1A] border border-white/10 rounded-2xl p-6 max-w-sm mx-4 shadow-2xl w-full max-h-[90vh] overflow-y-auto">
+        {/* Close button */}
+        <button
+          onClick={onClose}
+          className="absolute top-4 right-4 text-white/40 hover:text-white/70 transition-colors"
+        >
+          <X size={20} />
+        </button>
+
+        {/* Header */}
+        <div className="flex items-center gap-3 mb-5">
+          <div className="w-10 h-10 rounded-full bg-white/10 flex items-center justify-center">
+            <Key size={20} className="text-white" />
+          </div>
+          <div>
+            <h2 className="text-lg font-semibold text-white">
+              {t("licenseManage.title")}
+            </h2>
+            <p className="text-xs text-white/50 font-mono">
+              {isSiteLicense
+                ? t("licenseManage.siteLicense", { licensee: pro.licensee })
+                : pro.keyHint}
+            </p>
+          </div>
+        </div>
+
+        {/* 오프라인 유예 */}
+        {offlineDaysLeft !== null && (
+          <div className="flex items-center gap-2 mb-4 px-3 py-2 rounded-lg bg-yellow-500/10 text-yellow-300 text-xs">
+            <AlertCircle size={14} />
+            <span>
+              {t("licenseManage.offline", { count: offlineDaysLeft })}
+            </span>
+          </div>
+        )}
+
+        {/* 활성화 기기 */}
+        {!isSiteLicense && (
+          <div className="mb-5">
+            <h3 className="text-xs font-medium text-white/50 uppercase tracking-wide mb-2">
+              {t("licenseManage.devices")}
+            </h3>
+
+            {isLoading && (
+              <div className="flex items-center gap-2 text-white/50 text-sm">
+                <Loader2 size={14} className="animate-spin" />
+                <span>{t("licenseManage.loading")}</span>
+              </div>
+            )}
+
+            {loadError && (
+              <div className="flex items-center gap-2 text-red-400 text-sm">
+                <AlertCircle size={14} />
+                <span>{loadError}</span>
+              </div>
+            )}
+
+            {activations && !isLoading && (
+              <>
+                <p className="text-sm text-white/70 mb-2">
+                  {activations.activationLimit !== null
+                    ? t("licenseManage.seats", {
+                        used: activations.activationUsage,
+                        limit: activations.activationLimit,
+                      })
+                    : t("licenseManage.seatsUnlimited", {
+                        used: activations.activationUsage,
+                      })}
+                </p>
+                {activations.thisDevice && (
+                  <div className="flex items-start gap-3 px-3 py-2 rounded-lg bg-white/5">
+                    <Laptop size={16} className="text-white/60 mt-0.5" />
+                    <div className="flex-1 min-w-0">
+                      <div className="flex items-center gap-2">
+                        <span className="text-sm text-white truncate">
+                          {activations.thisDevice.name}
+                        </span>
+                        <span className="text-[10px] px-1.5 py-0.5 rounded bg-white/10 text-white/70">
+                          {t("licenseManage.thisDevice")}
+                        </span>
+                      </div>
+                      <p className="text-[11px] text-white/40 font-mono truncate">
+                        {activations.thisDevice.id}
+                      </p>
+                      {activations.thisDevice.createdAt && (
+                        <p className="text-[11px] text-white/40">
+                          {t("licenseManage.activatedOn", {
+                            date: new Date(
+                              activations.thisDevice.createdAt
+                            ).toLocaleDateString(),
+                          })}
+                        </p>
+                      )}
+                    </div>
+                  </div>
+                )}
+              </>
+            )}
+
+            {/* 다른 기기 해제 */}
+            <form onSubmit={handleReleaseOther} className="mt-4">
+              <label className="block text-xs text-white/50 mb-1">
+                {t("licenseManage.otherDevice")}
+              </label>
+              <div className="flex gap-2">
+                <input
+                  type="text"
+                  value={otherInstanceId}
+                  onChange={(e) => {
+                    setOtherInstanceId(e.target.value);
+                    setReleaseError(null);
+                    setReleased(false);
+                  }}
+                  placeholder={t("licenseManage.instanceId")}
+                  className="flex-1 min-w-0 px-3 py-2 bg-white/5 border border-white/10 rounded-lg text-white placeholder-white/30 focus:outline-none focus:border-white/30 font-mono text-xs"
+                  disabled={isReleasing}
+                />
+                <button
+                  type="submit"
+                  disabled={isReleasing || !otherInstanceId.trim()}
+                  className="px-3 py-2 rounded-lg bg-white/10 text-white text-xs hover:bg-white/20 transition-colors disabled:opacity-50 disabled:cursor-not-allowed flex items-center gap-1"
+                >
+                  {isReleasing && (
+                    <Loader2 size={12} className="animate-spin" />
+                  )}
+                  {t("licenseManage.deactivate")}
+                </button>
+              </div>
+              <p className="text-[11px] text-white/40 mt-1">
+                {t("licenseManage.otherDeviceHint")}
+              </p>
+              {releaseError && (
+                <div className="flex items-center gap-2 mt-2 text-red-400 text-xs">
+                  <AlertCircle size={14} />
+                  <span>{releaseError}</span>
+                </div>
+              )}
+              {released && (
+                <div className="flex items-center gap-2 mt-2 text-white text-xs">
+                  <CheckCircle size={14} />
+                  <span>{t("licenseManage.released")}</span>
+                </div>
+              )}
+            </form>
+          </div>
+        )}
+
+        {/* 이 기기에서 제거 */}
+        <button
+          onClick={handleRemove}
+          disabled={isRemoving}
+          className="w-full px-4 py-3 rounded-xl bg-red-500/10 text-red-300 font-medium text-sm hover:bg-red-500/20 transition-colors disabled:opacity-50 disabled:cursor-not-allowed flex items-center justify-center gap-2"
+        >
+          {isRemoving && <Loader2 size={16} className="animate-spin" />}
+          {t("licenseManage.remove")}
+        </button>
+        {removeError && (
+          <div className="flex items-center gap-2 mt-3 text-red-400 text-sm">
+            <AlertCircle size={16} />
+            <span>{removeError}</span>
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
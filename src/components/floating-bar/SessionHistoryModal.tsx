@@ -13,8 +13,8 @@ import {
   Timer,
 } from "lucide-react";
 import type { TimerSession, SessionStats } from "@/types/timer";
+import type { SoundControl } from "@/types/audio";
 import { TIMER_PRESETS } from "@/constants/timer";
-import { SOUNDS } from "@/constants/sounds";
 
 interface SessionHistoryModalProps {
   sessions: TimerSession[];
@@ -22,6 +22,7 @@ interface SessionHistoryModalProps {
   onClose: () => void;
   onClear: () => void;
   isPro: boolean;
+  sounds: SoundControl[]; // 세션에 기록된 사운드 아이콘 표시용
 }
 
 // 모달용 윈도우 크기
@@ -96,7 +97,13 @@ function formatDateLabel(
 /**
  * 세션 아이템 컴포넌트
  */
-function SessionItem({ session }: { session: TimerSession }) {
+function SessionItem({
+  session,
+  sounds,
+}: {
+  session: TimerSession;
+  sounds: SoundControl[];
+}) {
   const { t, i18n } = useTranslation();
   const preset = TIMER_PRESETS.find((p) => p.id === session.preset);
 
@@ -143,7 +150,7 @@ function SessionItem({ session }: { session: TimerSession }) {
         {session.activeSounds.length > 0 && (
           <div className="flex items-center gap-0.5">
             {session.activeSounds.map((soundId) => {
-              const sound = SOUNDS.find((s) => s.id === soundId);
+              const sound = sounds.find((s) => s.id === soundId);
               if (!sound) return null;
               const Icon = sound.icon;
               return (
@@ -178,6 +185,7 @@ export function SessionHistoryModal({
   onClose,
   onClear,
   isPro,
+  sounds,
 }: SessionHistoryModalProps) {
   const { t, i18n } = useTranslation();
   const modalRef = useRef<HTMLDivElement>(null);
@@ -555,7 +563,11 @@ export function SessionHistoryModal({
                 </div>
                 <div className="space-y-2">
                   {dateSessions.map((session) => (
-                    <SessionItem key={session.id} session={session} />
+                    <SessionItem
+                      key={session.id}
+                      session={session}
+                      sounds={sounds}
+                    />
                   ))}
                 </div>
               </div>
@@ -1,7 +1,20 @@
 "use client";
 
+import { useTranslation } from "react-i18next";
+import { X } from "lucide-react";
 import { cn } from "@/lib/utils";
-import type { SoundControl, SoundType } from "@/types/audio";
+import { MIN_LOWPASS, MAX_LOWPASS } from "@/constants/sounds";
+import type { SoundControl, SoundEffect, SoundType } from "@/types/audio";
+
+const SLIDER_CLASS =
+  "w-full h-1 bg-white/30 rounded-lg appearance-none cursor-pointer [&::-webkit-slider-thumb]:appearance-none [&::-webkit-slider-thumb]:w-3 [&::-webkit-slider-thumb]:h-3 [&::-webkit-slider-thumb]:bg-white [&::-webkit-slider-thumb]:rounded-full";
+
+// 톤 슬라이더(0~1)를 로우패스 컷오프(Hz)에 로그 스케일로 매핑
+const LOWPASS_RANGE = Math.log(MAX_LOWPASS / MIN_LOWPASS);
+const toneToCutoff = (tone: number) =>
+  MIN_LOWPASS * Math.exp(tone * LOWPASS_RANGE);
+const cutoffToTone = (cutoff: number) =>
+  Math.log(Math.max(cutoff, MIN_LOWPASS) / MIN_LOWPASS) / LOWPASS_RANGE;
 
 /**
  * SoundButton - 개별 사운드 컨트롤 버튼 컴포넌트
@@ -9,24 +22,34 @@ import type { SoundControl, SoundType } from "@/types/audio";
  * @param sound - 사운드 정보 객체 (id, icon, label, src 포함)
  * @param isActive - 현재 사운드가 활성화되어 있는지 여부
  * @param volume - 현재 볼륨 값 (0~1)
+ * @param effect - 현재 팬(-1~1)/로우패스(Hz) 설정
  * @param onToggle - 사운드 활성화/비활성화 토글 콜백
  * @param onVolumeChange - 볼륨 변경 콜백 (id, 0~1 값)
+ * @param onEffectChange - 팬/톤 변경 콜백 (id, 변경할 필드)
+ * @param onRemove - 가져온 사운드 삭제 콜백 (내장 사운드는 없음)
  */
 interface SoundButtonProps {
   sound: SoundControl;
   isActive: boolean;
   volume: number;
+  effect: SoundEffect;
   onToggle: (id: SoundType) => void;
   onVolumeChange: (id: SoundType, value: number) => void;
+  onEffectChange: (id: SoundType, effect: Partial<SoundEffect>) => void;
+  onRemove?: () => void;
 }
 
 export function SoundButton({
   sound,
   isActive,
   volume,
+  effect,
   onToggle,
   onVolumeChange,
+  onEffectChange,
+  onRemove,
 }: SoundButtonProps) {
+  const { t } = useTranslation();
   const Icon = sound.icon;
 
   return (
@@ -45,10 +68,22 @@ export function SoundButton({
         <Icon size={20} />
       </button>
 
-      {/* Volume Slider (Visible on hover/active) */}
+      {/* 가져온 사운드 삭제 (hover 시 표시) */}
+      {onRemove && (
+        <button
+          onClick={onRemove}
+          className="absolute -top-1 -right-1 p-0.5 rounded-full bg-[#1A1A1A] border border-white/20 text-white/60 hover:text-white opacity-0 group-hover:opacity-100 transition-opacity"
+          title={t("sound.remove")}
+          data-tauri-drag-region="false"
+        >
+          <X size={10} />
+        </button>
+      )}
+
+      {/* Volume / Pan / Tone Sliders (Visible on hover/active) */}
       <div
         className={cn(
-          "absolute -bottom-8 left-1/2 -translate-x-1/2 w-16 transition-all duration-200 origin-top z-50 p-1.5 rounded-full bg-[#1A1A1A]/95 border border-white/20 flex items-center justify-center",
+          "absolute -bottom-8 left-1/2 -translate-x-1/2 w-40 gap-2 transition-all duration-200 origin-top z-50 p-1.5 rounded-full bg-[#1A1A1A]/95 border border-white/20 flex items-center justify-center",
           isActive
             ? "opacity-0 group-hover:opacity-100 scale-100"
             : "opacity-0 scale-95 pointer-events-none"
@@ -61,7 +96,37 @@ export function SoundButton({
           step="0.01"
           value={volume}
           onChange={(e) => onVolumeChange(sound.id, parseFloat(e.target.value))}
-          className="w-full h-1 bg-white/30 rounded-lg appearance-none cursor-pointer [&::-webkit-slider-thumb]:appearance-none [&::-webkit-slider-thumb]:w-3 [&::-webkit-slider-thumb]:h-3 [&::-webkit-slider-thumb]:bg-white [&::-webkit-slider-thumb]:rounded-full"
+          className={SLIDER_CLASS}
+          title={t("sound.volume")}
+          data-tauri-drag-region="false"
+        />
+        <input
+          type="range"
+          min="-1"
+          max="1"
+          step="0.05"
+          value={effect.pan}
+          onChange={(e) =>
+            onEffectChange(sound.id, { pan: parseFloat(e.target.value) })
+          }
+          onDoubleClick={() => onEffectChange(sound.id, { pan: 0 })}
+          className={SLIDER_CLASS}
+          title={t("sound.pan")}
+          data-tauri-drag-region="false"
+        />
+        <input
+          type="range"
+          min="0"
+          max="1"
+          step="0.01"
+          value={cutoffToTone(effect.lowpass)}
+          onChange={(e) =>
+            onEffectChange(sound.id, {
+              lowpass: Math.round(toneToCutoff(parseFloat(e.target.value))),
+            })
+          }
+          className={SLIDER_CLASS}
+          title={t("sound.tone")}
           data-tauri-drag-region="false"
         />
       </div>
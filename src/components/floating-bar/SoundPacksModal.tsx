@@ -0,0 +1,201 @@
+"use client";
+
+import { useEffect, useRef } from "react";
+import { useTranslation } from "react-i18next";
+import { X, Package, Plus, Trash2 } from "lucide-react";
+import { cn } from "@/lib/utils";
+import type { InstalledPack } from "@/types/audio";
+
+interface SoundPacksModalProps {
+  packs: InstalledPack[];
+  onInstall: () => void;
+  onSetEnabled: (packId: string, enabled: boolean) => void;
+  onUninstall: (packId: string) => void;
+  onClose: () => void;
+}
+
+const MODAL_WINDOW_SIZE = { width: 400, height: 420 };
+
+export function SoundPacksModal({
+  packs,
+  onInstall,
+  onSetEnabled,
+  onUninstall,
+  onClose,
+}: SoundPacksModalProps) {
+  const { t } = useTranslation();
+  const originalSizeRef = useRef<{ width: number; height: number } | null>(
+    null
+  );
+  const originalPositionRef = useRef<{ x: number; y: number } | null>(null);
+
+  // 모달 열릴 때 윈도우 크기 확장 및 중앙 배치
+  useEffect(() => {
+    const expandWindow = async () => {
+      try {
+        const {
+          getCurrentWindow,
+          LogicalSize,
+          PhysicalPosition,
+          currentMonitor,
+        } = await import("@tauri-apps/api/window");
+        const appWindow = getCurrentWindow();
+
+        // 현재 크기 및 위치 저장
+        const currentSize = await appWindow.outerSize();
+        const currentPos = await appWindow.outerPosition();
+
+        originalSizeRef.current = {
+          width: currentSize.width,
+          height: currentSize.height,
+        };
+        originalPositionRef.current = {
+          x: currentPos.x,
+          y: currentPos.y,
+        };
+
+        // 모달 크기로 확장
+        await appWindow.setSize(
+          new LogicalSize(MODAL_WINDOW_SIZE.width, MODAL_WINDOW_SIZE.height)
+        );
+
+        // 화면 상단 중앙으로 이동
+        const monitor = await currentMonitor();
+        if (monitor) {
+          const scaleFactor = monitor.scaleFactor;
+          const monitorSize = monitor.size;
+          const monitorPosition = monitor.position;
+
+          const physicalWidth = MODAL_WINDOW_SIZE.width * scaleFactor;
+
+          const x =
+            monitorPosition.x +
+            Math.round((monitorSize.width - physicalWidth) / 2);
+          const y = monitorPosition.y + 50;
+
+          await appWindow.setPosition(new PhysicalPosition(x, y));
+        }
+      } catch {
+        console.log("Not in Tauri environment");
+      }
+    };
+
+    expandWindow();
+
+    return () => {
+      const restoreWindow = async () => {
+        if (originalSizeRef.current && originalPositionRef.current) {
+          try {
+            const { getCurrentWindow, PhysicalSize, PhysicalPosition } =
+              await import("@tauri-apps/api/window");
+            const appWindow = getCurrentWindow();
+
+            await appWindow.setSize(
+              new PhysicalSize(
+                originalSizeRef.current.width,
+                originalSizeRef.current.height
+              )
+            );
+
+            await appWindow.setPosition(
+              new PhysicalPosition(
+                originalPositionRef.current.x,
+                originalPositionRef.current.y
+              )
+            );
+          } catch {
+            console.log("Failed to restore window size/position");
+          }
+        }
+      };
+      restoreWindow();
+    };
+  }, []);
+
+  // ESC 키로 닫기
+  useEffect(() => {
+    function handleEscape(event: KeyboardEvent) {
+      if (event.key === "Escape") {
+        onClose();
+      }
+    }
+
+    document.addEventListener("keydown", handleEscape);
+    return () => document.removeEventListener("keydown", handleEscape);
+  }, [onClose]);
+
+  return (
+    <div className="fixed inset-0 z-50 flex flex-col bg-[#1A1A1A] overflow-hidden rounded-2xl border border-white/10">
+      {/* 헤더 */}
+      <div className="flex items-center justify-between p-4 border-b border-white/10">
+        <h3 className="text-white text-sm font-semibold">
+          {t("soundPacks.title")}
+        </h3>
+        <button
+          onClick={onClose}
+          className="text-white/50 hover:text-white p-1 rounded-full hover:bg-white/10 transition-all"
+        >
+          <X size={16} />
+        </button>
+      </div>
+
+      <div className="flex-1 p-4 overflow-auto space-y-2">
+        {packs.length === 0 && (
+          <p className="text-white/50 text-sm text-center py-4">
+            {t("soundPacks.empty")}
+          </p>
+        )}
+        {packs.map(({ manifest, enabled }) => (
+          <div
+            key={manifest.id}
+            className={cn(
+              "flex items-start gap-3 px-3 py-2 rounded-lg bg-white/5",
+              !enabled && "opacity-60"
+            )}
+          >
+            <Package size={16} className="shrink-0 mt-0.5 text-white/70" />
+            <div className="flex-1 min-w-0">
+              <p className="text-white text-sm truncate">
+                {manifest.name}{" "}
+                <span className="text-white/40 text-xs">
+                  v{manifest.version}
+                </span>
+              </p>
+              <p className="text-white/50 text-xs truncate">
+                {t("soundPacks.sounds", { count: manifest.sounds.length })}
+                {manifest.author &&
+                  ` · ${t("soundPacks.by", { author: manifest.author })}`}
+              </p>
+              <p className="text-white/40 text-xs truncate">
+                {t("soundPacks.license", { license: manifest.license })}
+              </p>
+            </div>
+            <button
+              onClick={() => onSetEnabled(manifest.id, !enabled)}
+              className="shrink-0 text-xs text-white/70 hover:text-white px-2 py-1 rounded-md hover:bg-white/10 transition-all"
+            >
+              {enabled ? t("soundPacks.disable") : t("soundPacks.enable")}
+            </button>
+            <button
+              onClick={() => onUninstall(manifest.id)}
+              title={t("soundPacks.uninstall")}
+              className="shrink-0 text-white/50 hover:text-red-400 p-1 rounded-md hover:bg-white/10 transition-all"
+            >
+              <Trash2 size={14} />
+            </button>
+          </div>
+        ))}
+      </div>
+
+      <div className="px-4 py-3 border-t border-white/10">
+        <button
+          onClick={onInstall}
+          className="w-full flex items-center justify-center gap-2 px-3 py-2 rounded-lg text-sm text-white bg-white/10 hover:bg-white/15 transition-all"
+        >
+          <Plus size={16} />
+          {t("soundPacks.install")}
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -1,22 +1,26 @@
 "use client";
 
-import { useEffect, useRef } from "react";
+import { useEffect, useRef, useState } from "react";
 import { useTranslation } from "react-i18next";
-import { X, Clock, Timer, Sparkles, Crown } from "lucide-react";
+import { X, Clock, Timer, Sparkles, Crown, Headphones } from "lucide-react";
 import { usePro } from "@/hooks";
-import { FREE_DAILY_PLAYTIME_LIMIT, FREE_TIMER_TRIAL_COUNT } from "@/types/pro";
+import { getListeningStats } from "@/lib/history";
+import type { ListeningStats, SoundControl } from "@/types/audio";
 
 interface UsageModalProps {
+  sounds: SoundControl[];
   onClose: () => void;
 }
 
-const MODAL_WINDOW_SIZE = { width: 400, height: 420 };
+const MODAL_WINDOW_SIZE = { width: 400, height: 520 };
+const TOP_SOUNDS_COUNT = 3;
 const PURCHASE_URL =
   "https://zerohz-app.lemonsqueezy.com/buy/7f3f5f67-5a6c-4bec-8bb1-919aa1d735f3";
 
-export function UsageModal({ onClose }: UsageModalProps) {
+export function UsageModal({ sounds, onClose }: UsageModalProps) {
   const { t } = useTranslation();
   const pro = usePro();
+  const [listening, setListening] = useState<ListeningStats | null>(null);
   const originalSizeRef = useRef<{ width: number; height: number } | null>(
     null
   );
@@ -105,6 +109,23 @@ export function UsageModal({ onClose }: UsageModalProps) {
     };
   }, []);
 
+  // 이번 달 청취 기록 (웹 환경에서는 기록이 없다)
+  useEffect(() => {
+    let isMounted = true;
+
+    getListeningStats("month")
+      .then((stats) => {
+        if (isMounted) setListening(stats);
+      })
+      .catch(() => {
+        // Tauri 환경이 아니면 청취 기록 섹션을 숨긴다
+      });
+
+    return () => {
+      isMounted = false;
+    };
+  }, []);
+
   // ESC 키로 닫기
   useEffect(() => {
     function handleEscape(event: KeyboardEvent) {
@@ -141,11 +162,68 @@ export function UsageModal({ onClose }: UsageModalProps) {
     onClose();
   };
 
-  const playtimeUsed = FREE_DAILY_PLAYTIME_LIMIT - pro.dailyPlaytimeRemaining;
-  const playtimePercent = (playtimeUsed / FREE_DAILY_PLAYTIME_LIMIT) * 100;
+  // 카탈로그에서 빠진 사운드(삭제한 팩 등)는 ID를 그대로 보여준다
+  const soundLabel = (id: string): string =>
+    sounds.find((sound) => sound.id === id)?.label ?? id;
+
+  const topSounds = listening?.sounds.slice(0, TOP_SOUNDS_COUNT) ?? [];
+  const topSoundSeconds = topSounds[0]?.seconds ?? 0;
 
-  const timerUsed = FREE_TIMER_TRIAL_COUNT - pro.timerTrialRemaining;
-  const timerPercent = (timerUsed / FREE_TIMER_TRIAL_COUNT) * 100;
+  const listeningSection = listening && (
+    <div className="space-y-3">
+      <div className="flex items-center gap-2 text-white/80">
+        <Headphones size={18} />
+        <span className="text-sm font-medium">
+          {t("usage.listeningThisMonth")}
+        </span>
+      </div>
+
+      <div className="bg-white/5 rounded-xl p-4 border border-white/10">
+        <div className="flex items-baseline justify-between mb-3">
+          <span className="text-2xl font-bold text-white">
+            {formatTime(listening.totalSeconds)}
+          </span>
+          <span className="text-sm text-white/50">
+            {t("usage.sessions", { count: listening.sessionCount })}
+          </span>
+        </div>
+
+        {topSounds.length > 0 ? (
+          <div className="space-y-2">
+            <div className="text-xs text-white/50">{t("usage.topSounds")}</div>
+            {topSounds.map((item) => (
+              <div key={item.sound} className="space-y-1">
+                <div className="flex justify-between text-xs text-white/80">
+                  <span className="truncate">{soundLabel(item.sound)}</span>
+                  <span className="text-white/50">
+                    {formatTime(item.seconds)}
+                  </span>
+                </div>
+                <div className="w-full h-1.5 bg-white/10 rounded-full overflow-hidden">
+                  <div
+                    className="h-full bg-gradient-to-r from-emerald-500 to-teal-500"
+                    style={{
+                      width: `${(item.seconds / topSoundSeconds) * 100}%`,
+                    }}
+                  />
+                </div>
+              </div>
+            ))}
+          </div>
+        ) : (
+          <div className="text-xs text-white/50">
+            {t("usage.noListeningYet")}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+
+  const playtimeUsed = pro.dailyPlaytimeLimit - pro.dailyPlaytimeRemaining;
+  const playtimePercent = (playtimeUsed / pro.dailyPlaytimeLimit) * 100;
+
+  const timerUsed = pro.timerTrialLimit - pro.timerTrialRemaining;
+  const timerPercent = (timerUsed / pro.timerTrialLimit) * 100;
 
   return (
     <div className="fixed inset-0 z-50 flex flex-col bg-[#1A1A1A] overflow-hidden rounded-2xl border border-white/10">
@@ -173,14 +251,20 @@ export function UsageModal({ onClose }: UsageModalProps) {
       <div className="flex-1 p-6 overflow-auto">
         {pro.isPro ? (
           // Pro 사용자
-          <div className="flex flex-col items-center justify-center h-full text-center">
-            <div className="w-20 h-20 rounded-full bg-gradient-to-br from-yellow-500/20 to-amber-500/20 flex items-center justify-center mb-4 border border-yellow-500/30">
-              <Sparkles size={40} className="text-yellow-400" />
+          <div className="space-y-6">
+            <div className="flex flex-col items-center justify-center text-center">
+              <div className="w-20 h-20 rounded-full bg-gradient-to-br from-yellow-500/20 to-amber-500/20 flex items-center justify-center mb-4 border border-yellow-500/30">
+                <Sparkles size={40} className="text-yellow-400" />
+              </div>
+              <h4 className="text-lg font-semibold text-white mb-2">
+                {t("usage.proUnlimited")}
+              </h4>
+              <p className="text-white/60 text-sm">
+                {t("usage.proDescription")}
+              </p>
             </div>
-            <h4 className="text-lg font-semibold text-white mb-2">
-              {t("usage.proUnlimited")}
-            </h4>
-            <p className="text-white/60 text-sm">{t("usage.proDescription")}</p>
+
+            {listeningSection}
           </div>
         ) : (
           // 무료 사용자
@@ -200,7 +284,7 @@ export function UsageModal({ onClose }: UsageModalProps) {
                     {formatTime(playtimeUsed)}
                   </span>
                   <span className="text-sm text-white/50">
-                    / {formatTime(FREE_DAILY_PLAYTIME_LIMIT)}
+                    / {formatTime(pro.dailyPlaytimeLimit)}
                   </span>
                 </div>
 
@@ -237,7 +321,7 @@ export function UsageModal({ onClose }: UsageModalProps) {
                     {timerUsed}
                   </span>
                   <span className="text-sm text-white/50">
-                    / {FREE_TIMER_TRIAL_COUNT} {t("usage.times")}
+                    / {pro.timerTrialLimit} {t("usage.times")}
                   </span>
                 </div>
 
@@ -259,6 +343,9 @@ export function UsageModal({ onClose }: UsageModalProps) {
               </div>
             </div>
 
+            {/* 이번 달 청취 기록 */}
+            {listeningSection}
+
             {/* Pro 업그레이드 CTA */}
             <div className="pt-4 border-t border-white/10">
               <div className="bg-gradient-to-r from-yellow-500/10 to-amber-500/10 rounded-xl p-4 border border-yellow-500/20">
@@ -0,0 +1,262 @@
+"use client";
+
+import { useEffect, useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { X, Webhook as WebhookIcon, Plus, Trash2, Copy } from "lucide-react";
+import { cn } from "@/lib/utils";
+import { useWebhooks } from "@/hooks";
+import type { Webhook } from "@/types/webhook";
+
+interface WebhooksModalProps {
+  onClose: () => void;
+}
+
+const MODAL_WINDOW_SIZE = { width: 420, height: 460 };
+
+export function WebhooksModal({ onClose }: WebhooksModalProps) {
+  const { t } = useTranslation();
+  const { webhooks, addWebhook, removeWebhook, setWebhookEnabled, sendTest } =
+    useWebhooks();
+  const [url, setUrl] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  // 방금 추가한 웹훅의 서명 키 (수신 측 설정용으로 한 번 보여줌)
+  const [added, setAdded] = useState<Webhook | null>(null);
+  const [copied, setCopied] = useState(false);
+  const originalSizeRef = useRef<{ width: number; height: number } | null>(
+    null
+  );
+  const originalPositionRef = useRef<{ x: number; y: number } | null>(null);
+
+  // 모달 열릴 때 윈도우 크기 확장 및 중앙 배치
+  useEffect(() => {
+    const expandWindow = async () => {
+      try {
+        const {
+          getCurrentWindow,
+          LogicalSize,
+          PhysicalPosition,
+          currentMonitor,
+        } = await import("@tauri-apps/api/window");
+        const appWindow = getCurrentWindow();
+
+        // 현재 크기 및 위치 저장
+        const currentSize = await appWindow.outerSize();
+        const currentPos = await appWindow.outerPosition();
+
+        originalSizeRef.current = {
+          width: currentSize.width,
+          height: currentSize.height,
+        };
+        originalPositionRef.current = {
+          x: currentPos.x,
+          y: currentPos.y,
+        };
+
+        // 모달 크기로 확장
+        await appWindow.setSize(
+          new LogicalSize(MODAL_WINDOW_SIZE.width, MODAL_WINDOW_SIZE.height)
+        );
+
+        // 화면 상단 중앙으로 이동
+        const monitor = await currentMonitor();
+        if (monitor) {
+          const scaleFactor = monitor.scaleFactor;
+          const monitorSize = monitor.size;
+          const monitorPosition = monitor.position;
+
+          const physicalWidth = MODAL_WINDOW_SIZE.width * scaleFactor;
+
+          const x =
+            monitorPosition.x +
+            Math.round((monitorSize.width - physicalWidth) / 2);
+          const y = monitorPosition.y + 50;
+
+          await appWindow.setPosition(new PhysicalPosition(x, y));
+        }
+      } catch {
+        console.log("Not in Tauri environment");
+      }
+    };
+
+    expandWindow();
+
+    return () => {
+      const restoreWindow = async () => {
+        if (originalSizeRef.current && originalPositionRef.current) {
+          try {
+            const { getCurrentWindow, PhysicalSize, PhysicalPosition } =
+              await import("@tauri-apps/api/window");
+            const appWindow = getCurrentWindow();
+
+            await appWindow.setSize(
+              new PhysicalSize(
+                originalSizeRef.current.width,
+                originalSizeRef.current.height
+              )
+            );
+
+            await appWindow.setPosition(
+              new PhysicalPosition(
+                originalPositionRef.current.x,
+                originalPositionRef.current.y
+              )
+            );
+          } catch {
+            console.log("Failed to restore window size/position");
+          }
+        }
+      };
+      restoreWindow();
+    };
+  }, []);
+
+  // ESC 키로 닫기
+  useEffect(() => {
+    function handleEscape(event: KeyboardEvent) {
+      if (event.key === "Escape") {
+        onClose();
+      }
+    }
+
+    document.addEventListener("keydown", handleEscape);
+    return () => document.removeEventListener("keydown", handleEscape);
+  }, [onClose]);
+
+  const handleAdd = async () => {
+    if (!url.trim()) return;
+    try {
+      const webhook = await addWebhook(url);
+      setAdded(webhook);
+      setCopied(false);
+      setUrl("");
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleCopySecret = async () => {
+    if (!added) return;
+    try {
+      await navigator.clipboard.writeText(added.secret);
+      setCopied(true);
+    } catch (e) {
+      console.error("Failed to copy secret:", e);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 z-50 flex flex-col bg-[#1A1A1A] overflow-hidden rounded-2xl border border-white/10">
+      {/* 헤더 */}
+      <div className="flex items-center justify-between p-4 border-b border-white/10">
+        <h3 className="text-white text-sm font-semibold">
+          {t("webhooks.title")}
+        </h3>
+        <button
+          onClick={onClose}
+          className="text-white/50 hover:text-white p-1 rounded-full hover:bg-white/10 transition-all"
+        >
+          <X size={16} />
+        </button>
+      </div>
+
+      <div className="flex-1 p-4 overflow-auto space-y-2">
+        <p className="text-white/50 text-xs pb-1">
+          {t("webhooks.description")}
+        </p>
+        {webhooks.length === 0 && (
+          <p className="text-white/50 text-sm text-center py-4">
+            {t("webhooks.empty")}
+          </p>
+        )}
+        {webhooks.map((webhook) => (
+          <div
+            key={webhook.id}
+            className={cn(
+              "flex items-start gap-3 px-3 py-2 rounded-lg bg-white/5",
+              !webhook.enabled && "opacity-60"
+            )}
+          >
+            <WebhookIcon size={16} className="shrink-0 mt-0.5 text-white/70" />
+            <div className="flex-1 min-w-0">
+              <p className="text-white text-sm truncate" title={webhook.url}>
+                {webhook.url}
+              </p>
+              {webhook.pending > 0 && (
+                <p className="text-amber-400/80 text-xs truncate">
+                  {t("webhooks.pending", { count: webhook.pending })}
+                </p>
+              )}
+            </div>
+            <button
+              onClick={() => sendTest(webhook.id)}
+              disabled={!webhook.enabled}
+              className="shrink-0 text-xs text-white/70 hover:text-white px-2 py-1 rounded-md hover:bg-white/10 transition-all disabled:opacity-40"
+            >
+              {t("webhooks.test")}
+            </button>
+            <button
+              onClick={() => setWebhookEnabled(webhook.id, !webhook.enabled)}
+              className="shrink-0 text-xs text-white/70 hover:text-white px-2 py-1 rounded-md hover:bg-white/10 transition-all"
+            >
+              {webhook.enabled ? t("webhooks.disable") : t("webhooks.enable")}
+            </button>
+            <button
+              onClick={() => removeWebhook(webhook.id)}
+              title={t("webhooks.remove")}
+              className="shrink-0 text-white/50 hover:text-red-400 p-1 rounded-md hover:bg-white/10 transition-all"
+            >
+              <Trash2 size={14} />
+            </button>
+          </div>
+        ))}
+
+        {/* 방금 추가한 웹훅의 서명 키 */}
+        {added && webhooks.some((w) => w.id === added.id) && (
+          <div className="px-3 py-2 rounded-lg border border-white/10 space-y-1">
+            <p className="text-white/50 text-xs">{t("webhooks.secret")}</p>
+            <div className="flex items-center gap-2">
+              <code className="flex-1 min-w-0 truncate text-white/80 text-xs font-mono">
+                {added.secret}
+              </code>
+              <button
+                onClick={handleCopySecret}
+                className="shrink-0 flex items-center gap-1 text-xs text-white/70 hover:text-white px-2 py-1 rounded-md hover:bg-white/10 transition-all"
+              >
+                <Copy size={12} />
+                {copied ? t("webhooks.copied") : t("webhooks.copy")}
+              </button>
+            </div>
+          </div>
+        )}
+      </div>
+
+      <div className="px-4 py-3 border-t border-white/10 space-y-2">
+        <div className="flex gap-2">
+          <input
+            type="url"
+            value={url}
+            onChange={(e) => {
+              setUrl(e.target.value);
+              setError(null);
+            }}
+            onKeyDown={(e) => {
+              if (e.key === "Enter") handleAdd();
+            }}
+            placeholder={t("webhooks.urlPlaceholder")}
+            className="flex-1 min-w-0 px-3 py-2 bg-white/5 border border-white/10 rounded-lg text-white placeholder-white/30 focus:outline-none focus:border-white/30 text-sm"
+          />
+          <button
+            onClick={handleAdd}
+            disabled={!url.trim()}
+            className="shrink-0 flex items-center gap-1 px-3 py-2 rounded-lg text-sm text-white bg-white/10 hover:bg-white/15 transition-all disabled:opacity-40"
+          >
+            <Plus size={16} />
+            {t("webhooks.add")}
+          </button>
+        </div>
+        {error && <p className="text-red-400 text-xs">{error}</p>}
+      </div>
+    </div>
+  );
+}
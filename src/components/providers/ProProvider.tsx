@@ -6,42 +6,48 @@ import React, {
   useState,
   useEffect,
   useCallback,
-  useRef,
 } from "react";
-import type { ProSettingsData, UpgradeReason } from "@/types/pro";
+import type { LicenseStatus, UpgradeReason, Usage } from "@/types/pro";
 import { FREE_DAILY_PLAYTIME_LIMIT, FREE_TIMER_TRIAL_COUNT } from "@/types/pro";
-import { validateLicense, activateLicense } from "@/lib/license";
-
-// 저장소 키
-const STORE_FILE = "pro-settings.json";
-const STORE_KEY = "pro";
+import {
+  LICENSE_EVENT,
+  getLicenseStatus,
+  activateLicense,
+  deactivateLicense,
+} from "@/lib/license";
+import { USAGE_EVENT, getUsage } from "@/lib/usage";
 
 // Tauri 환경 체크
 const isTauri =
   typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
 
-// 오늘 날짜 (YYYY-MM-DD)
-const getTodayDate = () => new Date().toISOString().split("T")[0];
+// 웹 환경 (Rust 사용량 집계 없음)
+const WEB_USAGE: Usage = {
+  playtimeSeconds: 0,
+  playtimeLimitSeconds: FREE_DAILY_PLAYTIME_LIMIT,
+  timerTrials: 0,
+  timerTrialLimit: FREE_TIMER_TRIAL_COUNT,
+};
 
 interface ProContextValue {
   // Pro 상태
   isPro: boolean;
   isValidating: boolean;
-  licenseKey: string | null;
+  keyHint: string | null;
+  licensee: string | null;
+  offlineUntil: number | null;
 
-  // Free 제한 상태
+  // Free 제한 상태 (Rust `usage` 모듈이 집계)
+  dailyPlaytimeLimit: number;
   dailyPlaytimeRemaining: number;
+  timerTrialLimit: number;
   timerTrialRemaining: number;
 
   // 액션
   activateLicenseKey: (
     key: string
   ) => Promise<{ success: boolean; error?: string }>;
-  removeLicense: () => void;
-
-  // 플레이타임 추적
-  trackPlaytime: (seconds: number) => void;
-  useTimerTrial: () => boolean;
+  removeLicense: () => Promise<{ success: boolean; error?: string }>;
 
   // 업그레이드 필요 여부
   canPlay: () => boolean;
@@ -58,255 +64,143 @@ export function ProProvider({ children }: { children: React.ReactNode }) {
   // Pro 상태
   const [isPro, setIsPro] = useState(false);
   const [isValidating, setIsValidating] = useState(false);
-  const [licenseKey, setLicenseKey] = useState<string | null>(null);
+  const [keyHint, setKeyHint] = useState<string | null>(null);
+  const [licensee, setLicensee] = useState<string | null>(null);
+  const [offlineUntil, setOfflineUntil] = useState<number | null>(null);
 
   // Free 제한 상태
-  const [dailyPlaytimeUsed, setDailyPlaytimeUsed] = useState(0);
-  const [lastPlayDate, setLastPlayDate] = useState(getTodayDate());
-  const [timerTrialUsed, setTimerTrialUsed] = useState(0);
+  const [usage, setUsage] = useState<Usage>(WEB_USAGE);
 
   const [isLoaded, setIsLoaded] = useState(false);
-  const storeRef = useRef<unknown>(null);
 
   // 계산된 값
   const dailyPlaytimeRemaining = Math.max(
     0,
-    FREE_DAILY_PLAYTIME_LIMIT - dailyPlaytimeUsed
+    usage.playtimeLimitSeconds - usage.playtimeSeconds
   );
   const timerTrialRemaining = Math.max(
     0,
-    FREE_TIMER_TRIAL_COUNT - timerTrialUsed
+    usage.timerTrialLimit - usage.timerTrials
   );
 
-  // 저장
-  const saveSettings = useCallback(async (data: ProSettingsData) => {
-    try {
-      if (isTauri && storeRef.current) {
-        const store = storeRef.current as {
-          set: (key: string, value: unknown) => Promise<void>;
-          save: () => Promise<void>;
-        };
-        await store.set(STORE_KEY, data);
-        await store.save();
-      } else {
-        localStorage.setItem("zerohz_pro_settings", JSON.stringify(data));
-      }
-    } catch (error) {
-      console.error("Failed to save pro settings:", error);
-    }
+  // Rust 라이센스 상태 반영
+  const applyLicenseStatus = useCallback((status: LicenseStatus) => {
+    setIsPro(status.isPro);
+    setKeyHint(status.keyHint);
+    setLicensee(status.licensee);
+    setOfflineUntil(status.offlineUntil);
+    setIsValidating(status.isValidating);
   }, []);
 
   // 초기 로드
   useEffect(() => {
-    const loadSettings = async () => {
+    const loadStatus = async () => {
       try {
-        let saved: ProSettingsData | null = null;
-
         if (isTauri) {
-          const { load } = await import("@tauri-apps/plugin-store");
-          const store = await load(STORE_FILE, {
-            autoSave: true,
-            defaults: {},
-          });
-          storeRef.current = store;
-          saved = (await store.get<ProSettingsData>(STORE_KEY)) ?? null;
-        } else {
-          const raw = localStorage.getItem("zerohz_pro_settings");
-          if (raw) saved = JSON.parse(raw);
-        }
-
-        if (saved) {
-          setLicenseKey(saved.licenseKey);
-          // 날짜 체크 - 다른 날이면 플레이타임 리셋
-          const today = getTodayDate();
-          if (saved.lastPlayDate === today) {
-            setDailyPlaytimeUsed(saved.dailyPlaytimeUsed);
-            setTimerTrialUsed(saved.timerTrialUsed);
-            setLastPlayDate(saved.lastPlayDate);
-          } else {
-            setDailyPlaytimeUsed(0);
-            setTimerTrialUsed(0);
-            setLastPlayDate(today);
-          }
-
-          // 저장된 라이센스 키가 있으면 검증
-          if (saved.licenseKey) {
-            setIsValidating(true);
-            const result = await validateLicense(saved.licenseKey);
-            setIsPro(result.valid);
-            setIsValidating(false);
-
-            // 유효하지 않으면 키 제거
-            if (!result.valid) {
-              setLicenseKey(null);
-              await saveSettings({
-                licenseKey: null,
-                dailyPlaytimeUsed: 0,
-                lastPlayDate: today,
-                timerTrialUsed: saved.timerTrialUsed,
-              });
-            }
-          }
+          applyLicenseStatus(await getLicenseStatus());
+          setUsage(await getUsage());
         }
       } catch (error) {
-        console.error("Failed to load pro settings:", error);
+        console.error("Failed to load pro status:", error);
       }
       setIsLoaded(true);
     };
 
-    loadSettings();
-  }, [saveSettings]);
+    loadStatus();
+  }, [applyLicenseStatus]);
 
-  // 날짜 변경 체크 (1분마다)
+  // 라이센스 상태 변경 (시작 시 검증 완료, 트레이/다른 경로에서 변경)
   useEffect(() => {
-    const checkDate = () => {
-      const today = getTodayDate();
-      if (lastPlayDate !== today) {
-        setDailyPlaytimeUsed(0);
-        setTimerTrialUsed(0);
-        setLastPlayDate(today);
+    if (!isTauri) return;
+    let unlistenFn: (() => void) | undefined;
+    let isMounted = true;
+
+    import("@tauri-apps/api/event")
+      .then(({ listen }) =>
+        listen<LicenseStatus>(LICENSE_EVENT, (event) => {
+          applyLicenseStatus(event.payload);
+        })
+      )
+      .then((fn) => {
+        if (isMounted) {
+          unlistenFn = fn;
+        } else {
+          fn();
+        }
+      })
+      .catch((error) => {
+        console.error("Failed to listen for license changes:", error);
+      });
 
-        saveSettings({
-          licenseKey,
-          dailyPlaytimeUsed: 0,
-          lastPlayDate: today,
-          timerTrialUsed: 0,
-        });
-      }
+    return () => {
+      isMounted = false;
+      unlistenFn?.();
     };
+  }, [applyLicenseStatus]);
+
+  // 사용량 변경 (재생 중 매초, 타이머 완료, 날짜 변경)
+  useEffect(() => {
+    if (!isTauri) return;
+    let unlistenFn: (() => void) | undefined;
+    let isMounted = true;
+
+    import("@tauri-apps/api/event")
+      .then(({ listen }) =>
+        listen<Usage>(USAGE_EVENT, (event) => {
+          setUsage(event.payload);
+        })
+      )
+      .then((fn) => {
+        if (isMounted) {
+          unlistenFn = fn;
+        } else {
+          fn();
+        }
+      })
+      .catch((error) => {
+        console.error("Failed to listen for usage changes:", error);
+      });
 
-    const interval = setInterval(checkDate, 60 * 1000);
-    return () => clearInterval(interval);
-  }, [lastPlayDate, licenseKey, saveSettings]);
+    return () => {
+      isMounted = false;
+      unlistenFn?.();
+    };
+  }, []);
 
-  // 라이센스 활성화
+  // 라이센스 활성화 (Rust가 LemonSqueezy에 등록 후 키 저장)
   const activateLicenseKey = useCallback(
     async (key: string): Promise<{ success: boolean; error?: string }> => {
+      if (!isTauri) {
+        return { success: false, error: "Available in the desktop app" };
+      }
       setIsValidating(true);
 
       try {
-        const result = await activateLicense(key);
-
-        if (result.valid) {
-          setIsPro(true);
-          setLicenseKey(key);
-          await saveSettings({
-            licenseKey: key,
-            dailyPlaytimeUsed,
-            lastPlayDate,
-            timerTrialUsed,
-          });
-          setIsValidating(false);
-          return { success: true };
-        } else {
-          setIsValidating(false);
-          return {
-            success: false,
-            error: result.error || "Invalid license key",
-          };
-        }
+        applyLicenseStatus(await activateLicense(key));
+        return { success: true };
       } catch (error) {
-        console.error("License validation error:", error);
+        console.error("License activation error:", error);
         setIsValidating(false);
-        return {
-          success: false,
-          error: "Failed to validate license",
-        };
+        return { success: false, error: String(error) };
       }
     },
-    [dailyPlaytimeUsed, lastPlayDate, timerTrialUsed, saveSettings]
+    [applyLicenseStatus]
   );
 
-  // 라이센스 제거
-  const removeLicense = useCallback(() => {
-    setIsPro(false);
-    setLicenseKey(null);
-    saveSettings({
-      licenseKey: null,
-      dailyPlaytimeUsed,
-      lastPlayDate,
-      timerTrialUsed,
-    });
-  }, [dailyPlaytimeUsed, lastPlayDate, timerTrialUsed, saveSettings]);
-
-  // 플레이타임 추적
-  const trackPlaytime = useCallback(
-    (seconds: number) => {
-      if (isPro) return;
-
-      const today = getTodayDate();
-      let newUsed = dailyPlaytimeUsed;
-      let newDate = lastPlayDate;
-      let currentTimerTrialUsed = timerTrialUsed;
-
-      if (lastPlayDate !== today) {
-        newUsed = 0;
-        newDate = today;
-        setLastPlayDate(today);
-
-        currentTimerTrialUsed = 0;
-        setTimerTrialUsed(0);
-      }
-
-      newUsed = Math.min(newUsed + seconds, FREE_DAILY_PLAYTIME_LIMIT);
-      setDailyPlaytimeUsed(newUsed);
-
-      saveSettings({
-        licenseKey,
-        dailyPlaytimeUsed: newUsed,
-        lastPlayDate: newDate,
-        timerTrialUsed: currentTimerTrialUsed,
-      });
-    },
-    [
-      isPro,
-      dailyPlaytimeUsed,
-      lastPlayDate,
-      licenseKey,
-      timerTrialUsed,
-      saveSettings,
-    ]
-  );
-
-  // 타이머 트라이얼 사용
-  const useTimerTrial = useCallback((): boolean => {
-    if (isPro) return true;
-
-    const today = getTodayDate();
-    let currentTimerTrialUsed = timerTrialUsed;
-    let currentDailyPlaytimeUsed = dailyPlaytimeUsed;
-
-    // 날짜가 바뀌었으면 리셋
-    if (lastPlayDate !== today) {
-      currentTimerTrialUsed = 0;
-      currentDailyPlaytimeUsed = 0;
-
-      setTimerTrialUsed(0);
-      setDailyPlaytimeUsed(0);
-      setLastPlayDate(today);
+  // 라이센스 제거 (기기 등록 해제)
+  const removeLicense = useCallback(async (): Promise<{
+    success: boolean;
+    error?: string;
+  }> => {
+    if (!isTauri) return { success: true };
+    try {
+      applyLicenseStatus(await deactivateLicense());
+      return { success: true };
+    } catch (error) {
+      console.error("License deactivation error:", error);
+      return { success: false, error: String(error) };
     }
-
-    if (currentTimerTrialUsed >= FREE_TIMER_TRIAL_COUNT) return false;
-
-    const newUsed = currentTimerTrialUsed + 1;
-    setTimerTrialUsed(newUsed);
-
-    saveSettings({
-      licenseKey,
-      dailyPlaytimeUsed: currentDailyPlaytimeUsed,
-      lastPlayDate: today,
-      timerTrialUsed: newUsed,
-    });
-
-    return true;
-  }, [
-    isPro,
-    timerTrialUsed,
-    licenseKey,
-    dailyPlaytimeUsed,
-    lastPlayDate,
-    saveSettings,
-  ]);
+  }, [applyLicenseStatus]);
 
   // 재생 가능 여부
   const canPlay = useCallback((): boolean => {
@@ -330,13 +224,15 @@ export function ProProvider({ children }: { children: React.ReactNode }) {
   const value: ProContextValue = {
     isPro,
     isValidating,
-    licenseKey,
+    keyHint,
+    licensee,
+    offlineUntil,
+    dailyPlaytimeLimit: usage.playtimeLimitSeconds,
     dailyPlaytimeRemaining,
+    timerTrialLimit: usage.timerTrialLimit,
     timerTrialRemaining,
     activateLicenseKey,
     removeLicense,
-    trackPlaytime,
-    useTimerTrial,
     canPlay,
     canUseTimer,
     getUpgradeReason,
@@ -9,8 +9,16 @@ interface TrayLabels {
   show_window: string;
   session_history: string;
   usage: string;
+  audio_output: string;
+  sound_packs: string;
+  webhooks: string;
   start_at_login: string;
+  local_api: string;
+  shell_hooks: string;
+  share_usage_data: string;
   activate_license: string;
+  manage_license: string;
+  manage_license_offline: string;
   language: string;
   check_for_updates: string;
   quit: string;
@@ -25,8 +33,16 @@ export default function TrayLanguageSync() {
       show_window: t("tray.showWindow"),
       session_history: t("tray.sessionHistory"),
       usage: t("tray.usage"),
+      audio_output: t("tray.audioOutput"),
+      sound_packs: t("tray.soundPacks"),
+      webhooks: t("tray.webhooks"),
       start_at_login: t("tray.startAtLogin"),
+      local_api: t("tray.localApi"),
+      shell_hooks: t("tray.shellHooks"),
+      share_usage_data: t("tray.shareUsageData"),
       activate_license: t("tray.activateLicense"),
+      manage_license: t("tray.manageLicense"),
+      manage_license_offline: t("tray.manageLicenseOffline"),
       language: t("tray.language"),
       check_for_updates: t("tray.checkForUpdates"),
       quit: t("tray.quit"),
@@ -1,81 +1,18 @@
-import {
-  Wind,
-  CloudRain,
-  Flame,
-  Waves,
-  Bird,
-  Droplets,
-  Plane,
-  TrainFront,
-  Moon,
-  Keyboard,
-  CloudLightning,
-  Utensils,
-} from "lucide-react";
-import type { SoundControl, SoundType, SoundVolumes } from "@/types/audio";
+import type { SoundEffect } from "@/types/audio";
 
-export const SOUNDS: SoundControl[] = [
-  // Nature
-  { id: "rain", icon: CloudRain, label: "Rain", src: "/sounds/rain.m4a" },
-  { id: "wind", icon: Wind, label: "Wind", src: "/sounds/wind.m4a" },
-  { id: "waves", icon: Waves, label: "Waves", src: "/sounds/waves.m4a" },
-  // Life
-  { id: "forest", icon: Bird, label: "Forest", src: "/sounds/forest.m4a" },
-  { id: "stream", icon: Droplets, label: "Stream", src: "/sounds/stream.m4a" },
-  { id: "fire", icon: Flame, label: "Fire", src: "/sounds/fire.m4a" },
-  // Places
-  { id: "flight", icon: Plane, label: "Flight", src: "/sounds/flight.m4a" },
-  { id: "train", icon: TrainFront, label: "Train", src: "/sounds/train.m4a" },
-  { id: "night", icon: Moon, label: "Night", src: "/sounds/night.m4a" },
-  // Work
-  {
-    id: "keyboard",
-    icon: Keyboard,
-    label: "Keyboard",
-    src: "/sounds/keyboard.m4a",
-  },
-  {
-    id: "thunder",
-    icon: CloudLightning,
-    label: "Thunder",
-    src: "/sounds/thunder.m4a",
-  },
-  {
-    id: "glass-fruit",
-    icon: Utensils,
-    label: "Glass Fruit",
-    src: "/sounds/glass-fruit.m4a",
-  },
-];
+// 사운드 목록은 Rust 카탈로그(`list_sounds`)가 관리한다. 볼륨/효과는 사운드를
+// 처음 켜거나 조절할 때 아래 기본값으로 채워진다.
 
-export const SOUND_IDS: SoundType[] = [
-  "rain",
-  "wind",
-  "waves",
-  "forest",
-  "stream",
-  "fire",
-  "flight",
-  "train",
-  "night",
-  "keyboard",
-  "thunder",
-  "glass-fruit",
-];
+// 데스크톱 앱에서는 Rust가 사운드별 라우드니스(EBU R128)를 맞춘 뒤 적용하므로
+// 같은 볼륨 값이면 모든 사운드가 비슷한 크기로 들린다.
+export const DEFAULT_VOLUME = 0.5;
 
-export const DEFAULT_VOLUMES: SoundVolumes = {
-  rain: 0.5,
-  wind: 0.5,
-  waves: 0.5,
-  forest: 0.5,
-  stream: 0.5,
-  fire: 0.5,
-  flight: 0.5,
-  train: 0.5,
-  night: 0.5,
-  keyboard: 0.5,
-  thunder: 0.5,
-  "glass-fruit": 0.5,
-};
+/**
+ * 톤 컨트롤 범위 (로우패스 컷오프, Hz). 최댓값이면 필터 꺼짐
+ */
+export const MIN_LOWPASS = 200;
+export const MAX_LOWPASS = 20000;
+
+export const DEFAULT_EFFECT: SoundEffect = { pan: 0, lowpass: MAX_LOWPASS };
 
 export const ITEMS_PER_SLIDE = 4;
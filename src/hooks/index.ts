@@ -1,8 +1,15 @@
 export { useAudioPlayer } from "./useAudioPlayer";
+export { useAudioDevices } from "./useAudioDevices";
+export { useRemoteCommands } from "./useRemoteCommands";
+export { useCustomSounds } from "./useCustomSounds";
+export { useSoundPacks } from "./useSoundPacks";
+export { useWebhooks } from "./useWebhooks";
+export { useSoundCatalog } from "./useSoundCatalog";
 export { useWindowResize } from "./useWindowResize";
 export { useElapsedTime } from "./useElapsedTime";
 export { usePlaybackTracking } from "./usePlaybackTracking";
 export { useTimer } from "./useTimer";
+export { useTimerSync } from "./useTimerSync";
 export { useTimerSessions, createSessionData } from "./useTimerSessions";
 export { useAppSettings } from "./useAppSettings";
 export { usePro } from "./usePro";
@@ -1,7 +1,7 @@
 "use client";
 
 import { useState, useEffect, useCallback, useRef } from "react";
-import type { SoundType, SoundVolumes } from "@/types/audio";
+import type { SoundEffects, SoundType, SoundVolumes } from "@/types/audio";
 import type { AppSettings } from "@/types/app";
 import { DEFAULT_APP_SETTINGS } from "@/types/app";
 
@@ -17,12 +17,14 @@ interface UseAppSettingsReturn {
   // Audio settings
   activeSounds: Set<SoundType>;
   volumes: SoundVolumes;
+  effects: SoundEffects;
   isMuted: boolean;
   language: string;
 
   // Setters
   setActiveSounds: (sounds: Set<SoundType>) => void;
   setVolumes: (volumes: SoundVolumes) => void;
+  setEffects: (effects: SoundEffects) => void;
   setIsMuted: (muted: boolean) => void;
   setLanguage: (lang: string) => void;
 
@@ -41,6 +43,9 @@ export function useAppSettings(): UseAppSettingsReturn {
   const [volumes, setVolumesState] = useState<SoundVolumes>(
     DEFAULT_APP_SETTINGS.volumes
   );
+  const [effects, setEffectsState] = useState<SoundEffects>(
+    DEFAULT_APP_SETTINGS.effects
+  );
   const [isMuted, setIsMutedState] = useState(DEFAULT_APP_SETTINGS.isMuted);
   const [language, setLanguageState] = useState(DEFAULT_APP_SETTINGS.language);
   const [isLoaded, setIsLoaded] = useState(false);
@@ -53,17 +58,23 @@ export function useAppSettings(): UseAppSettingsReturn {
       try {
         if (isTauri) {
           // Tauri Store 사용
-          const { load } = await import("@tauri-apps/plugin-store");
-          const store = await load(STORE_FILE, {
-            autoSave: true,
-            defaults: {},
-          });
+          // 웹뷰는 경로로 스토어를 열 수 없다. Rust가 시작할 때 열어 둔다 (src-tauri/src/protected.rs)
+          const { getStore } = await import("@tauri-apps/plugin-store");
+          const store = await getStore(STORE_FILE);
+          if (!store) {
+            throw new Error(`${STORE_FILE} is not open`);
+          }
           storeRef.current = store;
 
           const saved = await store.get<AppSettings>(STORE_KEY);
           if (saved) {
             setActiveSoundsState(new Set(saved.activeSounds));
             setVolumesState(saved.volumes);
+            // 이전 버전 설정에는 effects가 없음
+            setEffectsState({
+              ...DEFAULT_APP_SETTINGS.effects,
+              ...saved.effects,
+            });
             setIsMutedState(saved.isMuted);
             setLanguageState(saved.language);
           }
@@ -74,6 +85,10 @@ export function useAppSettings(): UseAppSettingsReturn {
             const settings = JSON.parse(saved) as AppSettings;
             setActiveSoundsState(new Set(settings.activeSounds));
             setVolumesState(settings.volumes);
+            setEffectsState({
+              ...DEFAULT_APP_SETTINGS.effects,
+              ...settings.effects,
+            });
             setIsMutedState(settings.isMuted);
             setLanguageState(settings.language);
           }
@@ -117,11 +132,12 @@ export function useAppSettings(): UseAppSettingsReturn {
       saveSettings({
         activeSounds: Array.from(sounds),
         volumes,
+        effects,
         isMuted,
         language,
       });
     },
-    [volumes, isMuted, language, saveSettings]
+    [volumes, effects, isMuted, language, saveSettings]
   );
 
   const setVolumes = useCallback(
@@ -130,11 +146,26 @@ export function useAppSettings(): UseAppSettingsReturn {
       saveSettings({
         activeSounds: Array.from(activeSounds),
         volumes: newVolumes,
+        effects,
+        isMuted,
+        language,
+      });
+    },
+    [activeSounds, effects, isMuted, language, saveSettings]
+  );
+
+  const setEffects = useCallback(
+    (newEffects: SoundEffects) => {
+      setEffectsState(newEffects);
+      saveSettings({
+        activeSounds: Array.from(activeSounds),
+        volumes,
+        effects: newEffects,
         isMuted,
         language,
       });
     },
-    [activeSounds, isMuted, language, saveSettings]
+    [activeSounds, volumes, isMuted, language, saveSettings]
   );
 
   const setIsMuted = useCallback(
@@ -143,11 +174,12 @@ export function useAppSettings(): UseAppSettingsReturn {
       saveSettings({
         activeSounds: Array.from(activeSounds),
         volumes,
+        effects,
         isMuted: muted,
         language,
       });
     },
-    [activeSounds, volumes, language, saveSettings]
+    [activeSounds, volumes, effects, language, saveSettings]
   );
 
   const setLanguage = useCallback(
@@ -156,16 +188,18 @@ export function useAppSettings(): UseAppSettingsReturn {
       saveSettings({
         activeSounds: Array.from(activeSounds),
         volumes,
+        effects,
         isMuted,
         language: lang,
       });
     },
-    [activeSounds, volumes, isMuted, saveSettings]
+    [activeSounds, volumes, effects, isMuted, saveSettings]
   );
 
   return {
     activeSounds,
     volumes,
+    effects,
     isMuted,
     language,
     setActiveSounds,
@@ -0,0 +1,80 @@
+"use client";
+
+import { useState, useEffect, useCallback } from "react";
+import type { AudioOutputStatus, OutputDevice } from "@/types/audio";
+
+const isTauri =
+  typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
+
+/**
+ * useAudioDevices - 오디오 출력 장치 목록과 선택 상태를 관리하는 훅
+ *
+ * 장치 목록은 Rust가 열거하며, 기본 장치가 바뀌거나 선택한 장치가
+ * 분리/재연결되면 `audio-device-changed` 이벤트로 갱신된다.
+ *
+ * @returns devices - 사용 가능한 출력 장치 목록
+ * @returns status - 선택된 장치 / 실제 출력 장치 / 시스템 기본 장치
+ * @returns refresh - 장치 목록 다시 불러오기
+ * @returns selectDevice - 출력 장치 선택 (null = 시스템 기본값)
+ */
+export function useAudioDevices() {
+  const [devices, setDevices] = useState<OutputDevice[]>([]);
+  const [status, setStatus] = useState<AudioOutputStatus | null>(null);
+
+  const refresh = useCallback(async () => {
+    if (!isTauri) return;
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      const [list, current] = await Promise.all([
+        invoke<OutputDevice[]>("list_audio_devices"),
+        invoke<AudioOutputStatus>("get_audio_device"),
+      ]);
+      setDevices(list);
+      setStatus(current);
+    } catch (error) {
+      console.error("Failed to load audio devices:", error);
+    }
+  }, []);
+
+  useEffect(() => {
+    if (!isTauri) return;
+    let unlisten: (() => void) | undefined;
+    let isMounted = true;
+
+    const setup = async () => {
+      await refresh();
+      const { listen } = await import("@tauri-apps/api/event");
+      const fn = await listen<AudioOutputStatus>(
+        "audio-device-changed",
+        (event) => {
+          setStatus(event.payload);
+          refresh();
+        }
+      );
+      if (isMounted) {
+        unlisten = fn;
+      } else {
+        fn();
+      }
+    };
+
+    setup();
+
+    return () => {
+      isMounted = false;
+      unlisten?.();
+    };
+  }, [refresh]);
+
+  const selectDevice = useCallback(async (name: string | null) => {
+    if (!isTauri) return;
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      setStatus(await invoke<AudioOutputStatus>("set_audio_device", { name }));
+    } catch (error) {
+      console.error("Failed to select audio device:", error);
+    }
+  }, []);
+
+  return { devices, status, refresh, selectDevice };
+}
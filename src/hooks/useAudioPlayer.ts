@@ -1,34 +1,55 @@
 "use client";
 
 import { useState, useRef, useEffect, useCallback } from "react";
-import type { SoundType, SoundVolumes } from "@/types/audio";
-import { SOUNDS, DEFAULT_VOLUMES } from "@/constants/sounds";
+import type {
+  SoundControl,
+  SoundEffect,
+  SoundEffects,
+  SoundType,
+  SoundVolumes,
+} from "@/types/audio";
+import { DEFAULT_VOLUME, DEFAULT_EFFECT } from "@/constants/sounds";
+
+// Tauri 환경 체크
+const isTauri =
+  typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
 
 /**
- * useAudioPlayer - Web Audio API를 사용한 오디오 재생 관리 훅
+ * useAudioPlayer - 오디오 재생 관리 훅
+ * Tauri 환경에서는 Rust 오디오 엔진이 재생하고, 웹에서는 Web Audio API 사용
  *
  * @returns activeSounds - 현재 활성화된 사운드 ID들의 Set
  * @returns volumes - 각 사운드별 볼륨 값 (0~1)을 담은 객체
+ * @returns effects - 각 사운드별 팬/톤(로우패스) 설정
  * @returns isMuted - 전체 음소거 상태
  * @returns isPlaying - 오디오 재생 상태 (true: 재생 중, false: 일시정지)
  * @returns toggleSound - 특정 사운드 활성화/비활성화 토글 함수
  * @returns handleVolumeChange - 특정 사운드의 볼륨 변경 함수
+ * @returns handleEffectChange - 특정 사운드의 팬/톤 변경 함수
+ * @returns selectSounds - 지정한 사운드만 켜기 (나머지는 끄기)
+ * @returns stopAll - 모든 사운드 끄기
  * @returns toggleMute - 전체 음소거 토글 함수
  * @returns togglePlayPause - 재생/일시정지 토글 함수 (AudioContext suspend/resume)
  */
 interface UseAudioPlayerReturn {
   activeSounds: Set<SoundType>;
   volumes: SoundVolumes;
+  effects: SoundEffects;
   isMuted: boolean;
   isPlaying: boolean;
   toggleSound: (id: SoundType) => void;
   handleVolumeChange: (id: SoundType, value: number) => void;
+  handleEffectChange: (id: SoundType, effect: Partial<SoundEffect>) => void;
+  selectSounds: (ids: SoundType[]) => void;
+  stopAll: () => void;
   toggleMute: () => void;
   togglePlayPause: () => Promise<void>;
 }
 
 interface UseAudioPlayerOptions {
+  sounds?: SoundControl[]; // 웹 재생용 사운드 목록 (Tauri에서는 Rust가 재생)
   initialVolumes?: SoundVolumes;
+  initialEffects?: SoundEffects;
   initialMuted?: boolean;
   initialActiveSounds?: Set<SoundType>;
 }
@@ -37,7 +58,9 @@ export function useAudioPlayer(
   options: UseAudioPlayerOptions = {}
 ): UseAudioPlayerReturn {
   const {
-    initialVolumes = DEFAULT_VOLUMES,
+    sounds = [],
+    initialVolumes = {},
+    initialEffects = {},
     initialMuted = false,
     initialActiveSounds = new Set(),
   } = options;
@@ -45,55 +68,15 @@ export function useAudioPlayer(
   const [activeSounds, setActiveSounds] =
     useState<Set<SoundType>>(initialActiveSounds);
   const [volumes, setVolumes] = useState<SoundVolumes>(initialVolumes);
+  const [effects, setEffects] = useState<SoundEffects>(initialEffects);
   const [isMuted, setIsMuted] = useState(initialMuted);
   const [isPlaying, setIsPlaying] = useState(false);
 
   // Web Audio API refs
   const audioContextRef = useRef<AudioContext | null>(null);
-  const gainNodesRef = useRef<Record<SoundType, GainNode | null>>({
-    rain: null,
-    wind: null,
-    waves: null,
-    forest: null,
-    stream: null,
-    fire: null,
-    flight: null,
-    train: null,
-    night: null,
-    keyboard: null,
-    thunder: null,
-    "glass-fruit": null,
-  });
-  const sourceNodesRef = useRef<
-    Record<SoundType, AudioBufferSourceNode | null>
-  >({
-    rain: null,
-    wind: null,
-    waves: null,
-    forest: null,
-    stream: null,
-    fire: null,
-    flight: null,
-    train: null,
-    night: null,
-    keyboard: null,
-    thunder: null,
-    "glass-fruit": null,
-  });
-  const audioBuffersRef = useRef<Record<SoundType, AudioBuffer | null>>({
-    rain: null,
-    wind: null,
-    waves: null,
-    forest: null,
-    stream: null,
-    fire: null,
-    flight: null,
-    train: null,
-    night: null,
-    keyboard: null,
-    thunder: null,
-    "glass-fruit": null,
-  });
+  const gainNodesRef = useRef<Record<SoundType, GainNode | null>>({});
+  const sourceNodesRef = useRef<Record<SoundType, AudioBufferSourceNode | null>>({});
+  const audioBuffersRef = useRef<Record<SoundType, AudioBuffer | null>>({});
 
   // Keep track of active sounds in a ref for async access
   const activeSoundsRef = useRef<Set<SoundType>>(new Set());
@@ -103,8 +86,10 @@ export function useAudioPlayer(
     activeSoundsRef.current = activeSounds;
   }, [activeSounds]);
 
-  // Initialize AudioContext
+  // Initialize AudioContext (웹 전용)
   useEffect(() => {
+    if (isTauri) return;
+
     const AudioContextClass =
       window.AudioContext ||
       (
@@ -137,7 +122,7 @@ export function useAudioPlayer(
       source.loop = true;
 
       const gainNode = audioContextRef.current.createGain();
-      gainNode.gain.value = isMuted ? 0 : volumes[id];
+      gainNode.gain.value = isMuted ? 0 : volumes[id] ?? DEFAULT_VOLUME;
 
       source.connect(gainNode);
       gainNode.connect(audioContextRef.current.destination);
@@ -162,9 +147,11 @@ export function useAudioPlayer(
     }
   }, []);
 
-  // Load audio buffers - Run only ONCE
+  // Load audio buffers - 사운드마다 한 번만 (웹 전용)
   useEffect(() => {
-    const loadAudio = async (sound: (typeof SOUNDS)[number]) => {
+    if (isTauri) return;
+
+    const loadAudio = async (sound: SoundControl) => {
       try {
         const response = await fetch(sound.src);
         const arrayBuffer = await response.arrayBuffer();
@@ -184,14 +171,21 @@ export function useAudioPlayer(
       }
     };
 
-    SOUNDS.forEach(loadAudio);
+    sounds
+      .filter((sound) => audioBuffersRef.current[sound.id] === undefined)
+      .forEach((sound) => {
+        audioBuffersRef.current[sound.id] = null;
+        loadAudio(sound);
+      });
     // eslint-disable-next-line react-hooks/exhaustive-deps
-  }, []);
+  }, [sounds]);
 
   // Handle active sounds changes
   useEffect(() => {
-    SOUNDS.forEach((sound) => {
-      const isPlayingNode = sourceNodesRef.current[sound.id] !== null;
+    if (isTauri) return;
+
+    sounds.forEach((sound) => {
+      const isPlayingNode = !!sourceNodesRef.current[sound.id];
       const shouldPlay = activeSounds.has(sound.id);
 
       if (shouldPlay && !isPlayingNode) {
@@ -204,15 +198,15 @@ export function useAudioPlayer(
         stopSound(sound.id);
       }
     });
-  }, [activeSounds, playSound, stopSound, isPlaying]);
+  }, [sounds, activeSounds, playSound, stopSound, isPlaying]);
 
   // Handle volume/mute changes
   useEffect(() => {
-    SOUNDS.forEach((sound) => {
+    sounds.forEach((sound) => {
       const gainNode = gainNodesRef.current[sound.id];
       if (gainNode) {
         // Smooth volume transition
-        const targetVolume = isMuted ? 0 : volumes[sound.id];
+        const targetVolume = isMuted ? 0 : volumes[sound.id] ?? DEFAULT_VOLUME;
         gainNode.gain.setTargetAtTime(
           targetVolume,
           audioContextRef.current!.currentTime,
@@ -220,9 +214,34 @@ export function useAudioPlayer(
         );
       }
     });
-  }, [volumes, isMuted]);
+  }, [sounds, volumes, isMuted]);
+
+  // Tauri: 재생 상태를 Rust 오디오 엔진에 동기화
+  useEffect(() => {
+    if (!isTauri) return;
+
+    const syncAudio = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke("sync_audio_state", {
+          activeSounds: Array.from(activeSounds),
+          volumes,
+          effects,
+          isMuted,
+        });
+      } catch (error) {
+        console.error("Failed to sync audio state:", error);
+      }
+    };
+
+    syncAudio();
+  }, [activeSounds, volumes, effects, isMuted]);
 
   const toggleSound = useCallback((id: SoundType) => {
+    // 처음 켜는 사운드에는 기본 볼륨을 부여
+    setVolumes((prev) =>
+      prev[id] === undefined ? { ...prev, [id]: DEFAULT_VOLUME } : prev
+    );
     setActiveSounds((prev) => {
       const newSet = new Set(prev);
       if (newSet.has(id)) {
@@ -238,6 +257,33 @@ export function useAudioPlayer(
     setVolumes((prev) => ({ ...prev, [id]: value }));
   }, []);
 
+  const handleEffectChange = useCallback(
+    (id: SoundType, effect: Partial<SoundEffect>) => {
+      setEffects((prev) => ({
+        ...prev,
+        [id]: { ...DEFAULT_EFFECT, ...prev[id], ...effect },
+      }));
+    },
+    []
+  );
+
+  const selectSounds = useCallback((ids: SoundType[]) => {
+    setVolumes((prev) => {
+      const missing = ids.filter((id) => prev[id] === undefined);
+      if (missing.length === 0) return prev;
+      const next = { ...prev };
+      missing.forEach((id) => {
+        next[id] = DEFAULT_VOLUME;
+      });
+      return next;
+    });
+    setActiveSounds(new Set(ids));
+  }, []);
+
+  const stopAll = useCallback(() => {
+    setActiveSounds(new Set());
+  }, []);
+
   const toggleMute = useCallback(() => {
     setIsMuted((prev) => !prev);
   }, []);
@@ -257,10 +303,14 @@ export function useAudioPlayer(
   return {
     activeSounds,
     volumes,
+    effects,
     isMuted,
     isPlaying,
     toggleSound,
     handleVolumeChange,
+    handleEffectChange,
+    selectSounds,
+    stopAll,
     toggleMute,
     togglePlayPause,
   };
@@ -0,0 +1,60 @@
+"use client";
+
+import { useState, useEffect, useCallback } from "react";
+import type { CustomSound } from "@/types/audio";
+
+const isTauri =
+  typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
+
+/**
+ * useCustomSounds - 사용자가 가져온 사운드 목록을 관리하는 훅
+ *
+ * 파일 선택, 디코딩 검증, 앱 데이터 폴더로의 복사는 모두 Rust
+ * `import_sound` 커맨드가 처리한다. 웹 환경에서는 항상 빈 목록이다.
+ * 사운드 버튼 목록은 useSoundCatalog가 관리한다.
+ *
+ * @returns sounds - 가져온 사운드 목록
+ * @returns importSound - 파일 선택 후 가져오기 (취소 시 null)
+ * @returns removeSound - 가져온 사운드 삭제
+ */
+export function useCustomSounds() {
+  const [sounds, setSounds] = useState<CustomSound[]>([]);
+
+  useEffect(() => {
+    if (!isTauri) return;
+
+    const loadSounds = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        setSounds(await invoke<CustomSound[]>("list_custom_sounds"));
+      } catch (error) {
+        console.error("Failed to load custom sounds:", error);
+      }
+    };
+
+    loadSounds();
+  }, []);
+
+  const importSound = useCallback(async (): Promise<CustomSound | null> => {
+    if (!isTauri) return null;
+    const { invoke } = await import("@tauri-apps/api/core");
+    const sound = await invoke<CustomSound | null>("import_sound");
+    if (sound) {
+      setSounds((prev) => [...prev, sound]);
+    }
+    return sound;
+  }, []);
+
+  const removeSound = useCallback(async (id: CustomSound["id"]) => {
+    if (!isTauri) return;
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      await invoke("remove_custom_sound", { soundId: id });
+      setSounds((prev) => prev.filter((s) => s.id !== id));
+    } catch (error) {
+      console.error("Failed to remove custom sound:", error);
+    }
+  }, []);
+
+  return { sounds, importSound, removeSound };
+}
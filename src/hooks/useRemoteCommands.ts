@@ -0,0 +1,104 @@
+"use client";
+
+import { useEffect, useRef } from "react";
+import type { PlaybackCommand } from "@/types/audio";
+import type { TimerCommand } from "@/types/timer";
+import type { RemotePanel } from "@/types/app";
+
+const isTauri =
+  typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
+
+const PANELS: RemotePanel[] = [
+  "session-history",
+  "license-input",
+  "usage",
+  "audio-output",
+  "sound-packs",
+  "webhooks",
+];
+
+interface RemoteCommandHandlers {
+  onOpen: (panel: RemotePanel) => void;
+  onPlayback: (command: PlaybackCommand) => void;
+  onTimer: (command: TimerCommand) => void;
+}
+
+/**
+ * useRemoteCommands - 창 밖(트레이 메뉴, OS 미디어 키, MPRIS, 명령줄, zerohz:// 링크, 로컬 API)에서 온 명령을 처리하는 훅
+ *
+ * Rust가 `open-<panel>` / `playback-command` / `timer-command` 이벤트를 보내면 핸들러를 호출한다.
+ * 리스너가 모두 등록되면 remote_ready로 알려, 앱 실행 시 받은 명령도 전달받는다.
+ * 결과 상태는 평소처럼 sync_audio_state / sync_timer_state로 Rust에 다시 동기화된다.
+ *
+ * @param handlers - 명령 핸들러 (항상 최신 핸들러가 호출됨)
+ */
+export function useRemoteCommands(handlers: RemoteCommandHandlers) {
+  const handlersRef = useRef(handlers);
+
+  useEffect(() => {
+    handlersRef.current = handlers;
+  }, [handlers]);
+
+  useEffect(() => {
+    if (!isTauri) return;
+    const unlistenPanels: (() => void)[] = [];
+    let unlistenPlayback: (() => void) | undefined;
+    let unlistenTimer: (() => void) | undefined;
+    let isMounted = true;
+
+    const setup = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const { invoke } = await import("@tauri-apps/api/core");
+
+        for (const panel of PANELS) {
+          const panelFn = await listen(`open-${panel}`, () => {
+            handlersRef.current.onOpen(panel);
+          });
+          if (isMounted) {
+            unlistenPanels.push(panelFn);
+          } else {
+            panelFn();
+            return;
+          }
+        }
+
+        const playbackFn = await listen<PlaybackCommand>(
+          "playback-command",
+          (event) => {
+            handlersRef.current.onPlayback(event.payload);
+          }
+        );
+        if (isMounted) {
+          unlistenPlayback = playbackFn;
+        } else {
+          playbackFn();
+          return;
+        }
+
+        const timerFn = await listen<TimerCommand>("timer-command", (event) => {
+          handlersRef.current.onTimer(event.payload);
+        });
+        if (isMounted) {
+          unlistenTimer = timerFn;
+        } else {
+          timerFn();
+          return;
+        }
+
+        await invoke("remote_ready");
+      } catch (error) {
+        console.error("Failed to listen for remote commands:", error);
+      }
+    };
+
+    setup();
+
+    return () => {
+      isMounted = false;
+      unlistenPanels.forEach((fn) => fn());
+      unlistenPlayback?.();
+      unlistenTimer?.();
+    };
+  }, []);
+}
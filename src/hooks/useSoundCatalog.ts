@@ -0,0 +1,89 @@
+"use client";
+
+import { useState, useEffect, useMemo } from "react";
+import { convertFileSrc } from "@tauri-apps/api/core";
+import { soundIcon } from "@/lib/sound-icons";
+import type { CatalogSound, SoundControl } from "@/types/audio";
+
+const isTauri =
+  typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
+
+/**
+ * useSoundCatalog - Rust 사운드 카탈로그(`list_sounds`)를 불러오는 훅
+ *
+ * 내장 사운드, 가져온 사운드, 활성화된 팩의 사운드가 모두 Rust에 등록되어 있으므로
+ * 새 사운드를 추가할 때 프론트엔드 코드는 바꿀 필요가 없다.
+ * 가져오기/팩 설치 등으로 목록이 바뀌면 `sound-catalog-changed` 이벤트를 받아 다시 불러온다.
+ * 웹 환경에서는 항상 빈 목록이다.
+ *
+ * @returns sounds - 카탈로그 원본 목록
+ * @returns controls - 사운드 버튼에 바로 쓸 수 있는 SoundControl 목록
+ * @returns isLoaded - 첫 로드 완료 여부
+ */
+export function useSoundCatalog() {
+  const [sounds, setSounds] = useState<CatalogSound[]>([]);
+  const [isLoaded, setIsLoaded] = useState(!isTauri);
+
+  useEffect(() => {
+    if (!isTauri) return;
+
+    let unlistenFn: (() => void) | undefined;
+    let isMounted = true;
+
+    const loadSounds = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        const catalog = await invoke<CatalogSound[]>("list_sounds");
+        if (isMounted) {
+          setSounds(catalog);
+        }
+      } catch (error) {
+        console.error("Failed to load sound catalog:", error);
+      }
+      if (isMounted) {
+        setIsLoaded(true);
+      }
+    };
+
+    const setupListener = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const fn = await listen("sound-catalog-changed", loadSounds);
+        if (isMounted) {
+          unlistenFn = fn;
+        } else {
+          fn();
+        }
+      } catch (error) {
+        console.error("Failed to listen for catalog changes:", error);
+      }
+    };
+
+    loadSounds();
+    setupListener();
+
+    return () => {
+      isMounted = false;
+      unlistenFn?.();
+    };
+  }, []);
+
+  // 파일 에셋(가져온 사운드, 팩)은 asset 프로토콜 URL로 변환
+  const controls = useMemo<SoundControl[]>(
+    () =>
+      sounds.map((sound) => ({
+        id: sound.id,
+        icon: soundIcon(sound.icon),
+        label: sound.label,
+        src:
+          sound.asset.kind === "file"
+            ? convertFileSrc(sound.asset.path)
+            : sound.asset.path,
+        category: sound.category ?? undefined,
+        isCustom: sound.source.type === "custom",
+      })),
+    [sounds]
+  );
+
+  return { sounds, controls, isLoaded };
+}
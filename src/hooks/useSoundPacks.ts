@@ -0,0 +1,89 @@
+"use client";
+
+import { useState, useEffect, useCallback } from "react";
+import type { InstalledPack } from "@/types/audio";
+
+const isTauri =
+  typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
+
+/**
+ * useSoundPacks - 설치된 사운드 팩을 관리하는 훅
+ *
+ * 압축 해제, manifest 검증, 디코딩 검증은 모두 Rust
+ * `install_sound_pack` 커맨드가 처리한다 (docs/02-sound-packs.md).
+ * 웹 환경에서는 항상 빈 목록이다. 팩 사운드의 버튼 목록은 useSoundCatalog가 관리한다.
+ *
+ * @returns packs - 설치된 팩 목록
+ * @returns installPack - 파일 선택 후 설치 (취소 시 null)
+ * @returns setPackEnabled - 팩 활성화/비활성화
+ * @returns uninstallPack - 팩 삭제
+ */
+export function useSoundPacks() {
+  const [packs, setPacks] = useState<InstalledPack[]>([]);
+
+  useEffect(() => {
+    if (!isTauri) return;
+
+    const loadPacks = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        setPacks(await invoke<InstalledPack[]>("list_sound_packs"));
+      } catch (error) {
+        console.error("Failed to load sound packs:", error);
+      }
+    };
+
+    loadPacks();
+  }, []);
+
+  /**
+   * replace가 true면 같은 ID의 팩을 새 버전으로 교체
+   * 실패 시 Rust 쪽에서 오류 대화상자를 띄우고 reject
+   */
+  const installPack = useCallback(
+    async (replace = false): Promise<InstalledPack | null> => {
+      if (!isTauri) return null;
+      const { invoke } = await import("@tauri-apps/api/core");
+      const pack = await invoke<InstalledPack | null>("install_sound_pack", {
+        replace,
+      });
+      if (pack) {
+        setPacks((prev) => [
+          ...prev.filter((p) => p.manifest.id !== pack.manifest.id),
+          pack,
+        ]);
+      }
+      return pack;
+    },
+    []
+  );
+
+  const setPackEnabled = useCallback(
+    async (packId: string, enabled: boolean) => {
+      if (!isTauri) return;
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke("set_sound_pack_enabled", { packId, enabled });
+        setPacks((prev) =>
+          prev.map((p) => (p.manifest.id === packId ? { ...p, enabled } : p))
+        );
+      } catch (error) {
+        console.error("Failed to update sound pack:", error);
+      }
+    },
+    []
+  );
+
+  const uninstallPack = useCallback(async (packId: string) => {
+    if (!isTauri) return;
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      await invoke("uninstall_sound_pack", { packId });
+      setPacks((prev) => prev.filter((p) => p.manifest.id !== packId));
+    } catch (error) {
+      console.error("Failed to uninstall sound pack:", error);
+    }
+  }, []);
+
+  return { packs, installPack, setPackEnabled, uninstallPack };
+}
@@ -8,6 +8,7 @@ import type {
   TimerMode,
 } from "@/types/timer";
 import { MAX_SESSION_HISTORY } from "@/constants/timer";
+import { reportSessionEvent } from "@/lib/webhooks";
 
 // Tauri Store 설정
 const STORE_FILE = "timer-sessions.json";
@@ -91,11 +92,12 @@ export function useTimerSessions(): UseTimerSessionsReturn {
       try {
         if (isTauri) {
           // Tauri Store 사용
-          const { load } = await import("@tauri-apps/plugin-store");
-          const store = await load(STORE_FILE, {
-            autoSave: true,
-            defaults: {},
-          });
+          // 웹뷰는 경로로 스토어를 열 수 없다. Rust가 시작할 때 열어 둔다 (src-tauri/src/protected.rs)
+          const { getStore } = await import("@tauri-apps/plugin-store");
+          const store = await getStore(STORE_FILE);
+          if (!store) {
+            throw new Error(`${STORE_FILE} is not open`);
+          }
           storeRef.current = store;
 
           const saved = await store.get<TimerSession[]>(STORE_KEY);
@@ -145,12 +147,16 @@ export function useTimerSessions(): UseTimerSessionsReturn {
     saveSessions();
   }, [sessions, isLoaded]);
 
-  // 세션 추가
+  // 세션 추가 (웹훅으로 완료/미완료 이벤트 전송)
   const addSession = useCallback((sessionData: Omit<TimerSession, "id">) => {
     const newSession: TimerSession = {
       ...sessionData,
       id: crypto.randomUUID(),
     };
+    reportSessionEvent(
+      newSession.completed ? "completed" : "abandoned",
+      newSession
+    );
 
     setSessions((prev) => {
       const updated = [newSession, ...prev];
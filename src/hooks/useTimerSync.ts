@@ -0,0 +1,41 @@
+"use client";
+
+import { useEffect } from "react";
+import type { TimerStatus } from "@/types/timer";
+
+const isTauri =
+  typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
+
+/**
+ * useTimerSync - 타이머 상태를 Rust에 동기화하는 훅
+ * 창 밖(명령줄 --status 등)에서 현재 타이머를 조회할 수 있게 한다.
+ */
+export function useTimerSync(status: TimerStatus) {
+  const { mode, targetSeconds, currentSeconds, isRunning, isPaused } = status;
+  const { preset, task } = status;
+
+  useEffect(() => {
+    if (!isTauri) return;
+
+    const syncTimer = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke("sync_timer_state", {
+          status: {
+            mode,
+            targetSeconds,
+            currentSeconds,
+            isRunning,
+            isPaused,
+            preset,
+            task,
+          },
+        });
+      } catch (error) {
+        console.error("Failed to sync timer state:", error);
+      }
+    };
+
+    syncTimer();
+  }, [mode, targetSeconds, currentSeconds, isRunning, isPaused, preset, task]);
+}
@@ -0,0 +1,91 @@
+"use client";
+
+import { useState, useEffect, useCallback } from "react";
+import type { Webhook, WebhookInfo } from "@/types/webhook";
+
+const isTauri =
+  typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
+
+/**
+ * useWebhooks - 세션 이벤트 웹훅 목록을 관리하는 훅
+ *
+ * 전송, 서명, 재시도(outbox)는 Rust `webhooks` 모듈이 처리한다 (docs/06-webhooks.md).
+ * 웹 환경에서는 항상 빈 목록이다.
+ *
+ * @returns webhooks - 웹훅 목록 (재시도 대기 수 포함)
+ * @returns addWebhook - URL 추가 (서명 키가 생성됨, 실패 시 reject)
+ * @returns removeWebhook - 웹훅 삭제 (대기 중인 전송도 삭제)
+ * @returns setWebhookEnabled - 웹훅 활성화/비활성화
+ * @returns sendTest - ping 이벤트 전송
+ * @returns refresh - 목록 다시 불러오기
+ */
+export function useWebhooks() {
+  const [webhooks, setWebhooks] = useState<WebhookInfo[]>([]);
+
+  const refresh = useCallback(async () => {
+    if (!isTauri) return;
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      setWebhooks(await invoke<WebhookInfo[]>("list_webhooks"));
+    } catch (error) {
+      console.error("Failed to load webhooks:", error);
+    }
+  }, []);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const addWebhook = useCallback(async (url: string): Promise<Webhook> => {
+    const { invoke } = await import("@tauri-apps/api/core");
+    const webhook = await invoke<Webhook>("add_webhook", { url });
+    setWebhooks((prev) => [...prev, { ...webhook, pending: 0 }]);
+    return webhook;
+  }, []);
+
+  const removeWebhook = useCallback(async (id: string) => {
+    if (!isTauri) return;
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      await invoke("remove_webhook", { id });
+      setWebhooks((prev) => prev.filter((w) => w.id !== id));
+    } catch (error) {
+      console.error("Failed to remove webhook:", error);
+    }
+  }, []);
+
+  const setWebhookEnabled = useCallback(
+    async (id: string, enabled: boolean) => {
+      if (!isTauri) return;
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke("set_webhook_enabled", { id, enabled });
+        setWebhooks((prev) =>
+          prev.map((w) => (w.id === id ? { ...w, enabled } : w))
+        );
+      } catch (error) {
+        console.error("Failed to update webhook:", error);
+      }
+    },
+    []
+  );
+
+  const sendTest = useCallback(async (id: string) => {
+    if (!isTauri) return;
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      await invoke("send_test_webhook", { id });
+    } catch (error) {
+      console.error("Failed to send test webhook:", error);
+    }
+  }, []);
+
+  return {
+    webhooks,
+    addWebhook,
+    removeWebhook,
+    setWebhookEnabled,
+    sendTest,
+    refresh,
+  };
+}
@@ -4,8 +4,10 @@
  * 모든 PostHog 이벤트를 중앙 관리합니다.
  * 이벤트 추가/수정 시 이 파일만 수정하면 됩니다.
  *
- * Note: tauri-plugin-posthog has permission bugs, so we use posthog-js directly
- * in both web and Tauri environments. posthog-js works fine in Tauri's webview.
+ * 웹은 posthog-js로 직접 보내고, 데스크톱은 Rust의 posthog 플러그인을 거칩니다.
+ * 데스크톱에서는 사용자가 트레이의 "익명 사용 데이터 공유"에 동의하지 않았으면
+ * Rust가 이벤트를 버리고, 동의했으면 디스크 큐에 넣어 모아서 보냅니다
+ * (src-tauri/src/analytics/). 오프라인에서 기록한 이벤트도 나중에 전송됩니다.
  *
  * @example
  * import { analytics } from "@/lib/analytics";
@@ -31,51 +33,42 @@ export const analyticsState = {
   error: null as string | null,
 };
 
-// Initialize PostHog for Tauri environment (web is initialized in providers.tsx)
+// 데스크톱은 Rust가 동의 여부와 키를 확인하므로 여기서는 초기화하지 않음
 if (typeof window !== "undefined" && isTauri) {
-  const key = process.env.NEXT_PUBLIC_POSTHOG_KEY;
-  const host = process.env.NEXT_PUBLIC_POSTHOG_HOST;
-
-  if (key) {
-    if (process.env.NODE_ENV === "development") {
-      info(
-        `[Analytics] Initializing PostHog for Tauri... Host: ${
-          host || "default"
-        }`
-      ).catch(console.error);
-    }
-    try {
-      posthog.init(key, {
-        api_host: host || "https://app.posthog.com",
-        person_profiles: "identified_only",
-        persistence: "localStorage",
-        autocapture: false, // Disable automatic click/input tracking
-        capture_pageview: false, // Disable automatic pageviews for desktop app
-        capture_pageleave: false, // Disable automatic page leave tracking
-        loaded: (ph) => {
-          analyticsState.isInitialized = true;
-          if (process.env.NODE_ENV === "development") {
-            const msg = `[Analytics] PostHog initialized for Tauri. ID: ${ph.get_distinct_id()}`;
-            console.log(msg);
-            info(msg).catch(console.error);
-          }
-        },
-      });
-    } catch (e) {
-      const errMsg = `[Analytics] Failed to initialize PostHog: ${e}`;
-      console.error(errMsg);
-      error(errMsg).catch(console.error);
-      analyticsState.error = String(e);
-    }
+  if (process.env.NEXT_PUBLIC_POSTHOG_KEY) {
+    analyticsState.isInitialized = true;
   } else {
-    const errMsg =
-      "[Analytics] FATAL: NEXT_PUBLIC_POSTHOG_KEY not found for Tauri environment";
-    console.error(errMsg);
-    error(errMsg).catch(console.error);
     analyticsState.error = "Missing API Key";
   }
 }
 
+/**
+ * 이벤트 전송. 데스크톱은 posthog 플러그인의 큐에 넣음
+ */
+function capture(
+  eventName: string,
+  properties: Record<string, unknown>,
+  options?: { transport?: "sendBeacon" }
+) {
+  if (!isTauri) {
+    posthog.capture(eventName, properties, options);
+    return;
+  }
+  if (analyticsState.error) return;
+  // 한 번 실패해도 다음 이벤트는 계속 보냄 (일시적인 실패로 세션 내내 끊기지 않도록)
+  import("@tauri-apps/api/core")
+    .then(({ invoke }) =>
+      invoke("plugin:posthog|capture", {
+        request: { event: eventName, properties },
+      })
+    )
+    .catch((e) => {
+      const errMsg = `[Analytics] Failed to capture ${eventName}: ${e}`;
+      console.error(errMsg);
+      error(errMsg).catch(console.error);
+    });
+}
+
 // Debug: Log which analytics backend is being used (dev only)
 if (typeof window !== "undefined" && process.env.NODE_ENV === "development") {
   const envMsg = `[Analytics] Environment: ${
@@ -148,7 +141,7 @@ export function trackEvent(
     console.log(`[Analytics] ${eventName}`, properties);
   }
 
-  posthog.capture(eventName, {
+  capture(eventName, {
     ...properties,
     timestamp: new Date().toISOString(),
     platform: isTauri ? "desktop" : "web",
@@ -171,7 +164,7 @@ export const analytics = {
       platform: isTauri ? "desktop" : "web",
     };
 
-    posthog.capture(EVENTS.PLAYBACK_SESSION_START, properties);
+    capture(EVENTS.PLAYBACK_SESSION_START, properties);
   },
 
   /**
@@ -195,7 +188,7 @@ export const analytics = {
       platform: isTauri ? "desktop" : "web",
     };
 
-    posthog.capture(EVENTS.PLAYBACK_SESSION_END, properties, options);
+    capture(EVENTS.PLAYBACK_SESSION_END, properties, options);
   },
 
   /**
@@ -215,7 +208,7 @@ export const analytics = {
       platform: isTauri ? "desktop" : "web",
     };
 
-    posthog.capture(EVENTS.PLAYBACK_HEARTBEAT, properties);
+    capture(EVENTS.PLAYBACK_HEARTBEAT, properties);
   },
 
   /**
@@ -234,7 +227,7 @@ export const analytics = {
       platform: isTauri ? "desktop" : "web",
     };
 
-    posthog.capture(EVENTS.SOUND_TOGGLED, properties);
+    capture(EVENTS.SOUND_TOGGLED, properties);
   },
 };
 
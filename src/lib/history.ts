@@ -0,0 +1,20 @@
+/**
+ * 청취 기록 모듈
+ *
+ * 재생 세션(시작/끝, 재생한 사운드와 볼륨)은 Rust `history` 모듈이
+ * 이 기기에만 기록한다 (docs/10-listening-history.md).
+ * 분석 공유 동의와 상관없이 항상 기록되고, 밖으로 보내지 않는다.
+ */
+
+import type { ListeningPeriod, ListeningStats } from "@/types/audio";
+
+/**
+ * 기간별 청취 시간과 많이 들은 사운드
+ * 기간은 로컬 시간 기준 (주는 월요일부터)
+ */
+export async function getListeningStats(
+  period: ListeningPeriod
+): Promise<ListeningStats> {
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ListeningStats>("get_listening_stats", { period });
+}
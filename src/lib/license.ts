@@ -1,143 +1,77 @@
 /**
- * LemonSqueezy 라이센스 검증 모듈
+ * 라이센스 모듈
+ *
+ * LemonSqueezy 활성화/검증/해제는 Rust `license` 모듈이 처리하고,
+ * 라이센스 키는 웹뷰에서 접근할 수 없는 파일에 저장된다 (docs/01-pricing-and-licensing.md).
+ * 상태가 바뀌면 Rust가 LICENSE_EVENT 이벤트를 보낸다.
  */
 
-import type { LicenseValidationResponse } from "@/types/pro";
+import type { LicenseActivations, LicenseStatus } from "@/types/pro";
 
-const LEMONSQUEEZY_API_URL = "https://api.lemonsqueezy.com/v1/licenses";
-
-// 어드민 테스트 키 (환경변수로 관리)
-const ADMIN_LICENSE_KEY = process.env.NEXT_PUBLIC_ADMIN_LICENSE_KEY || "";
+export const LICENSE_EVENT = "license-changed";
 
 /**
- * 라이센스 키 검증
- * @param licenseKey - 검증할 라이센스 키
- * @returns 검증 결과
+ * 현재 라이센스 상태 (저장된 키 검증 중이면 isValidating)
  */
-export async function validateLicense(
-  licenseKey: string
-): Promise<LicenseValidationResponse> {
-  try {
-    const response = await fetch(`${LEMONSQUEEZY_API_URL}/validate`, {
-      method: "POST",
-      headers: {
-        "Content-Type": "application/json",
-        Accept: "application/json",
-      },
-      body: JSON.stringify({
-        license_key: licenseKey,
-      }),
-    });
-
-    const data = await response.json();
-    return data as LicenseValidationResponse;
-  } catch (error) {
-    console.error("License validation error:", error);
-    return {
-      valid: false,
-      error: "Network error during license validation",
-    };
-  }
+export async function getLicenseStatus(): Promise<LicenseStatus> {
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LicenseStatus>("get_license_status");
 }
 
 /**
  * 라이센스 키 활성화 (기기 등록)
  * @param licenseKey - 활성화할 라이센스 키
- * @param instanceName - 기기 이름 (옵션)
- * @returns 활성화 결과
+ * @returns 활성화 후 상태 (실패 시 오류 메시지로 reject)
  */
 export async function activateLicense(
-  licenseKey: string,
-  instanceName?: string
-): Promise<LicenseValidationResponse> {
-  // 어드민 키 허용
-  if (licenseKey === ADMIN_LICENSE_KEY) {
-    return {
-      valid: true,
-      license_key: {
-        id: 0,
-        status: "active",
-        key: ADMIN_LICENSE_KEY,
-        activation_limit: 999,
-        activation_usage: 1,
-        created_at: new Date().toISOString(),
-        expires_at: null,
-      },
-      error: null,
-    };
-  }
+  licenseKey: string
+): Promise<LicenseStatus> {
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LicenseStatus>("activate_license", { key: licenseKey });
+}
 
-  try {
-    const response = await fetch(`${LEMONSQUEEZY_API_URL}/activate`, {
-      method: "POST",
-      headers: {
-        "Content-Type": "application/json",
-        Accept: "application/json",
-      },
-      body: JSON.stringify({
-        license_key: licenseKey,
-        instance_name: instanceName || `ZeroHz-${Date.now()}`,
-      }),
-    });
+/**
+ * 라이센스 키 비활성화 (기기 해제 후 키 삭제)
+ * 라이센스 서버에 연결할 수 없으면 키를 유지하고 reject
+ */
+export async function deactivateLicense(): Promise<LicenseStatus> {
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LicenseStatus>("deactivate_license");
+}
 
-    const data = await response.json();
-    return data as LicenseValidationResponse;
-  } catch (error) {
-    console.error("License activation error:", error);
-    return {
-      valid: false,
-      error: "Network error during license activation",
-    };
-  }
+/**
+ * 저장된 키의 활성화 현황 (사용 중인 기기 수, 현재 기기)
+ * 다른 기기 목록은 License API가 주지 않는다
+ * 사이트 라이센스는 기기별 활성화가 없어 reject
+ */
+export async function getLicenseActivations(): Promise<LicenseActivations> {
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LicenseActivations>("get_license_activations");
 }
 
 /**
- * 라이센스 키 비활성화 (기기 해제)
- * @param licenseKey - 비활성화할 라이센스 키
- * @param instanceId - 해제할 기기 인스턴스 ID
- * @returns 비활성화 결과
+ * 다른 기기의 활성화 해제 (자리 확보)
+ * 현재 기기는 deactivateLicense로 해제한다
+ * @param instanceId - 해제할 기기의 instance ID
  */
-export async function deactivateLicense(
-  licenseKey: string,
+export async function deactivateLicenseDevice(
   instanceId: string
-): Promise<{ deactivated: boolean; error: string | null }> {
-  try {
-    const response = await fetch(`${LEMONSQUEEZY_API_URL}/deactivate`, {
-      method: "POST",
-      headers: {
-        "Content-Type": "application/json",
-        Accept: "application/json",
-      },
-      body: JSON.stringify({
-        license_key: licenseKey,
-        instance_id: instanceId,
-      }),
-    });
-
-    const data = await response.json();
-    return {
-      deactivated: data.deactivated === true,
-      error: data.error || null,
-    };
-  } catch (error) {
-    console.error("License deactivation error:", error);
-    return {
-      deactivated: false,
-      error: "Network error during license deactivation",
-    };
-  }
+): Promise<void> {
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke("deactivate_license_device", { instanceId });
 }
 
 /**
  * 라이센스 키 형식 검증 (기본 체크)
+ * LemonSqueezy 키 또는 사이트 라이센스 토큰 (서명은 Rust가 검증)
  * @param licenseKey - 검증할 라이센스 키
  * @returns 형식이 유효한지 여부
  */
 export function isValidLicenseKeyFormat(licenseKey: string): boolean {
   const trimmed = licenseKey.trim();
 
-  // 어드민 키 허용
-  if (trimmed === ADMIN_LICENSE_KEY) {
+  // 사이트 라이센스 토큰 (예: zerohz1.<payload>.<signature>)
+  if (/^zerohz1\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+$/.test(trimmed)) {
     return true;
   }
 
@@ -0,0 +1,14 @@
+import { Music, icons } from "lucide-react";
+
+/**
+ * 사운드 카탈로그의 kebab-case 아이콘 이름("cloud-rain")을 Lucide 컴포넌트로 변환
+ * 모르는 이름이면 기본 아이콘(Music)
+ */
+export function soundIcon(name?: string): React.ElementType {
+  if (!name) return Music;
+  const key = name
+    .split("-")
+    .map((part) => part.charAt(0).toUpperCase() + part.slice(1))
+    .join("") as keyof typeof icons;
+  return icons[key] ?? Music;
+}
@@ -0,0 +1,20 @@
+/**
+ * 사용량 모듈
+ *
+ * Free 플레이타임과 타이머 트라이얼은 Rust `usage` 모듈이 집계한다
+ * (docs/01-pricing-and-licensing.md). 재생 시간은 Rust가 직접 세고,
+ * 타이머 완료는 report_session_event로 전달된다.
+ * 사용량이 바뀌면 Rust가 USAGE_EVENT 이벤트를 보낸다.
+ */
+
+import type { Usage } from "@/types/pro";
+
+export const USAGE_EVENT = "usage-changed";
+
+/**
+ * 오늘 사용량
+ */
+export async function getUsage(): Promise<Usage> {
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<Usage>("get_usage");
+}
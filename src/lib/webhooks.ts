@@ -0,0 +1,23 @@
+import type { TimerSession } from "@/types/timer";
+import type { SessionEvent } from "@/types/webhook";
+
+const isTauri =
+  typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
+
+/**
+ * 세션 이벤트를 Rust에 전달 (웹훅 발송, 셸 훅 실행)
+ * 실제 전송, 서명, 재시도와 훅 실행은 Rust가 처리하므로 결과를 기다리지 않는다.
+ * 시작 이벤트는 아직 id, 종료 시간이 없다.
+ */
+export function reportSessionEvent(
+  event: SessionEvent,
+  session: Omit<TimerSession, "id" | "endedAt"> &
+    Partial<Pick<TimerSession, "id" | "endedAt">>
+) {
+  if (!isTauri) return;
+  import("@tauri-apps/api/core")
+    .then(({ invoke }) => invoke("report_session_event", { event, session }))
+    .catch((error) => {
+      console.error("Failed to report session event:", error);
+    });
+}
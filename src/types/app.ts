@@ -1,4 +1,4 @@
-import type { SoundType, SoundVolumes } from "./audio";
+import type { SoundEffects, SoundType, SoundVolumes } from "./audio";
 
 /**
  * 앱 설정 타입
@@ -7,6 +7,7 @@ export interface AppSettings {
   // 오디오 설정
   activeSounds: SoundType[];
   volumes: SoundVolumes;
+  effects: SoundEffects;
   isMuted: boolean;
 
   // 언어 설정
@@ -18,20 +19,20 @@ export interface AppSettings {
  */
 export const DEFAULT_APP_SETTINGS: AppSettings = {
   activeSounds: [],
-  volumes: {
-    rain: 0.5,
-    wind: 0.5,
-    waves: 0.5,
-    forest: 0.5,
-    stream: 0.5,
-    fire: 0.5,
-    flight: 0.5,
-    train: 0.5,
-    night: 0.5,
-    keyboard: 0.5,
-    thunder: 0.5,
-    "glass-fruit": 0.5,
-  },
+  // 사운드별 값은 처음 켤 때 DEFAULT_VOLUME / DEFAULT_EFFECT로 채워짐
+  volumes: {},
+  effects: {},
   isMuted: false,
   language: "en",
 };
+
+/**
+ * 트레이 메뉴나 zerohz:// 링크로 열 수 있는 패널 (Rust `open-<panel>` 이벤트)
+ */
+export type RemotePanel =
+  | "session-history"
+  | "license-input"
+  | "usage"
+  | "audio-output"
+  | "sound-packs"
+  | "webhooks";
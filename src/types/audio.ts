@@ -1,29 +1,181 @@
-export type SoundType =
-  | "rain"
-  | "wind"
-  | "waves"
-  | "forest"
-  | "stream"
-  | "fire"
-  | "flight"
-  | "train"
-  | "night"
-  | "keyboard"
-  | "thunder"
-  | "glass-fruit";
+/**
+ * 사용자가 가져온 사운드 ID (Rust `import_sound`가 `custom-` 접두사로 발급)
+ */
+export type CustomSoundId = `custom-${string}`;
+
+/**
+ * 사운드 팩의 사운드 ID (`<팩 id>.<사운드 id>`)
+ */
+export type PackSoundId = `${string}.${string}`;
+
+/**
+ * 사운드 ID. 목록은 Rust 카탈로그(`list_sounds`)가 관리한다
+ * - 내장 사운드: "rain", "glass-fruit" 등
+ * - 가져온 사운드: CustomSoundId
+ * - 팩 사운드: PackSoundId
+ */
+export type SoundType = string;
 
 export interface SoundControl {
   id: SoundType;
   icon: React.ElementType;
   label: string;
-  src: string;
+  src: string; // 웹 폴백 재생용 경로 (파일 에셋은 asset 프로토콜 URL)
+  category?: SoundCategory;
+  isCustom?: boolean;
+}
+
+export type SoundCategory = "nature" | "life" | "places" | "work";
+
+export type SoundSource =
+  | { type: "builtin" }
+  | { type: "custom" }
+  | { type: "pack"; packId: string };
+
+export type SoundAsset =
+  | { kind: "bundled"; path: string } // 프론트엔드 에셋 경로 ("/sounds/rain.m4a")
+  | { kind: "file"; path: string }; // 앱 데이터 폴더의 절대 경로
+
+/**
+ * Rust 사운드 카탈로그 항목 (`list_sounds`)
+ */
+export interface CatalogSound {
+  id: SoundType;
+  label: string;
+  icon: string; // Lucide 아이콘 이름 (kebab-case)
+  category: SoundCategory | null;
+  source: SoundSource;
+  asset: SoundAsset;
+}
+
+/**
+ * 사운드 팩 manifest.json (docs/02-sound-packs.md)
+ */
+export interface PackSound {
+  id: string;
+  label: string;
+  file: string;
+  icon?: string;
+  category?: SoundCategory;
+  loop?: { start: number; end?: number };
+  license?: string;
+  attribution?: string;
+}
+
+export interface PackManifest {
+  schemaVersion: number;
+  id: string;
+  name: string;
+  version: string;
+  author?: string;
+  description?: string;
+  license: string;
+  sounds: PackSound[];
+}
+
+export interface InstalledPack {
+  manifest: PackManifest;
+  enabled: boolean;
+  installedAt: number; // Unix ms
+}
+
+export interface CustomSound {
+  id: CustomSoundId;
+  label: string;
+  file: string;
+  duration: number; // 초
+  importedAt: number; // Unix ms
 }
 
+/**
+ * 창 밖에서 온 재생 명령 (Rust `playback-command` 이벤트)
+ * - pause/play: 음소거 켜기/끄기 (선택한 사운드는 유지)
+ * - stop: 모든 사운드 끄기
+ * - scene: 지정한 사운드만 재생 (음소거 해제)
+ * - sound: 사운드 하나를 켜기/끄기 또는 볼륨(0-1) 설정 (null이면 그대로 둠)
+ */
+export type PlaybackCommand =
+  | { type: "play" }
+  | { type: "pause" }
+  | { type: "play-pause" }
+  | { type: "stop" }
+  | { type: "scene"; sounds: SoundType[] }
+  | {
+      type: "sound";
+      sound: SoundType;
+      on: boolean | null;
+      volume: number | null;
+    };
+
 export type SoundVolumes = Record<SoundType, number>;
 
+/**
+ * 사운드별 음향 효과 (Rust DSP 체인에서 처리)
+ */
+export interface SoundEffect {
+  pan: number; // -1 (왼쪽) ~ 1 (오른쪽)
+  lowpass: number; // 로우패스 컷오프 (Hz, 20000 = 끔)
+}
+
+export type SoundEffects = Record<SoundType, SoundEffect>;
+
+/**
+ * 볼륨 오토메이션 (세션 시간에 따른 볼륨 곡선, Rust에서 평가)
+ */
+export type AutomationCurve = "linear" | "exponential";
+
+export interface AutomationKeyframe {
+  time: number; // 세션 시간 (초)
+  value: number; // 볼륨 슬라이더에 곱해지는 배율 (0~1)
+  curve?: AutomationCurve; // 이전 키프레임에서 이 키프레임까지의 곡선
+}
+
+/**
+ * 이벤트 레이어 (루프 대신 랜덤 간격으로 재생되는 원샷 사운드, 예: 천둥)
+ */
+export interface EventLayer {
+  interval: number; // 평균 재생 간격 (초)
+  jitter: number; // 간격 변동폭 (0~1, interval 대비 비율)
+  duration: number; // 한 번에 재생할 길이 (초, 0 = 전체)
+  minGain: number;
+  maxGain: number;
+  panSpread: number; // 좌우 랜덤 배치 범위 (0~1)
+}
+
+/**
+ * 오디오 출력 장치 (Rust에서 이름으로 식별)
+ */
+export interface OutputDevice {
+  name: string;
+  isDefault: boolean;
+}
+
+export interface AudioOutputStatus {
+  selected: string | null; // 사용자가 고른 장치 (null = 시스템 기본값 따름)
+  active: string | null; // 실제로 출력 중인 장치
+  defaultDevice: string | null;
+}
+
 export type AudioRefs = {
   audioContext: AudioContext | null;
   gainNodes: Record<SoundType, GainNode | null>;
   sourceNodes: Record<SoundType, AudioBufferSourceNode | null>;
   audioBuffers: Record<SoundType, AudioBuffer | null>;
 };
+
+/**
+ * 기간별 청취 기록 (Rust `history` 모듈, docs/10-listening-history.md)
+ */
+export type ListeningPeriod = "today" | "week" | "month" | "all";
+
+export interface SoundListeningTime {
+  sound: SoundType;
+  seconds: number;
+}
+
+export interface ListeningStats {
+  totalSeconds: number;
+  sessionCount: number;
+  longestSessionSeconds: number;
+  sounds: SoundListeningTime[]; // 많이 들은 순. 함께 재생한 사운드는 각각 전체 시간으로 센다
+}
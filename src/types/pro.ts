@@ -5,7 +5,7 @@
 // Pro 상태
 export interface ProState {
   isPro: boolean;
-  licenseKey: string | null;
+  keyHint: string | null;
   isValidating: boolean;
 }
 
@@ -18,48 +18,44 @@ export interface FreeLimits {
   timerTrialRemaining: number; // 남은 타이머 트라이얼 횟수
 }
 
-// 라이센스 검증 응답 (LemonSqueezy API)
-export interface LicenseValidationResponse {
-  valid: boolean;
-  error: string | null;
-  license_key?: {
-    id: number;
-    status: "active" | "inactive" | "expired" | "disabled";
-    key: string;
-    activation_limit: number;
-    activation_usage: number;
-    created_at: string;
-    expires_at: string | null;
-  };
-  instance?: {
-    id: string;
-    name: string;
-    created_at: string;
-  };
-  meta?: {
-    store_id: number;
-    product_id: number;
-    product_name: string;
-    variant_id: number;
-    variant_name: string;
-    customer_id: number;
-    customer_name: string;
-    customer_email: string;
-  };
+// 라이센스 상태 (Rust `license` 모듈)
+export interface LicenseStatus {
+  isPro: boolean;
+  keyHint: string | null; // 저장된 키의 마지막 4자리 (예: "…4532")
+  licensee: string | null; // 사이트 라이센스 발급 대상
+  isValidating: boolean; // 저장된 키 검증 중
+  offlineUntil: number | null; // 서버 연결 불가로 유예 중일 때 유예 만료 시각 (ms)
+}
+
+// 이 기기의 라이센스 활성화 (LemonSqueezy instance)
+export interface LicenseDevice {
+  id: string;
+  name: string;
+  createdAt: string | null;
+}
+
+// 라이센스 활성화 현황
+// LemonSqueezy License API는 다른 기기의 수만 알려주고 목록은 주지 않는다
+export interface LicenseActivations {
+  activationUsage: number;
+  activationLimit: number | null; // 무제한이면 null
+  thisDevice: LicenseDevice | null; // 이전 버전에서 옮겨온 키는 null
 }
 
 // Pro 업그레이드 모달 이유
 export type UpgradeReason = "playtime" | "timer";
 
-// 저장되는 Pro 설정 데이터
-export interface ProSettingsData {
-  licenseKey: string | null;
-  dailyPlaytimeUsed: number;
-  lastPlayDate: string;
-  timerTrialUsed: number;
+// 오늘 Free 사용량 (Rust `usage` 모듈)
+// 날짜는 로컬 자정에 바뀌고, 제한 값도 Rust가 정한다
+export interface Usage {
+  playtimeSeconds: number;
+  playtimeLimitSeconds: number;
+  timerTrials: number;
+  timerTrialLimit: number;
 }
 
 // 상수 (개발 모드에서는 짧은 값 사용)
+// 데스크톱 앱은 Rust의 Usage 값을 쓰고, 이 값은 웹 환경에서만 쓴다
 const IS_DEV = process.env.NODE_ENV === "development";
 export const FREE_DAILY_PLAYTIME_LIMIT = IS_DEV ? 1 * 60 * 60 : 2 * 60 * 60; // 개발: 1시간, 프로덕션: 2시간
 export const FREE_TIMER_TRIAL_COUNT = IS_DEV ? 5 : 3; // 개발: 5회, 프로덕션: 3회
@@ -14,6 +14,31 @@ export interface TimerState {
   completedAt: number | null; // 완료 타임스탬프
 }
 
+/**
+ * Rust에 동기화되는 타이머 상태 (`sync_timer_state`, 명령줄 --status 등에서 사용)
+ */
+export interface TimerStatus {
+  mode: TimerMode;
+  targetSeconds: number;
+  currentSeconds: number;
+  isRunning: boolean;
+  isPaused: boolean;
+  preset?: string; // 카운트다운을 시작한 프리셋 ID
+  task?: string; // 작업 내용 설명
+}
+
+/**
+ * 창 밖에서 온 타이머 명령 (Rust `timer-command` 이벤트)
+ * - start: 프리셋으로 카운트다운 시작 (진행 중인 세션은 미완료로 기록)
+ * - pause/resume: 실행 중인 타이머 일시정지/재개
+ * - stop: 타이머 정지 및 리셋
+ */
+export type TimerCommand =
+  | { type: "start"; preset: string; task: string | null }
+  | { type: "pause" }
+  | { type: "resume" }
+  | { type: "stop" };
+
 export interface TimerPreset {
   id: string;
   label: string;
@@ -0,0 +1,27 @@
+/**
+ * Webhook Types - 세션 이벤트 웹훅 (docs/06-webhooks.md)
+ */
+
+export interface Webhook {
+  id: string;
+  url: string;
+  secret: string; // X-ZeroHz-Signature 서명 키
+  enabled: boolean;
+}
+
+export interface WebhookInfo extends Webhook {
+  pending: number; // 재시도 대기 중인 전송 수
+}
+
+/**
+ * 웹훅, 셸 훅으로 보내는 세션 이벤트
+ * - started: 타이머 시작
+ * - paused / resumed: 일시정지 / 재개
+ * - completed / abandoned: 세션 기록 시 (완료 / 미완료)
+ */
+export type SessionEvent =
+  | "started"
+  | "paused"
+  | "resumed"
+  | "completed"
+  | "abandoned";